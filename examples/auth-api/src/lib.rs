@@ -6,7 +6,7 @@
 //!
 //! Demonstrates real-world patterns:
 //! - API key authentication via headers
-//! - Protected routes with guard!/ensure! macros
+//! - Protected routes returning `Result<_, ApiError>` with `?`
 //! - Structured error handling
 //! - Logging patterns
 //! - Environment variable access
@@ -79,12 +79,12 @@ routes! {
 // PUBLIC HANDLERS
 // ============================================================================
 
-fn index(_req: &Request) -> Response {
-    ok!({
-        "name": "Auth API Example",
-        "version": "0.1.0",
-        "auth_required": ["/me", "/protected", "/logout"]
-    })
+fn index(_req: &Request) -> IndexResponse {
+    IndexResponse {
+        name: "Auth API Example".to_string(),
+        version: "0.1.0".to_string(),
+        auth_required: ["/me", "/protected", "/logout"].map(String::from).to_vec(),
+    }
 }
 
 fn health(_req: &Request) -> Response {
@@ -94,7 +94,7 @@ fn health(_req: &Request) -> Response {
 }
 
 /// Login endpoint - validates credentials and returns a token.
-fn login(body: LoginInput, _req: &Request) -> Response {
+fn login(body: LoginInput, _req: &Request) -> Result<LoginResponse, ApiError> {
     // Log login attempt (structured logging)
     log!(info, "login attempt", username: &body.username);
 
@@ -102,11 +102,7 @@ fn login(body: LoginInput, _req: &Request) -> Response {
     // In production: validate against database, use proper password hashing
     if body.password != "password123" {
         log!(warn, "login failed", username: &body.username, reason: "invalid_password");
-        return error! {
-            status: status::UNAUTHORIZED,
-            title: "Unauthorized",
-            detail: "Invalid username or password"
-        };
+        return Err(ApiError::unauthorized("Invalid username or password"));
     }
 
     // Generate a demo token (in production: use proper JWT library)
@@ -116,10 +112,7 @@ fn login(body: LoginInput, _req: &Request) -> Response {
 
     log!(info, "login success", username: &body.username);
 
-    ok!({
-        "token": token,
-        "expires_in": expires_in
-    })
+    Ok(LoginResponse { token, expires_in })
 }
 
 // ============================================================================
@@ -127,58 +120,44 @@ fn login(body: LoginInput, _req: &Request) -> Response {
 // ============================================================================
 
 /// Get current user's profile - requires authentication.
-fn get_profile(req: &Request) -> Response {
-    // Extract and validate token
-    let user = match authenticate(req) {
-        Ok(u) => u,
-        Err(response) => return response,
-    };
-
-    ok!({
-        "id": user.id,
-        "username": user.username,
-        "role": user.role
+fn get_profile(req: &Request) -> Result<UserProfile, ApiError> {
+    // Extract and validate token - `?` returns the 401 problem response
+    let user = authenticate(req)?;
+
+    Ok(UserProfile {
+        id: user.id,
+        username: user.username,
+        role: user.role,
     })
 }
 
-/// Access protected resource - demonstrates guard! and ensure! macros.
-fn protected_resource(req: &Request) -> Response {
-    // Method 1: Using authenticate helper (recommended)
-    let user = match authenticate(req) {
-        Ok(u) => u,
-        Err(response) => return response,
-    };
-
-    // Method 2: Using guard! for additional checks
-    // guard! returns early with error if condition is false
-    guard!(
-        user.role == "admin" || user.role == "user",
-        403,
-        "Insufficient permissions"
-    );
+/// Access protected resource - demonstrates authorization checks.
+fn protected_resource(req: &Request) -> Result<ProtectedData, ApiError> {
+    let user = authenticate(req)?;
+
+    // Additional checks return early with their own problem response
+    if user.role != "admin" && user.role != "user" {
+        return Err(ApiError::forbidden("Insufficient permissions"));
+    }
 
     // Log access to protected resource
     log!(info, "protected access", user_id: &user.id, resource: "protected_data");
 
-    let accessed_at = time::now_iso();
-    ok!({
-        "message": "You have accessed the protected resource!",
-        "user_id": user.id,
-        "accessed_at": accessed_at
+    Ok(ProtectedData {
+        message: "You have accessed the protected resource!".to_string(),
+        user_id: user.id,
+        accessed_at: time::now_iso(),
     })
 }
 
 /// Logout - invalidate token (demo only, tokens are stateless here).
-fn logout(req: &Request) -> Response {
-    let user = match authenticate(req) {
-        Ok(u) => u,
-        Err(response) => return response,
-    };
+fn logout(req: &Request) -> Result<Response, ApiError> {
+    let user = authenticate(req)?;
 
     log!(info, "logout", user_id: &user.id);
 
     // In production: add token to blacklist or use short-lived tokens with refresh
-    no_content!()
+    Ok(no_content!())
 }
 
 // ============================================================================
@@ -194,35 +173,23 @@ struct AuthUser {
 
 /// Authenticate request using Bearer token.
 ///
-/// Returns `Ok(AuthUser)` if valid, `Err(Response)` with error otherwise.
-fn authenticate(req: &Request) -> Result<AuthUser, Response> {
+/// Returns `Ok(AuthUser)` if valid, a 401 `ApiError` otherwise.
+fn authenticate(req: &Request) -> Result<AuthUser, ApiError> {
     // Get Authorization header
     let auth_header = req.header_or("authorization", "");
     if auth_header.is_empty() {
-        return Err(error! {
-            status: status::UNAUTHORIZED,
-            title: "Unauthorized",
-            detail: "Missing Authorization header"
-        });
+        return Err(ApiError::unauthorized("Missing Authorization header"));
     }
 
     // Check Bearer scheme
     let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
-        error! {
-            status: status::UNAUTHORIZED,
-            title: "Unauthorized",
-            detail: "Invalid Authorization scheme, expected 'Bearer <token>'"
-        }
+        ApiError::unauthorized("Invalid Authorization scheme, expected 'Bearer <token>'")
     })?;
 
     // Validate token (demo: decode and check format)
     let user = validate_demo_token(token).ok_or_else(|| {
         log!(warn, "invalid token", token_prefix: &token.chars().take(10).collect::<String>());
-        error! {
-            status: status::UNAUTHORIZED,
-            title: "Unauthorized",
-            detail: "Invalid or expired token"
-        }
+        ApiError::unauthorized("Invalid or expired token")
     })?;
 
     Ok(user)
@@ -90,6 +90,33 @@ pub struct UpdateUserInput {
 pub struct IndexResponse {
     pub name: String,
     pub version: String,
+    pub endpoints: Vec<String>,
+}
+
+/// Generated SQL, returned so the demo shows what would run.
+#[derive(Type)]
+pub struct QueryDebug {
+    pub sql: String,
+    pub param_count: Option<usize>,
+    pub note: Option<String>,
+}
+
+impl QueryDebug {
+    const fn sql(sql: String) -> Self {
+        Self {
+            sql,
+            param_count: None,
+            note: None,
+        }
+    }
+
+    const fn with_params(sql: String, param_count: usize) -> Self {
+        Self {
+            sql,
+            param_count: Some(param_count),
+            note: None,
+        }
+    }
 }
 
 #[derive(Type)]
@@ -97,16 +124,35 @@ pub struct User {
     pub id: String,
     pub name: String,
     pub email: String,
+    pub status: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[field(rename = "_debug")]
+    pub debug: Option<QueryDebug>,
+}
+
+impl User {
+    fn new(id: &str, name: &str, email: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            email: email.to_string(),
+            status: None,
+            created_at: None,
+            updated_at: None,
+            debug: None,
+        }
+    }
 }
 
 #[derive(Type)]
 pub struct UserListResponse {
     pub users: Vec<User>,
-    pub page: i64,
-    pub limit: i64,
-    pub total: i64,
+    pub page: u32,
+    pub limit: u32,
+    pub total: usize,
+    #[field(rename = "_debug")]
+    pub debug: Option<QueryDebug>,
 }
 
 #[derive(Type)]
@@ -122,6 +168,8 @@ pub struct PostListResponse {
     pub has_next: bool,
     pub has_prev: bool,
     pub next_cursor: Option<String>,
+    #[field(rename = "_debug")]
+    pub debug: Option<QueryDebug>,
 }
 
 // ============================================================================
@@ -133,6 +181,7 @@ routes! {
 
     // Users - list, search, create
     GET  "/users" => list_users(query: ListUsersQuery) -> UserListResponse,
+    #[status(201)]
     POST "/users" => create_user(body: CreateUserInput) -> User,
     POST "/users/search" => search_users(query: SearchUsersQuery) -> UserListResponse,
 
@@ -148,25 +197,30 @@ routes! {
 // ============================================================================
 // HANDLERS
 // ============================================================================
-
-fn index(_req: &Request) -> Response {
-    ok!({
-        "name": "CRUD API Example",
-        "version": "0.1.0",
-        "endpoints": {
-            "list_users": "GET /users?page=1&limit=50",
-            "search_users": "POST /users/search?page=1&limit=20 (body: Mongo-style filter)",
-            "get_user": "GET /users/{id}",
-            "create_user": "POST /users",
-            "update_user": "PUT /users/{id}",
-            "delete_user": "DELETE /users/{id}",
-            "list_posts": "GET /posts?after={cursor}"
-        }
-    })
+//
+// Routes declaring `-> Type` return that type (or `Result<Type, ApiError>`);
+// routes! serializes it, so responses always match the OpenAPI schema.
+
+fn index(_req: &Request) -> IndexResponse {
+    IndexResponse {
+        name: "CRUD API Example".to_string(),
+        version: "0.1.0".to_string(),
+        endpoints: [
+            "GET /users?page=1&limit=50",
+            "POST /users/search?page=1&limit=20 (body: Mongo-style filter)",
+            "GET /users/{id}",
+            "POST /users",
+            "PUT /users/{id}",
+            "DELETE /users/{id}",
+            "GET /posts?after={cursor}",
+        ]
+        .map(String::from)
+        .to_vec(),
+    }
 }
 
 /// List users with offset-based pagination.
-fn list_users(query: ListUsersQuery, _req: &Request) -> Response {
+fn list_users(query: ListUsersQuery, _req: &Request) -> UserListResponse {
     // Generate SQL query for listing users (offset pagination)
     let (sql, params) = sql_read!(users {
         select: [id, name, email],
@@ -178,20 +232,16 @@ fn list_users(query: ListUsersQuery, _req: &Request) -> Response {
 
     // In production: execute query against database
     // For demo, return mock data with generated SQL
-    let param_count = params.len();
-    ok!({
-        "users": [
-            { "id": "1", "name": "Alice", "email": "alice@example.com" },
-            { "id": "2", "name": "Bob", "email": "bob@example.com" }
+    UserListResponse {
+        users: vec![
+            User::new("1", "Alice", "alice@example.com"),
+            User::new("2", "Bob", "bob@example.com"),
         ],
-        "page": query.page,
-        "limit": query.limit,
-        "total": 2,
-        "_debug": {
-            "sql": sql,
-            "param_count": param_count
-        }
-    })
+        page: query.page,
+        limit: query.limit,
+        total: 2,
+        debug: Some(QueryDebug::with_params(sql, params.len())),
+    }
 }
 
 /// Search users with Mongo-style filters.
@@ -206,57 +256,53 @@ fn list_users(query: ListUsersQuery, _req: &Request) -> Response {
 ///
 /// The filter supports operators: `$eq`, `$ne`, `$gt`, `$gte`, `$lt`, `$lte`,
 /// `$in`, `$nin`, `$like`, `$starts_with`, `$ends_with`, `$contains`, `$and`, `$or`, `$not`
-fn search_users(query: SearchUsersQuery, req: &Request) -> Response {
+fn search_users(query: SearchUsersQuery, req: &Request) -> Result<UserListResponse, ApiError> {
     // Parse filter from request body
-    let filter_json = ensure!(req.text(), 400, "Filter body required");
-    let filter = ensure!(parse_filter(filter_json), 400, "Invalid filter syntax");
+    let filter_json = req
+        .text()
+        .ok_or_else(|| ApiError::bad_request("Filter body required"))?;
+    let filter =
+        parse_filter(filter_json).map_err(|_| ApiError::bad_request("Invalid filter syntax"))?;
 
     // Generate SQL with merged filter
     // Returns Result because merge: requires runtime validation
-    let (sql, params) = ensure!(
-        sql_read!(users {
-            select: [id, name, email, status, created_at],
-            filter: { active: true },              // Always applied (trusted)
-            merge: filter,                          // User's filter (validated)
-            allow: [name, email, status, created_at], // Whitelist of allowed fields
-            deny_ops: [$like, $ilike],              // Deny regex-like operators
-            order: name,
-            page: query.page,
-            limit: query.limit,
-        }),
-        400,
-        "Invalid filter field or operator"
-    );
+    let (sql, params) = sql_read!(users {
+        select: [id, name, email, status, created_at],
+        filter: { active: true },              // Always applied (trusted)
+        merge: filter,                          // User's filter (validated)
+        allow: [name, email, status, created_at], // Whitelist of allowed fields
+        deny_ops: [$like, $ilike],              // Deny regex-like operators
+        order: name,
+        page: query.page,
+        limit: query.limit,
+    })
+    .map_err(|_| ApiError::bad_request("Invalid filter field or operator"))?;
 
     // In production: execute query against database
-    let param_count = params.len();
-    ok!({
-        "users": [
-            { "id": "1", "name": "Alice", "email": "alice@example.com", "status": "active" }
-        ],
-        "page": query.page,
-        "limit": query.limit,
-        "total": 1,
-        "_debug": {
-            "sql": sql,
-            "param_count": param_count,
-            "note": "Filter merged with base filter (active: true)"
-        }
+    let alice = User {
+        status: Some("active".to_string()),
+        ..User::new("1", "Alice", "alice@example.com")
+    };
+    Ok(UserListResponse {
+        users: vec![alice],
+        page: query.page,
+        limit: query.limit,
+        total: 1,
+        debug: Some(QueryDebug {
+            note: Some("Filter merged with base filter (active: true)".to_string()),
+            ..QueryDebug::with_params(sql, params.len())
+        }),
     })
 }
 
 /// Get a single user by ID.
-fn get_user(path: UserPath, _req: &Request) -> Response {
+fn get_user(path: UserPath, _req: &Request) -> Result<User, ApiError> {
     let id = &path.id;
 
     // Parse ID to integer for SQL query
-    let Ok(user_id) = id.parse::<i64>() else {
-        return error! {
-            status: status::BAD_REQUEST,
-            title: "Bad Request",
-            detail: "User ID must be a number"
-        };
-    };
+    let user_id = id
+        .parse::<i64>()
+        .map_err(|_| ApiError::bad_request("User ID must be a number"))?;
 
     // Generate SQL query
     let (sql, _params) = sql_read!(users {
@@ -266,34 +312,27 @@ fn get_user(path: UserPath, _req: &Request) -> Response {
     });
 
     // In production: execute query against database
-    match id.as_str() {
-        "1" => ok!({
-            "id": "1",
-            "name": "Alice",
-            "email": "alice@example.com",
-            "created_at": "2024-01-15T10:30:00Z",
-            "_debug": { "sql": sql }
-        }),
-        "2" => ok!({
-            "id": "2",
-            "name": "Bob",
-            "email": "bob@example.com",
-            "created_at": "2024-02-20T14:45:00Z",
-            "_debug": { "sql": sql }
-        }),
-        _ => {
-            let detail = format!("User '{id}' not found");
-            error! {
-                status: status::NOT_FOUND,
-                title: "Not Found",
-                detail: &detail
-            }
-        },
-    }
+    let (user, created_at) = match id.as_str() {
+        "1" => (
+            User::new("1", "Alice", "alice@example.com"),
+            "2024-01-15T10:30:00Z",
+        ),
+        "2" => (
+            User::new("2", "Bob", "bob@example.com"),
+            "2024-02-20T14:45:00Z",
+        ),
+        _ => return Err(ApiError::not_found(format!("User '{id}' not found"))),
+    };
+
+    Ok(User {
+        created_at: Some(created_at.to_string()),
+        debug: Some(QueryDebug::sql(sql)),
+        ..user
+    })
 }
 
 /// Create a new user.
-fn create_user(body: CreateUserInput, _req: &Request) -> Response {
+fn create_user(body: CreateUserInput, _req: &Request) -> User {
     // body is already parsed and validated by the derive macro!
     // name and email are guaranteed to be present
 
@@ -306,59 +345,33 @@ fn create_user(body: CreateUserInput, _req: &Request) -> Response {
     });
 
     // In production: execute query, get returned id and created_at
-    let new_id = "3"; // Mock generated ID
-    let location = format!("/users/{new_id}");
-    let created_at = get_iso_time();
-
-    // Return 201 Created with the new resource
-    Response {
-        status: status::CREATED,
-        headers: vec![
-            ("content-type".to_string(), "application/json".to_string()),
-            ("location".to_string(), location),
-        ],
-        body: Some(
-            json::obj()
-                .set("id", json::str(new_id))
-                .set("name", json::str(&body.name))
-                .set("email", json::str(&body.email))
-                .set("created_at", json::str(&created_at))
-                .set("_debug", json::obj().set("sql", json::str(&sql)))
-                .to_bytes(),
-        ),
+    // Sent as 201 Created by the route's #[status(201)]
+    User {
+        created_at: Some(get_iso_time()),
+        debug: Some(QueryDebug::sql(sql)),
+        ..User::new("3", &body.name, &body.email)
     }
 }
 
 /// Update an existing user.
-fn update_user(path: UserPath, body: UpdateUserInput, _req: &Request) -> Response {
+fn update_user(path: UserPath, body: UpdateUserInput, _req: &Request) -> Result<User, ApiError> {
     let id = &path.id;
 
     // Parse and validate ID
-    let Ok(user_id) = id.parse::<i64>() else {
-        return error! {
-            status: status::BAD_REQUEST,
-            title: "Bad Request",
-            detail: "User ID must be a number"
-        };
-    };
+    let user_id = id
+        .parse::<i64>()
+        .map_err(|_| ApiError::bad_request("User ID must be a number"))?;
 
     // Check if user exists (in production: database lookup)
     if id != "1" && id != "2" {
-        let detail = format!("User '{id}' not found");
-        return error! {
-            status: status::NOT_FOUND,
-            title: "Not Found",
-            detail: &detail
-        };
+        return Err(ApiError::not_found(format!("User '{id}' not found")));
     }
 
     // At least one field must be provided
     if body.name.is_none() && body.email.is_none() {
-        return error! {
-            status: status::UNPROCESSABLE_ENTITY,
-            title: "Validation Error",
-            detail: "At least one field (name or email) is required"
-        };
+        return Err(ApiError::unprocessable(
+            "At least one field (name or email) is required",
+        ));
     }
 
     let name = body.name.as_deref().unwrap_or("");
@@ -382,13 +395,10 @@ fn update_user(path: UserPath, body: UpdateUserInput, _req: &Request) -> Respons
         email
     };
 
-    let updated_at = get_iso_time();
-    ok!({
-        "id": id,
-        "name": final_name,
-        "email": final_email,
-        "updated_at": updated_at,
-        "_debug": { "sql": sql }
+    Ok(User {
+        updated_at: Some(get_iso_time()),
+        debug: Some(QueryDebug::sql(sql)),
+        ..User::new(id, final_name, final_email)
     })
 }
 
@@ -426,7 +436,7 @@ fn delete_user(path: UserPath, _req: &Request) -> Response {
 }
 
 /// List posts with cursor-based pagination.
-fn list_posts(query: ListPostsQuery, _req: &Request) -> Response {
+fn list_posts(query: ListPostsQuery, _req: &Request) -> PostListResponse {
     // sql_read! supports cursor pagination directly via after/before
     let (sql, params) = sql_read!(posts {
         select: [id, title, created_at],
@@ -457,42 +467,19 @@ fn list_posts(query: ListPostsQuery, _req: &Request) -> Response {
         .with_next_cursor(next_cursor.clone())
         .with_has_prev(query.after.is_some());
 
-    // Build response
-    let posts_json: Vec<_> = mock_posts
-        .iter()
-        .map(|(id, title, created_at)| {
-            json::obj()
-                .set("id", json::str(id))
-                .set("title", json::str(title))
-                .set("created_at", json::str(created_at))
-        })
-        .collect();
-
-    let mut posts_array = json::arr();
-    for post in posts_json {
-        posts_array = posts_array.push(post);
-    }
-
-    Response {
-        status: status::OK,
-        headers: vec![("content-type".to_string(), "application/json".to_string())],
-        body: Some(
-            json::obj()
-                .set("posts", posts_array)
-                .set("has_next", json::bool(page_info.has_next))
-                .set("has_prev", json::bool(page_info.has_prev))
-                .set(
-                    "next_cursor",
-                    next_cursor.as_ref().map_or_else(json::null, json::str),
-                )
-                .set(
-                    "_debug",
-                    json::obj()
-                        .set("sql", json::str(&sql))
-                        .set("param_count", json::int(params.len() as i64)),
-                )
-                .to_bytes(),
-        ),
+    PostListResponse {
+        posts: mock_posts
+            .iter()
+            .map(|(id, title, created_at)| Post {
+                id: (*id).to_string(),
+                title: (*title).to_string(),
+                created_at: (*created_at).to_string(),
+            })
+            .collect(),
+        has_next: page_info.has_next,
+        has_prev: page_info.has_prev,
+        next_cursor,
+        debug: Some(QueryDebug::with_params(sql, params.len())),
     }
 }
 
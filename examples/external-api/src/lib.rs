@@ -112,19 +112,21 @@ routes! {
 // HANDLERS
 // ============================================================================
 
-fn index(_req: &Request) -> Response {
-    ok!({
-        "name": "External API Example",
-        "version": "0.1.0",
-        "endpoints": [
+fn index(_req: &Request) -> IndexResponse {
+    IndexResponse {
+        name: "External API Example".to_string(),
+        version: "0.1.0".to_string(),
+        endpoints: [
             "GET /github/{username} - Fetch GitHub user info",
             "GET /proxy?url=<url> - Proxy external URL (SSRF protected)",
             "POST /webhook - Send webhook with JSON body",
             "GET /aggregate - Aggregate multiple API calls",
             "GET /fetch-local?url=<url> - Fetch from local URL (testing only)",
-            "POST /fetch-local - Fetch with body (testing only)"
+            "POST /fetch-local - Fetch with body (testing only)",
         ]
-    })
+        .map(String::from)
+        .to_vec(),
+    }
 }
 
 /// Fetch GitHub user information using the public API.
@@ -133,7 +135,7 @@ fn index(_req: &Request) -> Response {
 /// - Simple GET request to external API with `.send()`
 /// - JSON response parsing with path_* methods
 /// - Error handling for HTTP failures
-fn github_user(path: UsernamePath, req: &Request) -> Response {
+fn github_user(path: UsernamePath, req: &Request) -> Result<GithubUser, ApiError> {
     let url = format!("https://api.github.com/users/{}", path.username);
 
     log!(info, "fetching github user", username: &path.username);
@@ -154,61 +156,31 @@ fn github_user(path: UsernamePath, req: &Request) -> Response {
     })
     .send();
 
-    // Handle the response
-    let response = match result {
-        Ok(r) => r,
-        Err(e) => {
-            log!(error, "github api failed", error: &e.to_string());
-            return error! {
-                status: 502,
-                title: "Bad Gateway",
-                detail: "Failed to fetch from GitHub API"
-            };
-        },
-    };
+    // Handle the response - `?` turns HTTP client errors into 502/504 problems
+    let response = result.inspect_err(|e| {
+        log!(error, "github api failed", error: &e.to_string());
+    })?;
 
     // Check HTTP status
     if !response.is_success() {
         if response.status() == 404 {
-            return error! {
-                status: 404,
-                title: "Not Found",
-                detail: "GitHub user not found"
-            };
+            return Err(ApiError::not_found("GitHub user not found"));
         }
-        return error! {
-            status: 502,
-            title: "Bad Gateway",
-            detail: "GitHub API returned an error"
-        };
+        return Err(ApiError::new(502).with_detail("GitHub API returned an error"));
     }
 
     // Parse the JSON response using lazy path extraction
     let body = response.body();
-    let parsed = match json::try_parse(&body) {
-        Some(p) => p,
-        None => {
-            return error! {
-                status: 502,
-                title: "Bad Gateway",
-                detail: "Invalid JSON from GitHub API"
-            };
-        },
-    };
+    let parsed = json::try_parse(&body)
+        .ok_or_else(|| ApiError::new(502).with_detail("Invalid JSON from GitHub API"))?;
 
     // Extract fields using path_* methods (fast, no tree building)
-    let login = parsed.path_str_or(&["login"], "unknown");
-    let id = parsed.path_int_or(&["id"], 0);
-    let name = parsed.path_str(&["name"]);
-    let bio = parsed.path_str(&["bio"]);
-    let public_repos = parsed.path_int_or(&["public_repos"], 0);
-
-    ok!({
-        "login": login,
-        "id": id,
-        "name": name,
-        "bio": bio,
-        "public_repos": public_repos
+    Ok(GithubUser {
+        login: parsed.path_str_or(&["login"], "unknown"),
+        id: parsed.path_int_or(&["id"], 0),
+        name: parsed.path_str(&["name"]),
+        bio: parsed.path_str(&["bio"]),
+        public_repos: parsed.path_int_or(&["public_repos"], 0),
     })
 }
 
@@ -217,7 +189,7 @@ fn github_user(path: UsernamePath, req: &Request) -> Response {
 /// Demonstrates:
 /// - `deny_private_ips()` for SSRF protection
 /// - Passing through response status and body
-fn proxy(query: ProxyQuery, req: &Request) -> Response {
+fn proxy(query: ProxyQuery, req: &Request) -> Result<ProxyResponse, ApiError> {
     log!(info, "proxying url", url: &query.url);
 
     // CRITICAL: Use deny_private_ips() when the URL comes from user input!
@@ -241,18 +213,12 @@ fn proxy(query: ProxyQuery, req: &Request) -> Response {
             // Check if it's an SSRF block
             if error_msg.contains("SSRF") || error_msg.contains("private") {
                 log!(warn, "ssrf blocked", url: &query.url);
-                return error! {
-                    status: 403,
-                    title: "Forbidden",
-                    detail: "Requests to private/internal addresses are not allowed"
-                };
+                return Err(ApiError::forbidden(
+                    "Requests to private/internal addresses are not allowed",
+                ));
             }
             log!(error, "proxy failed", url: &query.url, error: &error_msg);
-            return error! {
-                status: 502,
-                title: "Bad Gateway",
-                detail: "Failed to fetch URL"
-            };
+            return Err(ApiError::new(502).with_detail("Failed to fetch URL"));
         },
     };
 
@@ -270,16 +236,10 @@ fn proxy(query: ProxyQuery, req: &Request) -> Response {
     let body = response.body();
     let body_str = String::from_utf8_lossy(&body);
 
-    // Build headers array
-    let headers_arr = headers
-        .iter()
-        .fold(json::arr(), |arr, h| arr.push(json::str(h)));
-
-    let truncated_body: String = body_str.chars().take(1000).collect();
-    ok!({
-        "status": status as i64,
-        "body": truncated_body,
-        "headers": headers_arr
+    Ok(ProxyResponse {
+        status: i64::from(status),
+        body: Some(body_str.chars().take(1000).collect()),
+        headers,
     })
 }
 
@@ -288,7 +248,7 @@ fn proxy(query: ProxyQuery, req: &Request) -> Response {
 /// Demonstrates:
 /// - POST request with JSON body
 /// - Custom headers
-fn send_webhook(body: WebhookPayload, req: &Request) -> Response {
+fn send_webhook(body: WebhookPayload, req: &Request) -> Result<WebhookResponse, ApiError> {
     // In a real app, this URL would come from configuration
     let webhook_url = "https://httpbin.org/post";
 
@@ -317,26 +277,21 @@ fn send_webhook(body: WebhookPayload, req: &Request) -> Response {
     match result {
         Ok(response) if response.is_success() => {
             log!(info, "webhook sent", event: &body.event, status: response.status());
-            ok!({
-                "success": true,
-                "message": "Webhook delivered successfully"
+            Ok(WebhookResponse {
+                success: true,
+                message: "Webhook delivered successfully".to_string(),
             })
         },
         Ok(response) => {
             log!(warn, "webhook failed", event: &body.event, status: response.status());
-            let message = format!("Webhook returned status {}", response.status());
-            ok!({
-                "success": false,
-                "message": message
+            Ok(WebhookResponse {
+                success: false,
+                message: format!("Webhook returned status {}", response.status()),
             })
         },
         Err(e) => {
             log!(error, "webhook error", event: &body.event, error: &e.to_string());
-            error! {
-                status: 502,
-                title: "Bad Gateway",
-                detail: "Failed to deliver webhook"
-            }
+            Err(ApiError::new(502).with_detail("Failed to deliver webhook"))
         },
     }
 }
@@ -425,7 +380,7 @@ fn aggregate(req: &Request) -> Response {
 ///
 /// WARNING: This endpoint does NOT use deny_private_ips() and is intended
 /// ONLY for local e2e testing where a mock server runs on localhost.
-fn fetch_local_get(query: FetchLocalQuery, req: &Request) -> Response {
+fn fetch_local_get(query: FetchLocalQuery, req: &Request) -> Result<ProxyResponse, ApiError> {
     log!(info, "fetch-local GET", url: &query.url);
 
     let trace_id = req.trace_id_or("");
@@ -441,7 +396,7 @@ fn fetch_local_get(query: FetchLocalQuery, req: &Request) -> Response {
 }
 
 /// Fetch from local URL with body (for e2e testing only - no SSRF protection).
-fn fetch_local_post(body: FetchLocalBody, req: &Request) -> Response {
+fn fetch_local_post(body: FetchLocalBody, req: &Request) -> Result<ProxyResponse, ApiError> {
     log!(info, "fetch-local POST", url: &body.url, method: body.method.as_deref().unwrap_or("POST"));
 
     let method = body.method.as_deref().unwrap_or("POST").to_uppercase();
@@ -490,11 +445,9 @@ fn fetch_local_post(body: FetchLocalBody, req: &Request) -> Response {
             .with_trace_id(trace_opt)
             .send(),
         _ => {
-            return error! {
-                status: 400,
-                title: "Bad Request",
-                detail: "Unsupported method. Use GET, POST, PUT, or DELETE."
-            };
+            return Err(ApiError::bad_request(
+                "Unsupported method. Use GET, POST, PUT, or DELETE.",
+            ));
         },
     };
 
@@ -502,7 +455,9 @@ fn fetch_local_post(body: FetchLocalBody, req: &Request) -> Response {
 }
 
 /// Common result handler for fetch operations.
-fn handle_fetch_result(result: Result<http_client::Response, http_client::Error>) -> Response {
+fn handle_fetch_result(
+    result: Result<http_client::Response, http_client::Error>,
+) -> Result<ProxyResponse, ApiError> {
     match result {
         Ok(response) => {
             let status = response.status();
@@ -517,25 +472,17 @@ fn handle_fetch_result(result: Result<http_client::Response, http_client::Error>
             let body = response.body();
             let body_str = String::from_utf8_lossy(&body);
 
-            let headers_arr = headers
-                .iter()
-                .fold(json::arr(), |arr, h| arr.push(json::str(h)));
-
-            let truncated_body: String = body_str.chars().take(10000).collect();
-            ok!({
-                "status": status as i64,
-                "body": truncated_body,
-                "headers": headers_arr
+            Ok(ProxyResponse {
+                status: i64::from(status),
+                body: Some(body_str.chars().take(10000).collect()),
+                headers,
             })
         },
         Err(e) => {
+            // Testing endpoint: expose the client error instead of the generic detail
             let error_msg = e.to_string();
             log!(error, "fetch failed", error: &error_msg);
-            error! {
-                status: 502,
-                title: "Bad Gateway",
-                detail: error_msg
-            }
+            Err(ApiError::new(502).with_detail(error_msg))
         },
     }
 }
@@ -5,7 +5,7 @@
 #[allow(warnings, unsafe_code)]
 mod bindings;
 
-use bindings::exports::mik::core::handler::{self, Guest, Response};
+use bindings::exports::mik::core::handler::{self, Guest};
 use mik_sdk::prelude::*;
 
 #[derive(Type)]
@@ -49,7 +49,7 @@ pub struct EchoResponse {
     #[field(x_example = "Hello, World!")]
     pub echo: String,
     #[field(x_example = 13)]
-    pub length: i64,
+    pub length: usize,
 }
 
 #[derive(Query)]
@@ -88,42 +88,42 @@ routes! {
     GET "/search" => search(query: SearchQuery) -> SearchResponse,
 }
 
-fn home(_req: &Request) -> Response {
-    ok!({
-        "message": "Welcome to mik-sdk!",
-        "version": "0.1.0",
-        "endpoints": ["/", "/hello/{name}", "/echo", "/search"],
-        "status": "online"
-    })
+fn home(_req: &Request) -> HomeResponse {
+    HomeResponse {
+        message: "Welcome to mik-sdk!".to_string(),
+        version: "0.1.0".to_string(),
+        endpoints: ["/", "/hello/{name}", "/echo", "/search"]
+            .map(String::from)
+            .to_vec(),
+        status: ApiStatus::Online,
+    }
 }
 
-fn hello(path: HelloPath, _req: &Request) -> Response {
+fn hello(path: HelloPath, _req: &Request) -> HelloResponse {
     log!(info, "hello called", name: &path.name);
-    let greeting = format!("Hello, {}!", path.name);
-    ok!({
-        "greeting": greeting,
-        "name": path.name
-    })
+    HelloResponse {
+        greeting: format!("Hello, {}!", path.name),
+        name: path.name,
+    }
 }
 
-fn echo(body: EchoInput, _req: &Request) -> Response {
-    let len = body.message.len();
-    ok!({
-        "echo": body.message,
-        "length": len
-    })
+fn echo(body: EchoInput, _req: &Request) -> EchoResponse {
+    EchoResponse {
+        length: body.message.len(),
+        echo: body.message,
+    }
 }
 
-fn search(query: SearchQuery, _req: &Request) -> Response {
+fn search(query: SearchQuery, _req: &Request) -> SearchResponse {
     let message = match &query.q {
         Some(q) => format!("Searching for \'{}\' on page {}", q, query.page),
         None => format!("Listing all items on page {}", query.page),
     };
 
-    ok!({
-        "query": query.q,
-        "page": query.page,
-        "limit": query.limit,
-        "message": message
-    })
+    SearchResponse {
+        query: query.q,
+        page: i64::from(query.page),
+        limit: i64::from(query.limit),
+        message,
+    }
 }
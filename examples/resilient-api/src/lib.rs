@@ -115,18 +115,20 @@ routes! {
 // HANDLERS
 // ============================================================================
 
-fn index(_req: &Request) -> Response {
-    ok!({
-        "name": "Resilient API Example",
-        "version": "0.1.0",
-        "patterns": [
+fn index(_req: &Request) -> IndexResponse {
+    IndexResponse {
+        name: "Resilient API Example".to_string(),
+        version: "0.1.0".to_string(),
+        patterns: [
             "GET /retry - Retry with exponential backoff",
             "GET /fallback - Graceful degradation with fallbacks",
             "GET /aggregate - Partial success aggregation",
             "GET /rate-limited - Rate limit (429) handling",
-            "GET /health - Health check with dependency verification"
+            "GET /health - Health check with dependency verification",
         ]
-    })
+        .map(String::from)
+        .to_vec(),
+    }
 }
 
 // ============================================================================
@@ -139,7 +141,7 @@ fn index(_req: &Request) -> Response {
 /// overload), retrying with increasing delays often succeeds.
 ///
 /// Backoff schedule: 100ms, 200ms, 400ms (exponential)
-fn retry_demo(req: &Request) -> Response {
+fn retry_demo(req: &Request) -> Result<RetryResponse, ApiError> {
     log!(info, "starting retry demo");
 
     // This endpoint sometimes fails (simulated with httpbin's status endpoint)
@@ -166,10 +168,10 @@ fn retry_demo(req: &Request) -> Response {
         match result {
             Ok(response) if response.is_success() => {
                 log!(info, "retry succeeded", attempts: attempts);
-                return ok!({
-                    "data": "Request succeeded",
-                    "attempts": attempts as i64,
-                    "success": true
+                return Ok(RetryResponse {
+                    data: Some("Request succeeded".to_string()),
+                    attempts: i64::from(attempts),
+                    success: true,
                 });
             },
             Ok(response) if is_retryable_status(response.status()) => {
@@ -178,11 +180,9 @@ fn retry_demo(req: &Request) -> Response {
             Ok(response) => {
                 // Non-retryable HTTP error (4xx except 429)
                 log!(error, "non-retryable error", status: response.status());
-                return error! {
-                    status: response.status(),
-                    title: "Request Failed",
-                    detail: format!("Non-retryable error: HTTP {}", response.status())
-                };
+                return Err(ApiError::new(response.status())
+                    .with_title("Request Failed")
+                    .with_detail(format!("Non-retryable error: HTTP {}", response.status())));
             },
             Err(e) if is_retryable_error(&e) => {
                 log!(warn, "retryable error", error: &e.to_string(), attempt: attempts);
@@ -190,11 +190,7 @@ fn retry_demo(req: &Request) -> Response {
             Err(e) => {
                 // Non-retryable error (e.g., invalid URL, SSRF block)
                 log!(error, "non-retryable error", error: &e.to_string());
-                return error! {
-                    status: 502,
-                    title: "Bad Gateway",
-                    detail: format!("Non-retryable error: {}", e)
-                };
+                return Err(ApiError::new(502).with_detail(format!("Non-retryable error: {e}")));
             },
         }
 
@@ -209,10 +205,10 @@ fn retry_demo(req: &Request) -> Response {
 
     // All retries exhausted
     log!(error, "all retries exhausted", attempts: attempts);
-    ok!({
-        "data": null,
-        "attempts": attempts as i64,
-        "success": false
+    Ok(RetryResponse {
+        data: None,
+        attempts: i64::from(attempts),
+        success: false,
     })
 }
 
@@ -239,25 +235,20 @@ const fn is_retryable_error(e: &HttpError) -> bool {
 ///
 /// When the primary data source fails, try alternatives before giving up.
 /// This provides graceful degradation instead of hard failure.
-fn fallback_demo(req: &Request) -> Response {
+fn fallback_demo(req: &Request) -> FallbackResponse {
     log!(info, "starting fallback demo");
 
-    // Try primary source
-    if let Some((data, source)) = try_primary_source(req) {
-        return ok!({ "data": data, "source": source });
-    }
-
-    // Try secondary source
-    if let Some((data, source)) = try_secondary_source(req) {
-        return ok!({ "data": data, "source": source });
+    // Try primary source, then secondary source
+    if let Some((data, source)) = try_primary_source(req).or_else(|| try_secondary_source(req)) {
+        return FallbackResponse { data, source };
     }
 
     // Return cached/default data
     log!(warn, "all sources failed, using default");
-    ok!({
-        "data": "Default fallback data (cached or static)",
-        "source": "default"
-    })
+    FallbackResponse {
+        data: "Default fallback data (cached or static)".to_string(),
+        source: "default".to_string(),
+    }
 }
 
 fn try_primary_source(req: &Request) -> Option<(String, String)> {
@@ -330,11 +321,11 @@ fn try_secondary_source(req: &Request) -> Option<(String, String)> {
 ///
 /// When aggregating from multiple sources, don't fail the entire request
 /// if one source fails. Return partial data with error details.
-fn aggregate_demo(req: &Request) -> Response {
+fn aggregate_demo(req: &Request) -> Result<AggregateResponse, ApiError> {
     log!(info, "starting aggregate demo");
 
-    let mut results: Vec<(String, String)> = Vec::new();
-    let mut errors: Vec<(String, String)> = Vec::new();
+    let mut results: Vec<AggregateResult> = Vec::new();
+    let mut errors: Vec<AggregateError> = Vec::new();
 
     let trace_id = req.trace_id_or("");
     let trace_opt = if trace_id.is_empty() {
@@ -345,20 +336,38 @@ fn aggregate_demo(req: &Request) -> Response {
 
     // Source 1: UUID service
     match fetch_source("uuid", "https://httpbin.org/uuid", trace_opt) {
-        Ok(data) => results.push(("uuid".to_string(), data)),
-        Err(reason) => errors.push(("uuid".to_string(), reason)),
+        Ok(data) => results.push(AggregateResult {
+            source: "uuid".to_string(),
+            data,
+        }),
+        Err(reason) => errors.push(AggregateError {
+            source: "uuid".to_string(),
+            reason,
+        }),
     }
 
     // Source 2: Headers echo
     match fetch_source("headers", "https://httpbin.org/headers", trace_opt) {
-        Ok(data) => results.push(("headers".to_string(), data)),
-        Err(reason) => errors.push(("headers".to_string(), reason)),
+        Ok(data) => results.push(AggregateResult {
+            source: "headers".to_string(),
+            data,
+        }),
+        Err(reason) => errors.push(AggregateError {
+            source: "headers".to_string(),
+            reason,
+        }),
     }
 
     // Source 3: IP address
     match fetch_source("ip", "https://httpbin.org/ip", trace_opt) {
-        Ok(data) => results.push(("ip".to_string(), data)),
-        Err(reason) => errors.push(("ip".to_string(), reason)),
+        Ok(data) => results.push(AggregateResult {
+            source: "ip".to_string(),
+            data,
+        }),
+        Err(reason) => errors.push(AggregateError {
+            source: "ip".to_string(),
+            reason,
+        }),
     }
 
     let total = results.len() + errors.len();
@@ -372,38 +381,17 @@ fn aggregate_demo(req: &Request) -> Response {
         partial: partial_success
     );
 
-    // Build response arrays
-    let results_arr = results.iter().fold(json::arr(), |arr, (source, data)| {
-        arr.push(
-            json::obj()
-                .set("source", json::str(source))
-                .set("data", json::str(data)),
-        )
-    });
-
-    let errors_arr = errors.iter().fold(json::arr(), |arr, (source, reason)| {
-        arr.push(
-            json::obj()
-                .set("source", json::str(source))
-                .set("reason", json::str(reason)),
-        )
-    });
-
     // Return appropriate status based on results
     if results.is_empty() {
         // Total failure
-        error! {
-            status: 502,
-            title: "Bad Gateway",
-            detail: format!("All {} sources failed", total)
-        }
-    } else {
-        ok!({
-            "results": results_arr,
-            "errors": errors_arr,
-            "partial_success": partial_success
-        })
+        return Err(ApiError::new(502).with_detail(format!("All {total} sources failed")));
     }
+
+    Ok(AggregateResponse {
+        results,
+        errors,
+        partial_success,
+    })
 }
 
 fn fetch_source(name: &str, url: &str, trace_id: Option<&str>) -> Result<String, String> {
@@ -528,10 +516,10 @@ fn rate_limit_demo(req: &Request) -> Response {
 /// - Returns quickly (uses short timeouts)
 /// - Reports individual dependency status
 /// - Returns 503 if any critical dependency is unhealthy
-fn health_check(req: &Request) -> Response {
+fn health_check(req: &Request) -> Result<HealthResponse, Response> {
     log!(info, "running health check");
 
-    let mut dependencies: Vec<(String, bool, Option<i64>, Option<String>)> = Vec::new();
+    let mut dependencies: Vec<DependencyStatus> = Vec::new();
     let mut all_healthy = true;
     let trace_id = req.trace_id_or("");
     let trace_opt = if trace_id.is_empty() {
@@ -545,51 +533,43 @@ fn health_check(req: &Request) -> Response {
     if !healthy {
         all_healthy = false;
     }
-    dependencies.push(("primary-api".to_string(), healthy, latency, error));
+    dependencies.push(DependencyStatus {
+        name: "primary-api".to_string(),
+        healthy,
+        latency_ms: latency,
+        error,
+    });
 
     // Check dependency 2: Secondary API
     let (healthy, latency, error) = check_dependency("https://httpbin.org/get", trace_opt);
     if !healthy {
         all_healthy = false;
     }
-    dependencies.push(("secondary-api".to_string(), healthy, latency, error));
-
-    // Build response
-    let deps_arr = dependencies
-        .iter()
-        .fold(json::arr(), |arr, (name, healthy, latency, error)| {
-            let mut obj = json::obj()
-                .set("name", json::str(name))
-                .set("healthy", json::bool(*healthy));
-
-            if let Some(ms) = latency {
-                obj = obj.set("latency_ms", json::int(*ms));
-            }
-            if let Some(err) = error {
-                obj = obj.set("error", json::str(err));
-            }
-
-            arr.push(obj)
-        });
+    dependencies.push(DependencyStatus {
+        name: "secondary-api".to_string(),
+        healthy,
+        latency_ms: latency,
+        error,
+    });
 
     let status_str = if all_healthy { "healthy" } else { "degraded" };
 
     log!(info, "health check complete", status: status_str);
 
-    // Return 503 if unhealthy, 200 if healthy
-    let http_status = if all_healthy { 200 } else { 503 };
+    let health = HealthResponse {
+        status: status_str.to_string(),
+        dependencies,
+    };
 
-    Response {
-        status: http_status,
-        headers: vec![("content-type".to_string(), "application/json".to_string())],
-        body: Some(
-            json::obj()
-                .set("status", json::str(status_str))
-                .set("dependencies", deps_arr)
-                .to_string()
-                .into_bytes(),
-        ),
+    // Return 200 if healthy; 503 with the same body if unhealthy
+    if all_healthy {
+        return Ok(health);
     }
+    Err(Response {
+        status: 503,
+        headers: vec![("content-type".to_string(), "application/json".to_string())],
+        body: Some(health.to_json().to_bytes()),
+    })
 }
 
 fn check_dependency(url: &str, trace_id: Option<&str>) -> (bool, Option<i64>, Option<String>) {
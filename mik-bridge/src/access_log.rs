@@ -0,0 +1,228 @@
+//! Request IDs and structured access-log lines.
+//!
+//! Pure formatting helpers; the WASI calls (random bytes, clocks, stderr) stay
+//! in `lib.rs` so this module can be unit tested natively.
+
+use crate::escape_json_string;
+use std::fmt::Write;
+
+/// Format 16 random bytes as a UUID v4 string (RFC 9562).
+///
+/// Sets the version and variant bits, so any input yields a valid v4 UUID.
+pub fn format_uuid_v4(mut bytes: [u8; 16]) -> String {
+    // Version 4 in the high nibble of byte 6, RFC variant in byte 8
+    bytes[6] = (bytes[6] & 0x0F) | 0x40;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+    let mut out = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+/// Request header carrying the W3C trace context (read by `Request::trace_id_or`).
+const HEADER_TRACEPARENT: &str = "traceparent";
+
+/// Request header some proxies use for a request ID.
+const HEADER_X_REQUEST_ID: &str = "x-request-id";
+
+/// Return the request's ID, adding one to `headers` when the client sent none.
+///
+/// A non-empty `traceparent` is used as-is. Otherwise the ID is taken from
+/// `x-request-id` or, failing that, from `generate`, and is inserted as
+/// `traceparent` so the handler sees it through `Request::trace_id_or`.
+pub fn ensure_request_id(
+    headers: &mut Vec<(String, String)>,
+    generate: impl FnOnce() -> String,
+) -> String {
+    let find = |headers: &[(String, String)], name: &str| {
+        headers
+            .iter()
+            .find(|(k, v)| k.eq_ignore_ascii_case(name) && !v.trim().is_empty())
+            .map(|(_, v)| v.trim().to_string())
+    };
+    if let Some(id) = find(headers, HEADER_TRACEPARENT) {
+        return id;
+    }
+    let id = find(headers, HEADER_X_REQUEST_ID).unwrap_or_else(generate);
+    // Drop blank traceparent headers so the handler sees only the new ID
+    headers.retain(|(k, _)| !k.eq_ignore_ascii_case(HEADER_TRACEPARENT));
+    headers.push((HEADER_TRACEPARENT.to_string(), id.clone()));
+    id
+}
+
+/// Format a Unix timestamp as ISO 8601 UTC, with milliseconds when non-zero.
+///
+/// # Note: Intentional Duplication
+///
+/// This mirrors `mik_sdk::time::to_iso()` so access-log lines carry the same
+/// `ts` format as `mik_sdk::log`. `mik-bridge` cannot depend on `mik-sdk`; if
+/// you change one, update the other.
+pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
+    let days = seconds / 86_400;
+    let remaining = seconds % 86_400;
+    let hours = remaining / 3_600;
+    let minutes = (remaining % 3_600) / 60;
+    let secs = remaining % 60;
+
+    // Howard Hinnant's civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(m <= 2);
+
+    if nanoseconds == 0 {
+        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}Z")
+    } else {
+        let millis = nanoseconds / 1_000_000;
+        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}.{millis:03}Z")
+    }
+}
+
+/// One completed request, as written to the access log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessLog<'a> {
+    pub method: &'a str,
+    /// Request path; the query string is dropped so tokens are not logged.
+    pub path: &'a str,
+    pub status: u16,
+    pub duration_ns: u64,
+    /// Response body bytes sent (after compression).
+    pub bytes: usize,
+    pub request_id: &'a str,
+}
+
+impl AccessLog<'_> {
+    /// Render the entry in the `mik_sdk::log` JSON format.
+    ///
+    /// Field values are strings, as with `log!`:
+    ///
+    /// ```json
+    /// {"level":"info","msg":"request","method":"GET","path":"/users","status":"200","duration_ms":"1.250","bytes":"512","request_id":"...","ts":"2025-01-16T10:30:00.123Z"}
+    /// ```
+    pub fn to_json(&self, ts: &str) -> String {
+        let path = self.path.split('?').next().unwrap_or_default();
+        let duration_ms = format!(
+            "{}.{:03}",
+            self.duration_ns / 1_000_000,
+            (self.duration_ns / 1_000) % 1_000
+        );
+        let fields = [
+            ("method", self.method.to_string()),
+            ("path", path.to_string()),
+            ("status", self.status.to_string()),
+            ("duration_ms", duration_ms),
+            ("bytes", self.bytes.to_string()),
+            ("request_id", self.request_id.to_string()),
+        ];
+
+        let mut out = String::from(r#"{"level":"info","msg":"request""#);
+        for (key, value) in fields {
+            let _ = write!(out, r#","{key}":"{}""#, escape_json_string(&value));
+        }
+        let _ = write!(out, r#","ts":"{ts}"}}"#);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uuid_v4() {
+        let id = format_uuid_v4([0xFF; 16]);
+        assert_eq!(id, "ffffffff-ffff-4fff-bfff-ffffffffffff");
+
+        let id = format_uuid_v4([0; 16]);
+        assert_eq!(id, "00000000-0000-4000-8000-000000000000");
+        assert_eq!(id.len(), 36);
+    }
+
+    #[test]
+    fn test_ensure_request_id_generates() {
+        let mut headers = vec![("accept".to_string(), "*/*".to_string())];
+        let id = ensure_request_id(&mut headers, || "generated".to_string());
+        assert_eq!(id, "generated");
+        assert_eq!(
+            headers.last(),
+            Some(&("traceparent".to_string(), "generated".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_ensure_request_id_reuses_incoming() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let mut headers = vec![("Traceparent".to_string(), traceparent.to_string())];
+        let id = ensure_request_id(&mut headers, || unreachable!());
+        assert_eq!(id, traceparent);
+        assert_eq!(headers.len(), 1);
+
+        let mut headers = vec![
+            ("traceparent".to_string(), " ".to_string()),
+            ("X-Request-Id".to_string(), "req-42".to_string()),
+        ];
+        let id = ensure_request_id(&mut headers, || unreachable!());
+        assert_eq!(id, "req-42");
+        assert_eq!(
+            headers,
+            vec![
+                ("X-Request-Id".to_string(), "req-42".to_string()),
+                ("traceparent".to_string(), "req-42".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_iso() {
+        assert_eq!(to_iso(0, 0), "1970-01-01T00:00:00Z");
+        assert_eq!(to_iso(1_737_024_600, 0), "2025-01-16T10:50:00Z");
+        assert_eq!(
+            to_iso(1_737_024_600, 123_456_789),
+            "2025-01-16T10:50:00.123Z"
+        );
+        // Leap day
+        assert_eq!(to_iso(951_782_400, 0), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn test_access_log_json() {
+        let entry = AccessLog {
+            method: "GET",
+            path: "/users?token=secret",
+            status: 200,
+            duration_ns: 1_250_000,
+            bytes: 512,
+            request_id: "abc-123",
+        };
+        assert_eq!(
+            entry.to_json("2025-01-16T10:30:00Z"),
+            r#"{"level":"info","msg":"request","method":"GET","path":"/users","status":"200","duration_ms":"1.250","bytes":"512","request_id":"abc-123","ts":"2025-01-16T10:30:00Z"}"#
+        );
+    }
+
+    #[test]
+    fn test_access_log_escapes_values() {
+        let entry = AccessLog {
+            method: "GET",
+            path: "/a\"b",
+            status: 404,
+            duration_ns: 0,
+            bytes: 0,
+            request_id: "x\ny",
+        };
+        let line = entry.to_json("ts");
+        assert!(line.contains(r#""path":"/a\"b""#));
+        assert!(line.contains(r#""request_id":"x\ny""#));
+        assert!(line.contains(r#""duration_ms":"0.000""#));
+    }
+}
@@ -0,0 +1,747 @@
+//! Response compression negotiated from `Accept-Encoding`, and decoding of
+//! `Content-Encoding` request bodies.
+//!
+//! Pure functions over plain header lists and bytes, so they can be unit tested
+//! natively. DEFLATE comes from `miniz_oxide` (pure Rust); the gzip framing and
+//! CRC-32 are implemented here.
+
+/// Default minimum body size worth compressing (1KB).
+pub const DEFAULT_MIN_SIZE: usize = 1024;
+
+/// Default compression level (miniz scale 0-10).
+pub const DEFAULT_LEVEL: u8 = 6;
+
+/// Content-Encoding header name.
+const HEADER_CONTENT_ENCODING: &str = "content-encoding";
+
+/// Content-Length header name.
+const HEADER_CONTENT_LENGTH: &str = "content-length";
+
+/// Maximum number of stacked content codings decoded from one request.
+const MAX_CODINGS: usize = 2;
+
+/// Vary header name.
+const HEADER_VARY: &str = "vary";
+
+/// `ETag` header name.
+const HEADER_ETAG: &str = "etag";
+
+/// A supported content coding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Gzip,
+    /// HTTP `deflate` is the zlib format (RFC 1950), not raw DEFLATE.
+    Deflate,
+}
+
+impl Encoding {
+    /// The `Content-Encoding` token.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+}
+
+/// Compression settings, read once from the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// `MIK_COMPRESSION` - set to `off`, `false` or `0` to disable.
+    pub enabled: bool,
+    /// `MIK_COMPRESSION_MIN_SIZE` - smaller bodies are sent as-is.
+    pub min_size: usize,
+    /// `MIK_COMPRESSION_LEVEL` - 1 (fastest) to 9 (smallest).
+    pub level: u8,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_size: DEFAULT_MIN_SIZE,
+            level: DEFAULT_LEVEL,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Build the config from environment variables, ignoring invalid values.
+    pub fn from_env(vars: &[(String, String)]) -> Self {
+        let get = |name: &str| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.trim());
+        let defaults = Self::default();
+        Self {
+            enabled: get("MIK_COMPRESSION").map_or(defaults.enabled, |v| {
+                !matches!(v.to_ascii_lowercase().as_str(), "off" | "false" | "0")
+            }),
+            min_size: get("MIK_COMPRESSION_MIN_SIZE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_size),
+            level: get("MIK_COMPRESSION_LEVEL")
+                .and_then(|v| v.parse().ok())
+                .filter(|level| (1..=9).contains(level))
+                .unwrap_or(defaults.level),
+        }
+    }
+}
+
+/// Pick the preferred supported coding from an `Accept-Encoding` value.
+///
+/// Honors q-values and `*`; `gzip` wins ties. Returns `None` when neither
+/// coding is acceptable (the body is then sent as identity).
+pub fn negotiate(accept_encoding: &str) -> Option<Encoding> {
+    let mut gzip = None;
+    let mut deflate = None;
+    let mut wildcard = None;
+
+    for entry in accept_encoding.split(',') {
+        let mut parts = entry.split(';');
+        let coding = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+        let quality = parts
+            .filter_map(|param| param.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+            .map_or(Some(1.0), |(_, value)| value.trim().parse::<f32>().ok())
+            .filter(|q| (0.0..=1.0).contains(q));
+        let Some(quality) = quality else {
+            continue;
+        };
+        match coding.as_str() {
+            "gzip" | "x-gzip" => gzip = Some(quality),
+            "deflate" => deflate = Some(quality),
+            "*" => wildcard = Some(quality),
+            _ => {}
+        }
+    }
+
+    let gzip = gzip.or(wildcard).unwrap_or(0.0);
+    let deflate = deflate.or(wildcard).unwrap_or(0.0);
+    if gzip > 0.0 && gzip >= deflate {
+        Some(Encoding::Gzip)
+    } else if deflate > 0.0 {
+        Some(Encoding::Deflate)
+    } else {
+        None
+    }
+}
+
+/// Whether a content type benefits from compression.
+///
+/// Media that is already compressed (images, audio, video, archives, fonts)
+/// is skipped. SVG is text and is compressed.
+pub fn is_compressible(content_type: Option<&str>) -> bool {
+    let Some(content_type) = content_type else {
+        return true;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    if essence == "image/svg+xml" {
+        return true;
+    }
+    let (main, sub) = essence.split_once('/').unwrap_or((essence.as_str(), ""));
+    if matches!(main, "image" | "audio" | "video" | "font") {
+        return false;
+    }
+    !matches!(
+        sub,
+        "gzip"
+            | "x-gzip"
+            | "zip"
+            | "zstd"
+            | "x-bzip2"
+            | "x-xz"
+            | "x-7z-compressed"
+            | "vnd.rar"
+            | "x-rar-compressed"
+            | "octet-stream"
+            | "pdf"
+            | "wasm"
+    )
+}
+
+/// Compress `body` with the given coding.
+pub fn compress(body: &[u8], encoding: Encoding, level: u8) -> Vec<u8> {
+    match encoding {
+        Encoding::Gzip => gzip(body, level),
+        Encoding::Deflate => miniz_oxide::deflate::compress_to_vec_zlib(body, level),
+    }
+}
+
+/// Wrap raw DEFLATE data in a gzip member (RFC 1952).
+fn gzip(body: &[u8], level: u8) -> Vec<u8> {
+    let deflated = miniz_oxide::deflate::compress_to_vec(body, level);
+    let mut out = Vec::with_capacity(deflated.len() + 18);
+    // Magic, CM=deflate, no flags, no mtime, no extra flags, OS=unknown
+    out.extend_from_slice(&[0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff]);
+    out.extend_from_slice(&deflated);
+    out.extend_from_slice(&crc32(body).to_le_bytes());
+    // ISIZE is the input length modulo 2^32
+    #[allow(clippy::cast_possible_truncation)]
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out
+}
+
+/// CRC-32 (IEEE 802.3, reflected), as used by gzip.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            #[allow(clippy::cast_possible_truncation)]
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// Compress a response body in place when the client and content allow it.
+///
+/// Skipped for bodies below `min_size`, statuses without content, responses
+/// that already set `Content-Encoding`, and incompressible content types.
+/// When compressing, sets `Content-Encoding`, drops `Content-Length`, adds
+/// `Vary: Accept-Encoding`, and weakens a strong `ETag` (the encoded bytes
+/// differ from the identity representation). A result that is not smaller
+/// than the input is discarded.
+pub fn compress_response(
+    config: &CompressionConfig,
+    accept_encoding: Option<&str>,
+    status: u16,
+    headers: &mut Vec<(String, String)>,
+    body: &mut Vec<u8>,
+) {
+    if !config.enabled
+        || body.len() < config.min_size
+        || matches!(status, 100..=199 | 204 | 304)
+        || find_header(headers, HEADER_CONTENT_ENCODING).is_some()
+        || !is_compressible(find_header(headers, "content-type"))
+    {
+        return;
+    }
+    let Some(encoding) = accept_encoding.and_then(negotiate) else {
+        return;
+    };
+
+    let compressed = compress(body, encoding, config.level);
+    if compressed.len() >= body.len() {
+        return;
+    }
+    *body = compressed;
+
+    headers.retain(|(k, _)| !k.eq_ignore_ascii_case(HEADER_CONTENT_LENGTH));
+    headers.push((
+        HEADER_CONTENT_ENCODING.to_string(),
+        encoding.as_str().to_string(),
+    ));
+    match headers
+        .iter_mut()
+        .find(|(k, _)| k.eq_ignore_ascii_case(HEADER_VARY))
+    {
+        Some((_, vary)) => {
+            let listed = vary
+                .split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("accept-encoding") || v.trim() == "*");
+            if !listed {
+                vary.push_str(", Accept-Encoding");
+            }
+        }
+        None => headers.push((HEADER_VARY.to_string(), "Accept-Encoding".to_string())),
+    }
+    if let Some((_, etag)) = headers
+        .iter_mut()
+        .find(|(k, _)| k.eq_ignore_ascii_case(HEADER_ETAG))
+        && etag.starts_with('"')
+    {
+        etag.insert_str(0, "W/");
+    }
+}
+
+/// Why a request body could not be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A content coding other than gzip, deflate or identity (415).
+    Unsupported,
+    /// The decoded body exceeds the size limit (413).
+    TooLarge,
+    /// The body is not valid for its declared coding (400).
+    Invalid,
+}
+
+/// Inflate with an output limit, mapping miniz errors to [`DecodeError`].
+fn inflate(
+    result: Result<Vec<u8>, miniz_oxide::inflate::DecompressError>,
+) -> Result<Vec<u8>, DecodeError> {
+    result.map_err(|e| match e.status {
+        miniz_oxide::inflate::TINFLStatus::HasMoreOutput => DecodeError::TooLarge,
+        _ => DecodeError::Invalid,
+    })
+}
+
+/// Decode a single gzip member (RFC 1952), checking CRC-32 and length.
+fn gunzip(data: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    if data.len() < 18 || data[..3] != [0x1f, 0x8b, 8] {
+        return Err(DecodeError::Invalid);
+    }
+    let flags = data[3];
+    let trailer_start = data.len() - 8;
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let extra = data.get(pos..pos + 2).ok_or(DecodeError::Invalid)?;
+        pos += 2 + usize::from(u16::from_le_bytes([extra[0], extra[1]]));
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = data
+                .get(pos..trailer_start)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or(DecodeError::Invalid)?;
+            pos += end + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let deflated = data.get(pos..trailer_start).ok_or(DecodeError::Invalid)?;
+
+    let body = inflate(miniz_oxide::inflate::decompress_to_vec_with_limit(
+        deflated, max_size,
+    ))?;
+    let trailer = &data[trailer_start..];
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let isize = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    #[allow(clippy::cast_possible_truncation)]
+    if crc != crc32(&body) || isize != body.len() as u32 {
+        return Err(DecodeError::Invalid);
+    }
+    Ok(body)
+}
+
+/// Decode `body` with one content coding, producing at most `max_size` bytes.
+///
+/// `deflate` is expected in zlib format; raw DEFLATE, which some clients send,
+/// is accepted as a fallback.
+pub fn decompress(
+    body: &[u8],
+    encoding: Encoding,
+    max_size: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    match encoding {
+        Encoding::Gzip => gunzip(body, max_size),
+        Encoding::Deflate => {
+            match inflate(miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(
+                body, max_size,
+            )) {
+                Err(DecodeError::Invalid) => inflate(
+                    miniz_oxide::inflate::decompress_to_vec_with_limit(body, max_size),
+                ),
+                result => result,
+            }
+        }
+    }
+}
+
+/// Decode a request body according to its `Content-Encoding` header(s).
+///
+/// Codings are undone in reverse order of application, with the size limit
+/// applied to every decoded stage (so small compressed payloads cannot expand
+/// past `max_size`). On success the `Content-Encoding` header is removed and
+/// `Content-Length` is updated to the decoded size. Unknown codings are
+/// rejected even when the body is empty.
+pub fn decode_request(
+    headers: &mut Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    max_size: usize,
+) -> Result<Option<Vec<u8>>, DecodeError> {
+    let mut codings = Vec::new();
+    for (_, value) in headers
+        .iter()
+        .filter(|(k, _)| k.eq_ignore_ascii_case(HEADER_CONTENT_ENCODING))
+    {
+        for token in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            match token.to_ascii_lowercase().as_str() {
+                "identity" => {}
+                "gzip" | "x-gzip" => codings.push(Encoding::Gzip),
+                "deflate" => codings.push(Encoding::Deflate),
+                _ => return Err(DecodeError::Unsupported),
+            }
+        }
+    }
+    if codings.len() > MAX_CODINGS {
+        return Err(DecodeError::Unsupported);
+    }
+    if !headers
+        .iter()
+        .any(|(k, _)| k.eq_ignore_ascii_case(HEADER_CONTENT_ENCODING))
+    {
+        return Ok(body);
+    }
+
+    let body = match body {
+        Some(mut bytes) => {
+            for encoding in codings.into_iter().rev() {
+                bytes = decompress(&bytes, encoding, max_size)?;
+            }
+            Some(bytes).filter(|bytes| !bytes.is_empty())
+        }
+        None => None,
+    };
+
+    headers.retain(|(k, _)| !k.eq_ignore_ascii_case(HEADER_CONTENT_ENCODING));
+    if let Some((_, length)) = headers
+        .iter_mut()
+        .find(|(k, _)| k.eq_ignore_ascii_case(HEADER_CONTENT_LENGTH))
+    {
+        *length = body.as_ref().map_or(0, Vec::len).to_string();
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_body() -> Vec<u8> {
+        let item = r#"{"id":1,"name":"Alice","email":"alice@example.com"}"#;
+        format!("[{}]", vec![item; 100].join(",")).into_bytes()
+    }
+
+    fn json_headers() -> Vec<(String, String)> {
+        vec![("content-type".to_string(), "application/json".to_string())]
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        find_header(headers, name)
+    }
+
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate("gzip, deflate, br"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("deflate"), Some(Encoding::Deflate));
+        assert_eq!(negotiate("gzip;q=0.5, deflate"), Some(Encoding::Deflate));
+        assert_eq!(negotiate("GZIP"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("x-gzip"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("*"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("*;q=0.1, gzip;q=0"), Some(Encoding::Deflate));
+        assert_eq!(negotiate("br, identity"), None);
+        assert_eq!(negotiate("gzip;q=0"), None);
+        assert_eq!(negotiate(""), None);
+    }
+
+    #[test]
+    fn test_is_compressible() {
+        assert!(is_compressible(Some("application/json")));
+        assert!(is_compressible(Some("text/csv; charset=utf-8")));
+        assert!(is_compressible(Some("image/svg+xml")));
+        assert!(is_compressible(None));
+        assert!(!is_compressible(Some("image/png")));
+        assert!(!is_compressible(Some("video/mp4")));
+        assert!(!is_compressible(Some("application/zip")));
+        assert!(!is_compressible(Some("Application/GZIP")));
+    }
+
+    #[test]
+    fn test_gzip_roundtrip() {
+        let body = json_body();
+        let gz = compress(&body, Encoding::Gzip, DEFAULT_LEVEL);
+        assert_eq!(&gz[..3], &[0x1f, 0x8b, 8]);
+        let inflated =
+            miniz_oxide::inflate::decompress_to_vec(&gz[10..gz.len() - 8]).expect("valid deflate");
+        assert_eq!(inflated, body);
+        assert_eq!(gz[gz.len() - 8..gz.len() - 4], crc32(&body).to_le_bytes());
+    }
+
+    #[test]
+    fn test_deflate_is_zlib() {
+        let body = json_body();
+        let zlib = compress(&body, Encoding::Deflate, DEFAULT_LEVEL);
+        let inflated = miniz_oxide::inflate::decompress_to_vec_zlib(&zlib).expect("valid zlib");
+        assert_eq!(inflated, body);
+    }
+
+    #[test]
+    fn test_compress_response_sets_headers() {
+        let mut headers = json_headers();
+        headers.push(("content-length".to_string(), "5101".to_string()));
+        headers.push(("ETag".to_string(), "\"v1\"".to_string()));
+        let mut body = json_body();
+        let original_len = body.len();
+
+        let config = CompressionConfig::default();
+        compress_response(&config, Some("gzip"), 200, &mut headers, &mut body);
+
+        assert!(body.len() < original_len);
+        assert_eq!(header(&headers, "content-encoding"), Some("gzip"));
+        assert_eq!(header(&headers, "content-length"), None);
+        assert_eq!(header(&headers, "vary"), Some("Accept-Encoding"));
+        assert_eq!(header(&headers, "etag"), Some("W/\"v1\""));
+    }
+
+    #[test]
+    fn test_compress_response_extends_vary() {
+        let mut headers = json_headers();
+        headers.push(("Vary".to_string(), "Origin".to_string()));
+        let mut body = json_body();
+        compress_response(
+            &CompressionConfig::default(),
+            Some("deflate"),
+            200,
+            &mut headers,
+            &mut body,
+        );
+        assert_eq!(header(&headers, "vary"), Some("Origin, Accept-Encoding"));
+    }
+
+    #[test]
+    fn test_compress_response_skips() {
+        type Case = (Option<&'static str>, u16, Vec<(String, String)>, Vec<u8>);
+        let config = CompressionConfig::default();
+        let cases: Vec<Case> = vec![
+            // Client did not ask
+            (None, 200, json_headers(), json_body()),
+            (Some("br"), 200, json_headers(), json_body()),
+            // Too small
+            (Some("gzip"), 200, json_headers(), b"{}".to_vec()),
+            // Already encoded
+            (
+                Some("gzip"),
+                200,
+                vec![("Content-Encoding".to_string(), "br".to_string())],
+                json_body(),
+            ),
+            // Compressed media
+            (
+                Some("gzip"),
+                200,
+                vec![("content-type".to_string(), "image/png".to_string())],
+                json_body(),
+            ),
+        ];
+        for (accept, status, mut headers, mut body) in cases {
+            let before = (headers.clone(), body.clone());
+            compress_response(&config, accept, status, &mut headers, &mut body);
+            assert_eq!((headers, body), before, "accept={accept:?}");
+        }
+    }
+
+    #[test]
+    fn test_compress_response_disabled() {
+        let config = CompressionConfig {
+            enabled: false,
+            ..CompressionConfig::default()
+        };
+        let mut headers = json_headers();
+        let mut body = json_body();
+        compress_response(&config, Some("gzip"), 200, &mut headers, &mut body);
+        assert_eq!(header(&headers, "content-encoding"), None);
+    }
+
+    #[test]
+    fn test_incompressible_body_is_kept() {
+        // Pseudo-random bytes do not shrink
+        let mut state = 0x1234_5678u32;
+        let mut body: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_le_bytes()[0]
+            })
+            .collect();
+        let original = body.clone();
+        let mut headers = json_headers();
+        compress_response(
+            &CompressionConfig::default(),
+            Some("gzip"),
+            200,
+            &mut headers,
+            &mut body,
+        );
+        assert_eq!(body, original);
+        assert_eq!(header(&headers, "content-encoding"), None);
+    }
+
+    #[test]
+    fn test_config_from_env() {
+        let vars = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect()
+        };
+        assert_eq!(
+            CompressionConfig::from_env(&[]),
+            CompressionConfig::default()
+        );
+
+        let config = CompressionConfig::from_env(&vars(&[
+            ("MIK_COMPRESSION", "off"),
+            ("MIK_COMPRESSION_MIN_SIZE", "256"),
+            ("MIK_COMPRESSION_LEVEL", "9"),
+        ]));
+        assert!(!config.enabled);
+        assert_eq!(config.min_size, 256);
+        assert_eq!(config.level, 9);
+
+        let config = CompressionConfig::from_env(&vars(&[
+            ("MIK_COMPRESSION_MIN_SIZE", "lots"),
+            ("MIK_COMPRESSION_LEVEL", "42"),
+        ]));
+        assert_eq!(config, CompressionConfig::default());
+    }
+
+    // ========================================================================
+    // Request decoding
+    // ========================================================================
+
+    fn encoded_headers(encoding: &str) -> Vec<(String, String)> {
+        vec![
+            ("content-type".to_string(), "application/json".to_string()),
+            ("Content-Encoding".to_string(), encoding.to_string()),
+            ("content-length".to_string(), "42".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_decode_request_gzip() {
+        let body = json_body();
+        let mut headers = encoded_headers("gzip");
+        let decoded = decode_request(
+            &mut headers,
+            Some(compress(&body, Encoding::Gzip, DEFAULT_LEVEL)),
+            1 << 20,
+        );
+        assert_eq!(decoded, Ok(Some(body.clone())));
+        assert_eq!(header(&headers, "content-encoding"), None);
+        assert_eq!(
+            header(&headers, "content-length"),
+            Some(body.len().to_string().as_str())
+        );
+    }
+
+    #[test]
+    fn test_decode_request_deflate_zlib_and_raw() {
+        let body = json_body();
+        let zlib = compress(&body, Encoding::Deflate, DEFAULT_LEVEL);
+        let raw = miniz_oxide::deflate::compress_to_vec(&body, DEFAULT_LEVEL);
+        for encoded in [zlib, raw] {
+            let mut headers = encoded_headers("deflate");
+            let decoded = decode_request(&mut headers, Some(encoded), 1 << 20);
+            assert_eq!(decoded, Ok(Some(body.clone())));
+        }
+    }
+
+    #[test]
+    fn test_decode_request_stacked_codings() {
+        let body = json_body();
+        let gz = compress(&body, Encoding::Gzip, DEFAULT_LEVEL);
+        let encoded = compress(&gz, Encoding::Deflate, DEFAULT_LEVEL);
+        let mut headers = encoded_headers("gzip, deflate");
+        assert_eq!(
+            decode_request(&mut headers, Some(encoded), 1 << 20),
+            Ok(Some(body))
+        );
+    }
+
+    #[test]
+    fn test_decode_request_gzip_optional_header_fields() {
+        let body = b"hello".to_vec();
+        let gz = compress(&body, Encoding::Gzip, DEFAULT_LEVEL);
+        // Rebuild with FEXTRA + FNAME set
+        let mut framed = vec![0x1f, 0x8b, 8, 0x04 | 0x08, 0, 0, 0, 0, 0, 0xff];
+        framed.extend_from_slice(&[3, 0, b'a', b'b', b'c']);
+        framed.extend_from_slice(b"hello.txt\0");
+        framed.extend_from_slice(&gz[10..]);
+        assert_eq!(decompress(&framed, Encoding::Gzip, 1024), Ok(body));
+    }
+
+    #[test]
+    fn test_decode_request_enforces_decoded_size() {
+        // 1MB of zeros compresses to about 1KB
+        let bomb = compress(&vec![0u8; 1 << 20], Encoding::Gzip, DEFAULT_LEVEL);
+        assert!(bomb.len() < 8 * 1024);
+        let mut headers = encoded_headers("gzip");
+        assert_eq!(
+            decode_request(&mut headers, Some(bomb), 64 * 1024),
+            Err(DecodeError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn test_decode_request_rejects_bad_input() {
+        let mut headers = encoded_headers("br");
+        assert_eq!(
+            decode_request(&mut headers, None, 1024),
+            Err(DecodeError::Unsupported)
+        );
+        let mut headers = encoded_headers("gzip, gzip, gzip");
+        assert_eq!(
+            decode_request(&mut headers, Some(b"x".to_vec()), 1024),
+            Err(DecodeError::Unsupported)
+        );
+
+        let mut headers = encoded_headers("gzip");
+        assert_eq!(
+            decode_request(
+                &mut headers,
+                Some(b"not gzip at all, really".to_vec()),
+                1024
+            ),
+            Err(DecodeError::Invalid)
+        );
+
+        let mut corrupt = compress(&json_body(), Encoding::Gzip, DEFAULT_LEVEL);
+        let crc_pos = corrupt.len() - 8;
+        corrupt[crc_pos] ^= 0xff;
+        assert_eq!(
+            decompress(&corrupt, Encoding::Gzip, 1 << 20),
+            Err(DecodeError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_decode_request_passthrough() {
+        let mut headers = json_headers();
+        let body = Some(b"{}".to_vec());
+        assert_eq!(decode_request(&mut headers, body.clone(), 1024), Ok(body));
+        assert_eq!(headers, json_headers());
+
+        let mut headers = encoded_headers("identity");
+        let body = Some(b"{}".to_vec());
+        assert_eq!(decode_request(&mut headers, body.clone(), 1024), Ok(body));
+        assert_eq!(header(&headers, "content-encoding"), None);
+    }
+}
@@ -12,6 +12,43 @@
 //!
 //! - `MIK_MAX_BODY_SIZE`: Maximum request body size in bytes (default: 10MB)
 //!   Example: `MIK_MAX_BODY_SIZE=52428800` for 50MB
+//! - `MIK_COMPRESSION`: Set to `off` to disable response compression (default: on)
+//! - `MIK_COMPRESSION_MIN_SIZE`: Smallest response body to compress, in bytes
+//!   (default: 1024)
+//! - `MIK_COMPRESSION_LEVEL`: Compression level from 1 (fastest) to 9 (smallest)
+//!   (default: 6)
+//! - `MIK_ACCESS_LOG`: Set to `off` to disable access logging (default: on)
+//!
+//! Response bodies are gzip- or deflate-encoded when the client sends a
+//! matching `Accept-Encoding`. Already-compressed media (images, audio, video,
+//! archives, fonts) and responses that set their own `Content-Encoding` are
+//! sent as-is.
+//!
+//! Request bodies sent with `Content-Encoding: gzip` or `deflate` are decoded
+//! before they reach the handler, and the header is removed. Other codings
+//! receive a 415 response.
+//!
+//! ## Streaming Responses
+//!
+//! When `take-body-stream` returns a stream after `handle`, the bridge sends
+//! the response head right away and then writes each chunk as the handler
+//! produces it, so the body never has to fit in memory. Streamed bodies are
+//! not compressed.
+//!
+//! ## Request IDs and Access Logs
+//!
+//! Every request carries an ID. An incoming `traceparent` (or `x-request-id`)
+//! is reused; otherwise the bridge generates a UUID v4 from `wasi:random` and
+//! adds it to the handler's request as `traceparent`, so
+//! `Request::trace_id_or` always sees one. The ID is echoed in the
+//! `x-request-id` response header.
+//!
+//! After each request the bridge writes one JSON line to stderr, in the
+//! `mik_sdk::log` format:
+//!
+//! ```json
+//! {"level":"info","msg":"request","method":"GET","path":"/users","status":"200","duration_ms":"1.250","bytes":"512","request_id":"...","ts":"2025-01-16T10:30:00.123Z"}
+//! ```
 //!
 //! ## Security Considerations
 //!
@@ -31,17 +68,24 @@
 //!
 //! The bridge enforces `MIK_MAX_BODY_SIZE` to prevent memory exhaustion from
 //! large request bodies. Requests exceeding this limit receive a 413 response.
+//! The limit also applies to the decoded size of compressed request bodies, so
+//! a small compressed upload cannot expand into an oversized one.
 
+mod access_log;
 #[allow(warnings)]
 mod bindings;
+mod compression;
 
 use bindings::exports::wasi::http::incoming_handler::Guest;
 use bindings::mik::core::handler::{self, Method, RequestData};
 use bindings::wasi::cli::environment;
 use bindings::wasi::cli::stderr;
+use bindings::wasi::clocks::{monotonic_clock, wall_clock};
 use bindings::wasi::http::types::{
     Fields, IncomingRequest, OutgoingBody, OutgoingResponse, ResponseOutparam,
 };
+use bindings::wasi::io::streams::{OutputStream, StreamError};
+use compression::{CompressionConfig, DecodeError};
 use std::sync::OnceLock;
 
 /// Default maximum request body size (10MB).
@@ -54,6 +98,9 @@ const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 /// Content-Type header name.
 const HEADER_CONTENT_TYPE: &str = "content-type";
 
+/// Response header echoing the request ID.
+const HEADER_REQUEST_ID: &str = "x-request-id";
+
 /// RFC 7807 Problem Details MIME type.
 const MIME_PROBLEM_JSON: &[u8] = b"application/problem+json";
 
@@ -67,6 +114,7 @@ const fn status_title(code: u16) -> &'static str {
         404 => "Not Found",
         405 => "Method Not Allowed",
         413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
         422 => "Unprocessable Entity",
         429 => "Too Many Requests",
         500 => "Internal Server Error",
@@ -107,6 +155,48 @@ fn get_max_body_size() -> usize {
     })
 }
 
+/// Cached response compression settings from environment.
+static COMPRESSION: OnceLock<CompressionConfig> = OnceLock::new();
+
+/// Returns the response compression settings.
+///
+/// Reads `MIK_COMPRESSION`, `MIK_COMPRESSION_MIN_SIZE` and
+/// `MIK_COMPRESSION_LEVEL` on first call; invalid values fall back to the
+/// defaults. Cached for the lifetime of the component, like
+/// [`get_max_body_size`].
+fn get_compression_config() -> &'static CompressionConfig {
+    COMPRESSION.get_or_init(|| CompressionConfig::from_env(&environment::get_environment()))
+}
+
+/// Cached access log switch from environment.
+static ACCESS_LOG: OnceLock<bool> = OnceLock::new();
+
+/// Returns whether an access-log line is written per request.
+///
+/// Reads `MIK_ACCESS_LOG` on first call; `off`, `false` or `0` disable it.
+fn access_log_enabled() -> bool {
+    *ACCESS_LOG.get_or_init(|| {
+        environment::get_environment()
+            .into_iter()
+            .find(|(k, _)| k == "MIK_ACCESS_LOG")
+            .is_none_or(|(_, v)| {
+                !matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "off" | "false" | "0"
+                )
+            })
+    })
+}
+
+/// Generate a UUID v4 request ID from `wasi:random`.
+fn generate_request_id() -> String {
+    let random = bindings::wasi::random::random::get_random_bytes(16);
+    let mut bytes = [0u8; 16];
+    let len = random.len().min(16);
+    bytes[..len].copy_from_slice(&random[..len]);
+    access_log::format_uuid_v4(bytes)
+}
+
 /// Bridge component implementing WASI HTTP to mik handler translation.
 ///
 /// This is the core component that enables portable HTTP handlers:
@@ -126,117 +216,244 @@ struct Bridge;
 ///
 /// ## Request Flow
 ///
-/// 1. Extract path, method, headers from WASI HTTP request
-/// 2. Read body with size limit enforcement (413 if exceeded)
+/// 1. Extract path, method, headers from WASI HTTP request, and ensure a
+///    request ID
+/// 2. Read body with size limit enforcement (413 if exceeded), decoding
+///    gzip/deflate `Content-Encoding`
 /// 3. Convert to `mik:core/handler::RequestData`
 /// 4. Call user's `handler::handle()` function
-/// 5. Convert response and send via WASI HTTP
+/// 5. Compress the body if the client accepts gzip/deflate (buffered bodies only)
+/// 6. Send the response head, then the body and any streamed chunks
+/// 7. Write the access-log line
 ///
 /// ## Error Handling
 ///
-/// - Unsupported HTTP methods (CONNECT, TRACE) → 501 Not Implemented
+/// - CONNECT → 501 Not Implemented (tunnelling is left to the host)
 /// - Body exceeds `MIK_MAX_BODY_SIZE` → 413 Payload Too Large
+///   (checked before and after decoding)
+/// - Unsupported `Content-Encoding` → 415 Unsupported Media Type
+/// - Malformed gzip/deflate body → 400 Bad Request
 /// - Invalid status codes are clamped to 500 with error logging
 /// - Invalid UTF-8 headers are silently dropped with error logging
 impl Guest for Bridge {
     fn handle(request: IncomingRequest, response_out: ResponseOutparam) {
-        // 1. Extract data from WASI HTTP request
+        let started = monotonic_clock::now();
+
+        // 1. Extract data from WASI HTTP request and tag it with a request ID
         let path = request.path_with_query().unwrap_or_default();
+        let method_name = method_name(&request.method());
+        let mut headers = extract_headers(&request);
+        let request_id = access_log::ensure_request_id(&mut headers, generate_request_id);
+
+        let (status, bytes) = serve(&request, response_out, path.clone(), headers, &request_id);
+
+        if access_log_enabled() {
+            let now = wall_clock::now();
+            let entry = access_log::AccessLog {
+                method: &method_name,
+                path: &path,
+                status,
+                duration_ns: monotonic_clock::now().saturating_sub(started),
+                bytes,
+                request_id: &request_id,
+            };
+            let line = entry.to_json(&access_log::to_iso(now.seconds, now.nanoseconds));
+            let stream = stderr::get_stderr();
+            let _ = stream.blocking_write_and_flush(format!("{line}\n").as_bytes());
+        }
+    }
+}
 
-        // Check for unsupported HTTP methods first
-        let method = match convert_method(request.method()) {
-            Some(m) => m,
-            None => {
-                // Return 501 Not Implemented for unsupported methods
-                send_error_response(response_out, 501, status_title(501), &path);
-                return;
-            }
-        };
-        let headers = extract_headers(&request);
-
-        // 2. Read body with size limit check
-        let body = match read_body(&request) {
-            BodyResult::Ok(body) => body,
-            BodyResult::TooLarge => {
-                // Return 413 Payload Too Large
-                send_error_response(response_out, 413, status_title(413), &path);
-                return;
-            }
-        };
-
-        // 3. Build mik request-data
-        let mik_request = RequestData {
-            method,
-            path,
-            headers,
-            body,
-        };
-
-        // 4. Call the user's handler
-        let mik_response = handler::handle(&mik_request);
-
-        // 5. Convert to WASI HTTP response and send
-        // Note: Fields resource is consumed by OutgoingResponse::new().
-        // We scope it explicitly to ensure proper WASI resource lifecycle.
-        let outgoing = {
-            let headers = Fields::new();
-            for (name, value) in mik_response.headers {
-                let _ = headers.append(&name, &value.into_bytes());
-            }
-            // Ownership of headers transfers to OutgoingResponse here
-            OutgoingResponse::new(headers)
-        };
-        // Validate and clamp status code to valid HTTP range (100-599)
-        let status_code = if mik_response.status < 100 {
-            log_error(&format!(
-                "Invalid HTTP status code {}: must be >= 100, using 500",
-                mik_response.status
-            ));
-            500
-        } else if mik_response.status >= 600 {
-            log_error(&format!(
-                "Invalid HTTP status code {}: must be < 600, using 500",
-                mik_response.status
-            ));
-            500
-        } else {
+/// Serve one request: steps 2-6 of the request flow.
+///
+/// Always sets `response_out`, and returns the status code and response body
+/// size for the access log. `request_id` is echoed in the `x-request-id`
+/// response header unless the handler set one.
+fn serve(
+    request: &IncomingRequest,
+    response_out: ResponseOutparam,
+    path: String,
+    mut headers: Vec<(String, String)>,
+    request_id: &str,
+) -> (u16, usize) {
+    let echo_id = [(HEADER_REQUEST_ID, request_id)];
+
+    // CONNECT is the only method the handler never sees
+    let method = match convert_method(request.method()) {
+        Some(m) => m,
+        None => {
+            // Return 501 Not Implemented for unsupported methods
+            let bytes = send_error_response(response_out, 501, status_title(501), &path, &echo_id);
+            return (501, bytes);
+        }
+    };
+    let accept_encoding = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("accept-encoding"))
+        .map(|(_, v)| v.clone());
+
+    // 2. Read body with size limit check, then undo any Content-Encoding
+    let body = match read_body(request) {
+        BodyResult::Ok(body) => body,
+        BodyResult::TooLarge => {
+            // Return 413 Payload Too Large
+            let bytes = send_error_response(response_out, 413, status_title(413), &path, &echo_id);
+            return (413, bytes);
+        }
+    };
+    let body = match compression::decode_request(&mut headers, body, get_max_body_size()) {
+        Ok(body) => body,
+        Err(e) => {
+            let mut extra_headers = echo_id.to_vec();
+            let status = match e {
+                DecodeError::Unsupported => {
+                    // RFC 7694: advertise the codings we do accept
+                    extra_headers.push(("accept-encoding", "gzip, deflate"));
+                    415
+                }
+                DecodeError::TooLarge => 413,
+                DecodeError::Invalid => 400,
+            };
+            let title = status_title(status);
+            let bytes = send_error_response(response_out, status, title, &path, &extra_headers);
+            return (status, bytes);
+        }
+    };
+
+    // 3. Build mik request-data
+    let mik_request = RequestData {
+        method,
+        path,
+        headers,
+        body,
+    };
+
+    // 4. Call the user's handler
+    let mik_response = handler::handle(&mik_request);
+    let body_stream = handler::take_body_stream();
+
+    // Validate and clamp status code to valid HTTP range (100-599)
+    let status_code = if mik_response.status < 100 {
+        log_error(&format!(
+            "Invalid HTTP status code {}: must be >= 100, using 500",
             mik_response.status
-        };
-        let _ = outgoing.set_status_code(status_code);
-
-        // Get body handle and ensure it's always finished per WASI HTTP spec.
-        // OutgoingBody::finish() must be called whenever we successfully get a body handle.
-        match outgoing.body() {
-            Ok(body_handle) => {
-                // Write body bytes if present
-                if let Some(body_bytes) = mik_response.body.as_ref() {
-                    match body_handle.write() {
-                        Ok(stream) => {
-                            if let Err(e) = stream.blocking_write_and_flush(body_bytes) {
-                                log_error(&format!("Failed to write response body: {:?}", e));
-                            }
-                            // Explicitly drop stream before calling finish (WASI resource cleanup)
-                            drop(stream);
-                        }
-                        Err(e) => {
-                            log_error(&format!("Failed to get body write stream: {:?}", e));
-                        }
-                    }
+        ));
+        500
+    } else if mik_response.status >= 600 {
+        log_error(&format!(
+            "Invalid HTTP status code {}: must be < 600, using 500",
+            mik_response.status
+        ));
+        500
+    } else {
+        mik_response.status
+    };
+
+    // Echo the request ID unless the handler set its own
+    let mut response_headers = mik_response.headers;
+    if !response_headers
+        .iter()
+        .any(|(k, _)| k.eq_ignore_ascii_case(HEADER_REQUEST_ID))
+    {
+        response_headers.push((HEADER_REQUEST_ID.to_string(), request_id.to_string()));
+    }
+
+    // 5. Compress the body when negotiated (streamed bodies are sent as-is)
+    let mut response_body = mik_response.body;
+    if let (Some(body_bytes), None) = (response_body.as_mut(), &body_stream) {
+        compression::compress_response(
+            get_compression_config(),
+            accept_encoding.as_deref(),
+            status_code,
+            &mut response_headers,
+            body_bytes,
+        );
+    }
+
+    // 6. Convert to WASI HTTP response and send
+    // Note: Fields resource is consumed by OutgoingResponse::new().
+    // We scope it explicitly to ensure proper WASI resource lifecycle.
+    let outgoing = {
+        let headers = Fields::new();
+        for (name, value) in response_headers {
+            let _ = headers.append(&name, &value.into_bytes());
+        }
+        // Ownership of headers transfers to OutgoingResponse here
+        OutgoingResponse::new(headers)
+    };
+    let _ = outgoing.set_status_code(status_code);
+
+    // Get body handle and ensure it's always finished per WASI HTTP spec.
+    // OutgoingBody::finish() must be called whenever we successfully get a body handle.
+    let body_handle = outgoing.body();
+
+    // Send the head before the body so streamed chunks reach the client as they are produced
+    ResponseOutparam::set(response_out, Ok(outgoing));
+
+    let mut bytes_sent = 0;
+    match body_handle {
+        Ok(body_handle) => {
+            match body_handle.write() {
+                Ok(stream) => {
+                    bytes_sent =
+                        write_body(&stream, response_body.as_deref(), body_stream.as_ref());
+                    // Explicitly drop stream before calling finish (WASI resource cleanup)
+                    drop(stream);
                 }
-                // Always finish the body handle (required by WASI HTTP spec)
-                if let Err(e) = OutgoingBody::finish(body_handle, None) {
-                    log_error(&format!("Failed to finish response body: {:?}", e));
+                Err(e) => {
+                    log_error(&format!("Failed to get body write stream: {:?}", e));
                 }
             }
-            Err(e) => {
-                // body() failed - no body handle to finish, but log for debugging
-                log_error(&format!("Failed to get response body handle: {:?}", e));
+            // Always finish the body handle (required by WASI HTTP spec)
+            if let Err(e) = OutgoingBody::finish(body_handle, None) {
+                log_error(&format!("Failed to finish response body: {:?}", e));
             }
         }
+        Err(e) => {
+            // body() failed - no body handle to finish, but log for debugging
+            log_error(&format!("Failed to get response body handle: {:?}", e));
+        }
+    }
+
+    (status_code, bytes_sent)
+}
+
+/// Maximum bytes `blocking-write-and-flush` accepts in one call.
+const MAX_WRITE_SIZE: usize = 4096;
+
+/// Write the buffered body, then each streamed chunk as the handler produces it.
+///
+/// Returns the number of bytes written. Stops at the first write error (e.g.
+/// the client went away) without pulling further chunks.
+fn write_body(
+    out: &OutputStream,
+    body: Option<&[u8]>,
+    stream: Option<&handler::BodyStream>,
+) -> usize {
+    let mut written = 0;
+    if let Some(body) = body {
+        if let Err(e) = write_all(out, body) {
+            log_error(&format!("Failed to write response body: {:?}", e));
+            return written;
+        }
+        written += body.len();
+    }
+    while let Some(chunk) = stream.and_then(handler::BodyStream::next_chunk) {
+        if let Err(e) = write_all(out, &chunk) {
+            log_error(&format!("Failed to write response body: {:?}", e));
+            break;
+        }
+        written += chunk.len();
+    }
+    written
+}
 
-        // Set response exactly once at the end
-        ResponseOutparam::set(response_out, Ok(outgoing));
+/// Write `bytes` in pieces `blocking-write-and-flush` accepts.
+fn write_all(out: &OutputStream, bytes: &[u8]) -> Result<(), StreamError> {
+    for piece in bytes.chunks(MAX_WRITE_SIZE) {
+        out.blocking_write_and_flush(piece)?;
     }
+    Ok(())
 }
 
 /// Log an error message to stderr.
@@ -245,10 +462,28 @@ fn log_error(msg: &str) {
     let _ = stream.blocking_write_and_flush(format!("[mik-bridge] ERROR: {}\n", msg).as_bytes());
 }
 
+/// HTTP method name for the access log, including unsupported methods.
+fn method_name(m: &bindings::wasi::http::types::Method) -> String {
+    use bindings::wasi::http::types::Method as WasiMethod;
+    match m {
+        WasiMethod::Get => "GET".to_string(),
+        WasiMethod::Post => "POST".to_string(),
+        WasiMethod::Put => "PUT".to_string(),
+        WasiMethod::Delete => "DELETE".to_string(),
+        WasiMethod::Patch => "PATCH".to_string(),
+        WasiMethod::Head => "HEAD".to_string(),
+        WasiMethod::Options => "OPTIONS".to_string(),
+        WasiMethod::Connect => "CONNECT".to_string(),
+        WasiMethod::Trace => "TRACE".to_string(),
+        WasiMethod::Other(other) => other.clone(),
+    }
+}
+
 /// Convert WASI HTTP method to mik HTTP method.
 ///
-/// Returns `None` for unsupported methods (Connect, Trace, Other).
-/// Callers should return 501 Not Implemented for these.
+/// TRACE and extension methods (e.g., `PROPFIND`) are passed through; the
+/// handler answers them with 405 if no route accepts them. Returns `None` for
+/// CONNECT, which callers answer with 501 Not Implemented.
 fn convert_method(m: bindings::wasi::http::types::Method) -> Option<Method> {
     use bindings::wasi::http::types::Method as WasiMethod;
     match m {
@@ -259,8 +494,10 @@ fn convert_method(m: bindings::wasi::http::types::Method) -> Option<Method> {
         WasiMethod::Patch => Some(Method::Patch),
         WasiMethod::Head => Some(Method::Head),
         WasiMethod::Options => Some(Method::Options),
-        // Unsupported methods - return None to trigger 501
-        WasiMethod::Connect | WasiMethod::Trace | WasiMethod::Other(_) => None,
+        WasiMethod::Trace => Some(Method::Trace),
+        WasiMethod::Other(name) => Some(Method::Other(name)),
+        // CONNECT asks for a tunnel, which a handler cannot provide
+        WasiMethod::Connect => None,
     }
 }
 
@@ -408,7 +645,14 @@ fn escape_json_string(s: &str) -> String {
 /// Send an RFC 7807 error response.
 ///
 /// Includes the request path in the `instance` field per RFC 7807 for debugging.
-fn send_error_response(response_out: ResponseOutparam, status: u16, title: &str, instance: &str) {
+/// `extra_headers` are added after `Content-Type`. Returns the body size.
+fn send_error_response(
+    response_out: ResponseOutparam,
+    status: u16,
+    title: &str,
+    instance: &str,
+    extra_headers: &[(&str, &str)],
+) -> usize {
     // Escape the instance path for JSON per RFC 7158
     let escaped_instance = escape_json_string(instance);
 
@@ -419,6 +663,9 @@ fn send_error_response(response_out: ResponseOutparam, status: u16, title: &str,
 
     let headers = Fields::new();
     let _ = headers.append(HEADER_CONTENT_TYPE, MIME_PROBLEM_JSON);
+    for (name, value) in extra_headers {
+        let _ = headers.append(name, value.as_bytes());
+    }
 
     let outgoing = OutgoingResponse::new(headers);
     let _ = outgoing.set_status_code(status);
@@ -435,6 +682,7 @@ fn send_error_response(response_out: ResponseOutparam, status: u16, title: &str,
     }
 
     ResponseOutparam::set(response_out, Ok(outgoing));
+    body_json.len()
 }
 
 bindings::export!(Bridge with_types_in bindings);
@@ -461,6 +709,7 @@ mod tests {
         assert_eq!(status_title(404), "Not Found");
         assert_eq!(status_title(405), "Method Not Allowed");
         assert_eq!(status_title(413), "Payload Too Large");
+        assert_eq!(status_title(415), "Unsupported Media Type");
         assert_eq!(status_title(422), "Unprocessable Entity");
         assert_eq!(status_title(429), "Too Many Requests");
         assert_eq!(status_title(500), "Internal Server Error");
@@ -3,3 +3,25 @@
 /// Valid HTTP methods for routes and fetch macros.
 pub const VALID_HTTP_METHODS: &[&str] =
     &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Methods `routes!` accepts as bare identifiers (others are string literals).
+pub const VALID_ROUTE_METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE",
+];
+
+/// Most distinct custom methods one `routes!` block may use (allowed-methods
+/// mask bits left after the standard methods).
+pub const MAX_CUSTOM_METHODS: usize = 56;
+
+/// `#[field(format = ...)]` values `#[derive(Type)]` checks at runtime, each
+/// with a matching `mik_sdk::typed::formats::is_*` function.
+pub const CHECKED_FORMATS: &[&str] = &[
+    "email",
+    "uuid",
+    "date",
+    "date-time",
+    "uri",
+    "ipv4",
+    "ipv6",
+    "hostname",
+];
@@ -1,10 +1,12 @@
-//! Derive macros for typed inputs: Type, Query, Path.
+//! Derive macros for typed inputs: Type, Query, Path, Headers, Cookies.
 //!
-//! These generate implementations for FromJson, FromQuery, FromPath traits,
-//! along with OpenAPI schema generation and optional validation.
+//! These generate implementations for FromJson, FromQuery, FromPath, FromHeaders
+//! and FromCookies traits, along with OpenAPI schema generation and optional
+//! validation.
 
 mod path_derive;
 mod query_derive;
+pub mod regex;
 mod type_derive;
 
 use std::fmt::Write;
@@ -15,6 +17,7 @@ use quote::quote;
 use syn::{Attribute, Data, DeriveInput, Expr, Fields, Lit, Type};
 
 use crate::errors::did_you_mean;
+use type_derive::case::{RENAME_RULES, RenameRule};
 
 /// Valid #[field(...)] attributes.
 /// Note: x_* attributes are also valid for OpenAPI extensions.
@@ -27,6 +30,19 @@ const VALID_FIELD_ATTRS: &[&str] = &[
     "rename",
     "docs",
     "deprecated",
+    "flatten",
+    "skip",
+    "skip_if",
+];
+
+/// Valid #[mik(...)] container attributes.
+const VALID_TYPE_ATTRS: &[&str] = &[
+    "rename_all",
+    "deny_unknown_fields",
+    "default",
+    "tag",
+    "content",
+    "untagged",
 ];
 
 /// Value types for x-* extension attributes.
@@ -40,7 +56,7 @@ pub enum XAttrValue {
 
 // Re-export the public entry points
 pub use path_derive::derive_path_impl;
-pub use query_derive::derive_query_impl;
+pub use query_derive::{derive_cookies_impl, derive_headers_impl, derive_query_impl};
 pub use type_derive::derive_type_impl;
 
 // ============================================================================
@@ -85,6 +101,12 @@ pub struct FieldAttrs {
     pub(crate) x_attrs: Vec<(String, XAttrValue)>,
     /// Mark field as deprecated in OpenAPI schema
     pub(crate) deprecated: bool,
+    /// Inline the fields of a nested Type into the parent object
+    pub(crate) flatten: bool,
+    /// Leave the field out of JSON, filling it from `Default` when parsing
+    pub(crate) skip: bool,
+    /// Omit the field from output when this predicate returns true
+    pub(crate) skip_if: Option<syn::Path>,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -149,7 +171,20 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
                 let value: Lit = meta.value()?.parse()?;
                 match value {
                     Lit::Str(lit) => {
-                        result.pattern = Some(lit.value());
+                        let pattern = lit.value();
+                        if let Err(reason) = regex::parse(&pattern) {
+                            return Err(syn::Error::new_spanned(
+                                &lit,
+                                format!(
+                                    "Unsupported pattern: {reason}.\n\
+                                     \n\
+                                     ✅ Supported: literals, . ^ $, [a-z] [^0-9], \\d \\w \\s,\n\
+                                     (groups|alternatives), * + ? {{n}} {{n,}} {{n,m}}\n\
+                                     ❌ Not supported: backreferences, lookaround, \\b"
+                                ),
+                            ));
+                        }
+                        result.pattern = Some(pattern);
                     },
                     _ => {
                         return Err(syn::Error::new_spanned(
@@ -210,6 +245,26 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
                         ));
                     },
                 }
+            } else if meta.path.is_ident("flatten") {
+                result.flatten = true;
+            } else if meta.path.is_ident("skip") {
+                result.skip = true;
+            } else if meta.path.is_ident("skip_if") {
+                let value: Lit = meta.value()?.parse()?;
+                match value {
+                    Lit::Str(lit) => {
+                        result.skip_if = Some(lit.parse()?);
+                    },
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &value,
+                            "skip_if needs a function path as a string!\n\
+                             \n\
+                             ✅ Correct: #[field(skip_if = \"Option::is_none\")]\n\
+                             ❌ Wrong:   #[field(skip_if = Option::is_none)]",
+                        ));
+                    },
+                }
             } else {
                 let path = &meta.path;
                 let attr_name = quote!(#path).to_string();
@@ -258,11 +313,14 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
                              #[field(min = 1)]           // minimum value/length\n\
                              #[field(max = 100)]         // maximum value/length\n\
                              #[field(default = 10)]      // default value\n\
-                             #[field(format = \"email\")] // format hint (OpenAPI)\n\
-                             #[field(pattern = \"...\")]  // regex pattern (OpenAPI)\n\
+                             #[field(format = \"email\")] // string format\n\
+                             #[field(pattern = \"...\")]  // regex pattern\n\
                              #[field(rename = \"...\")]   // JSON key name\n\
                              #[field(docs = \"...\")]     // description\n\
                              #[field(deprecated = true)] // mark as deprecated\n\
+                             #[field(flatten)]           // inline a nested Type\n\
+                             #[field(skip)]              // leave out, use Default\n\
+                             #[field(skip_if = \"...\")]  // omit from output when true\n\
                              #[field(x_* = ...)]         // OpenAPI x-* extensions"
                         ),
                     ));
@@ -275,6 +333,138 @@ pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs, syn::Error>
     Ok(result)
 }
 
+// ============================================================================
+// CONTAINER ATTRIBUTE PARSING
+// ============================================================================
+
+/// `#[mik(...)]` attributes on a `#[derive(Type)]` item.
+#[derive(Default, Clone)]
+pub struct TypeAttrs {
+    /// Naming convention for struct fields, or enum variants
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Reject JSON objects with keys that aren't fields (structs only)
+    pub(crate) deny_unknown_fields: bool,
+    /// Fill missing fields from the type's `Default` (structs only)
+    pub(crate) default: bool,
+    /// Enum tag key: internally tagged, or adjacently tagged with `content`
+    pub(crate) tag: Option<String>,
+    /// Enum content key for adjacent tagging
+    pub(crate) content: Option<String>,
+    /// Enum variants are told apart by their shape alone
+    pub(crate) untagged: bool,
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn parse_type_attrs(attrs: &[Attribute]) -> Result<TypeAttrs, syn::Error> {
+    let mut result = TypeAttrs::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("mik") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let value: Lit = meta.value()?.parse()?;
+                let rule = match &value {
+                    Lit::Str(lit) => RenameRule::parse(&lit.value()).ok_or_else(|| {
+                        // did_you_mean ignores case, the usual mistake here (camelcase)
+                        let suggestion = RENAME_RULES
+                            .iter()
+                            .find(|rule| rule.eq_ignore_ascii_case(&lit.value()))
+                            .map_or_else(
+                                || did_you_mean(&lit.value(), RENAME_RULES),
+                                |rule| format!("\n\nDid you mean '{rule}'?"),
+                            );
+                        syn::Error::new_spanned(
+                            lit,
+                            format!(
+                                "Unknown rename_all rule '{}'.{suggestion}\n\
+                                 \n\
+                                 ✅ Valid rules: {}",
+                                lit.value(),
+                                RENAME_RULES.join(", ")
+                            ),
+                        )
+                    })?,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &value,
+                            "rename_all needs a string!\n\
+                             \n\
+                             ✅ Correct: #[mik(rename_all = \"camelCase\")]\n\
+                             ❌ Wrong:   #[mik(rename_all = camelCase)]",
+                        ));
+                    },
+                };
+                result.rename_all = Some(rule);
+            } else if meta.path.is_ident("deny_unknown_fields") {
+                result.deny_unknown_fields = true;
+            } else if meta.path.is_ident("default") {
+                result.default = true;
+            } else if meta.path.is_ident("tag") || meta.path.is_ident("content") {
+                let is_tag = meta.path.is_ident("tag");
+                let value: Lit = meta.value()?.parse()?;
+                match value {
+                    Lit::Str(lit) if is_tag => result.tag = Some(lit.value()),
+                    Lit::Str(lit) => result.content = Some(lit.value()),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &value,
+                            "tag and content need a string!\n\
+                             \n\
+                             ✅ Correct: #[mik(tag = \"kind\")]\n\
+                             ❌ Wrong:   #[mik(tag = kind)]",
+                        ));
+                    },
+                }
+            } else if meta.path.is_ident("untagged") {
+                result.untagged = true;
+            } else {
+                let path = &meta.path;
+                let attr_name = quote!(#path).to_string();
+                let suggestion = did_you_mean(&attr_name, VALID_TYPE_ATTRS);
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "Unknown mik attribute '{attr_name}'.{suggestion}\n\
+                         \n\
+                         ✅ Valid attributes:\n\
+                         #[mik(rename_all = \"camelCase\")]       // JSON naming convention\n\
+                         #[mik(deny_unknown_fields)]            // reject extra keys (structs)\n\
+                         #[mik(default)]                        // fill missing fields (structs)\n\
+                         #[mik(tag = \"kind\")]                   // internally tagged (enums)\n\
+                         #[mik(tag = \"kind\", content = \"data\")] // adjacently tagged (enums)\n\
+                         #[mik(untagged)]                       // no tag (enums)"
+                    ),
+                ));
+            }
+            Ok(())
+        })?;
+
+        if result.untagged && result.tag.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "An enum can't be both untagged and tagged.\n\
+                 \n\
+                 ✅ Use one of:\n\
+                 #[mik(tag = \"kind\")]\n\
+                 #[mik(untagged)]",
+            ));
+        }
+        if result.content.is_some() && result.tag.is_none() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "content needs a tag to go with it.\n\
+                 \n\
+                 ✅ Correct: #[mik(tag = \"kind\", content = \"data\")]",
+            ));
+        }
+    }
+
+    Ok(result)
+}
+
 // ============================================================================
 // TYPE HELPERS (delegating to centralized type_registry)
 // ============================================================================
@@ -303,6 +493,8 @@ pub fn rust_type_to_name(ty: &Type) -> &'static str {
 pub enum DeriveContext {
     Query,
     Path,
+    Headers,
+    Cookies,
 }
 
 impl DeriveContext {
@@ -310,6 +502,8 @@ impl DeriveContext {
         match self {
             Self::Query => "Query",
             Self::Path => "Path",
+            Self::Headers => "Headers",
+            Self::Cookies => "Cookies",
         }
     }
 
@@ -317,6 +511,8 @@ impl DeriveContext {
         match self {
             Self::Query => "struct MyQuery { page: u32, limit: u32 }",
             Self::Path => "struct UserPath { org_id: String, id: String }",
+            Self::Headers => "struct AuthHeaders { x_api_key: String }",
+            Self::Cookies => "struct SessionCookies { session_id: String }",
         }
     }
 
@@ -324,6 +520,8 @@ impl DeriveContext {
         match self {
             Self::Query => "for query parameters",
             Self::Path => "for URL path parameters",
+            Self::Headers => "for request headers",
+            Self::Cookies => "for request cookies",
         }
     }
 }
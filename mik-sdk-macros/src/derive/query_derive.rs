@@ -1,4 +1,7 @@
-//! #[derive(Query)] implementation for query parameter types.
+//! #[derive(Query)], #[derive(Headers)] and #[derive(Cookies)] implementations.
+//!
+//! All three parse flat `name = value` pairs into typed fields; they differ in
+//! the trait they implement, how names are matched and the OpenAPI `in` value.
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
@@ -13,15 +16,125 @@ use super::{
 use crate::openapi::utoipa::{ref_or_schema_to_json, schema_to_json};
 
 // ============================================================================
-// DERIVE QUERY
+// PARAMETER SOURCES
+// ============================================================================
+
+/// Where a derived parameter type reads its values from.
+#[derive(Clone, Copy)]
+enum ParamSource {
+    Query,
+    Header,
+    Cookie,
+}
+
+impl ParamSource {
+    const fn context(self) -> DeriveContext {
+        match self {
+            Self::Query => DeriveContext::Query,
+            Self::Header => DeriveContext::Headers,
+            Self::Cookie => DeriveContext::Cookies,
+        }
+    }
+
+    /// OpenAPI parameter location.
+    const fn openapi_in(self) -> &'static str {
+        match self {
+            Self::Query => "query",
+            Self::Header => "header",
+            Self::Cookie => "cookie",
+        }
+    }
+
+    /// Default name for a field without `#[field(rename = "...")]`.
+    ///
+    /// Header names use dashes, so `x_api_key` reads `x-api-key`.
+    fn default_key(self, field: &str) -> String {
+        match self {
+            Self::Query | Self::Cookie => field.to_string(),
+            Self::Header => field.replace('_', "-"),
+        }
+    }
+
+    /// Condition selecting the pair `(__k, __v)` for `key`.
+    fn key_matches(self, key: &str) -> TokenStream2 {
+        match self {
+            Self::Query | Self::Cookie => quote! { __k == #key },
+            // Header names are case-insensitive
+            Self::Header => quote! { __k.eq_ignore_ascii_case(#key) },
+        }
+    }
+
+    /// `impl` block header and signature of the parsing trait.
+    fn trait_impl(self, name: &syn::Ident, body: &TokenStream2) -> TokenStream2 {
+        match self {
+            Self::Query => quote! {
+                impl mik_sdk::typed::FromQuery for #name {
+                    fn from_query(__params: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
+                        #body
+                    }
+                }
+            },
+            Self::Header => quote! {
+                impl mik_sdk::typed::FromHeaders for #name {
+                    fn from_headers(__params: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
+                        #body
+                    }
+                }
+            },
+            Self::Cookie => quote! {
+                impl mik_sdk::typed::FromCookies for #name {
+                    fn from_cookies(__params: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
+                        #body
+                    }
+                }
+            },
+        }
+    }
+
+    /// `OpenApiSchema` method returning the parameters array.
+    fn openapi_params_fn(self, params_json: &str) -> TokenStream2 {
+        match self {
+            Self::Query => quote! {
+                fn openapi_query_params() -> &'static str {
+                    #params_json
+                }
+            },
+            Self::Header => quote! {
+                fn openapi_header_params() -> &'static str {
+                    #params_json
+                }
+            },
+            Self::Cookie => quote! {
+                fn openapi_cookie_params() -> &'static str {
+                    #params_json
+                }
+            },
+        }
+    }
+}
+
+// ============================================================================
+// DERIVE QUERY / HEADERS / COOKIES
 // ============================================================================
 
-#[allow(clippy::too_many_lines)] // Complex derive with many field processing branches
 pub fn derive_query_impl(input: TokenStream) -> TokenStream {
+    derive_params_impl(input, ParamSource::Query)
+}
+
+pub fn derive_headers_impl(input: TokenStream) -> TokenStream {
+    derive_params_impl(input, ParamSource::Header)
+}
+
+pub fn derive_cookies_impl(input: TokenStream) -> TokenStream {
+    derive_params_impl(input, ParamSource::Cookie)
+}
+
+#[allow(clippy::too_many_lines)] // Complex derive with many field processing branches
+fn derive_params_impl(input: TokenStream, source: ParamSource) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
-    let fields = match extract_named_fields(&input, DeriveContext::Query) {
+    let fields = match extract_named_fields(&input, source.context()) {
         Ok(fields) => fields,
         Err(err) => return err,
     };
@@ -47,7 +160,8 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
         let query_key = attrs
             .rename
             .clone()
-            .unwrap_or_else(|| field_name.to_string());
+            .unwrap_or_else(|| source.default_key(&field_name.to_string()));
+        let key_matches = source.key_matches(&query_key);
         let is_optional = is_option_type(field_ty);
 
         // Get the type name for error messages
@@ -63,7 +177,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
                 let mut #field_name: #field_ty = None;
             });
             field_matches.push(quote! {
-                #query_key => {
+                if #key_matches {
                     #field_name = Some(__v.parse().map_err(|_|
                         mik_sdk::typed::ParseError::type_mismatch(#query_key, #type_name)
                     )?);
@@ -76,7 +190,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
             schema_builder = schema_builder.property(&query_key, field_schema.clone());
 
             // OpenAPI parameter: optional
-            let param = build_query_parameter(&query_key, false, field_schema);
+            let param = build_query_parameter(&query_key, source, false, field_schema);
             query_params_array.push(param);
         } else if let Some(ref default) = attrs.default {
             // Has default value
@@ -87,7 +201,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
                 let mut #field_name: #field_ty = #default_val;
             });
             field_matches.push(quote! {
-                #query_key => {
+                if #key_matches {
                     #field_name = __v.parse().map_err(|_|
                         mik_sdk::typed::ParseError::type_mismatch(#query_key, #type_name)
                     )?;
@@ -103,7 +217,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
             schema_builder = schema_builder.property(&query_key, field_schema.clone());
 
             // OpenAPI parameter: optional (has default)
-            let param = build_query_parameter(&query_key, false, field_schema);
+            let param = build_query_parameter(&query_key, source, false, field_schema);
             query_params_array.push(param);
         } else {
             // Required without default
@@ -111,7 +225,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
                 let mut #field_name: Option<#field_ty> = None;
             });
             field_matches.push(quote! {
-                #query_key => {
+                if #key_matches {
                     #field_name = Some(__v.parse().map_err(|_|
                         mik_sdk::typed::ParseError::type_mismatch(#query_key, #type_name)
                     )?);
@@ -127,7 +241,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
             schema_builder = schema_builder.required(&query_key);
 
             // OpenAPI parameter: required
-            let param = build_query_parameter(&query_key, true, field_schema);
+            let param = build_query_parameter(&query_key, source, true, field_schema);
             query_params_array.push(param);
         }
     }
@@ -141,24 +255,29 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
     let query_params_json =
         serde_json::to_string(&query_params_array).unwrap_or_else(|_| "[]".to_string());
 
-    let tokens = quote! {
-        impl mik_sdk::typed::FromQuery for #name {
-            fn from_query(__params: &[(String, String)]) -> Result<Self, mik_sdk::typed::ParseError> {
-                #(#field_inits)*
-
-                for (__k, __v) in __params {
-                    match __k.as_str() {
-                        #(#field_matches)*
-                        _ => {}
-                    }
-                }
+    // Query strings keep the last value of a repeated key; headers and cookies
+    // keep the first, matching `Request::header_or` / `Request::cookie_or`
+    let pairs = match source {
+        ParamSource::Query => quote! { __params },
+        ParamSource::Header | ParamSource::Cookie => quote! { __params.iter().rev() },
+    };
+    let parse_body = quote! {
+        #(#field_inits)*
 
-                Ok(Self {
-                    #(#field_finals),*
-                })
-            }
+        for (__k, __v) in #pairs {
+            #(#field_matches else)* {}
         }
 
+        Ok(Self {
+            #(#field_finals),*
+        })
+    };
+    let trait_impl = source.trait_impl(name, &parse_body);
+    let params_fn = source.openapi_params_fn(&query_params_json);
+
+    let tokens = quote! {
+        #trait_impl
+
         impl mik_sdk::typed::OpenApiSchema for #name {
             fn openapi_schema() -> &'static str {
                 #schema_json
@@ -168,9 +287,7 @@ pub fn derive_query_impl(input: TokenStream) -> TokenStream {
                 #name_str
             }
 
-            fn openapi_query_params() -> &'static str {
-                #query_params_json
-            }
+            #params_fn
         }
     };
 
@@ -297,14 +414,19 @@ fn build_query_type_schema_with_default(
     RefOr::T(builder.build().into())
 }
 
-/// Build an OpenAPI query parameter object as a `serde_json::Value`.
-fn build_query_parameter(name: &str, required: bool, schema: RefOr<Schema>) -> serde_json::Value {
+/// Build an OpenAPI parameter object as a `serde_json::Value`.
+fn build_query_parameter(
+    name: &str,
+    source: ParamSource,
+    required: bool,
+    schema: RefOr<Schema>,
+) -> serde_json::Value {
     let schema_json: serde_json::Value = serde_json::from_str(&ref_or_schema_to_json(&schema))
         .unwrap_or_else(|_| serde_json::json!({}));
 
     serde_json::json!({
         "name": name,
-        "in": "query",
+        "in": source.openapi_in(),
         "required": required,
         "schema": schema_json
     })
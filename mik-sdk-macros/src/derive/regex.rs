@@ -0,0 +1,466 @@
+//! Compile-time parsing of `#[field(pattern = "...")]`.
+//!
+//! Patterns use the ECMA-262 syntax OpenAPI documents, restricted to what
+//! `mik_sdk::typed::pattern` can match without a regex engine:
+//!
+//! - literals, `.`, `^` and `$`,
+//! - classes like `[a-z_]` and `[^0-9]`, and `\d`, `\w`, `\s` (plus
+//!   `\D`, `\W`, `\S` outside classes),
+//! - groups `(...)`, `(?:...)` and `(?<name>...)` with `|`,
+//! - quantifiers `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}` (lazy forms are
+//!   accepted; they don't change whether a value matches).
+//!
+//! Backreferences, lookaround and `\b` are rejected at compile time.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+const DIGIT: &[(char, char)] = &[('0', '9')];
+const WORD: &[(char, char)] = &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')];
+const SPACE: &[(char, char)] = &[
+    ('\t', '\r'),
+    (' ', ' '),
+    ('\u{a0}', '\u{a0}'),
+    ('\u{1680}', '\u{1680}'),
+    ('\u{2000}', '\u{200a}'),
+    ('\u{2028}', '\u{2029}'),
+    ('\u{202f}', '\u{202f}'),
+    ('\u{205f}', '\u{205f}'),
+    ('\u{3000}', '\u{3000}'),
+    ('\u{feff}', '\u{feff}'),
+];
+
+/// A parsed pattern element, mirroring `mik_sdk::typed::pattern::Node`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Char(char),
+    Any,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    Start,
+    End,
+    Group(Vec<Vec<Self>>),
+    Repeat {
+        node: Box<Self>,
+        min: u32,
+        max: Option<u32>,
+    },
+}
+
+/// Parse a pattern, or explain which part is not supported.
+pub fn parse(pattern: &str) -> Result<Vec<Node>, String> {
+    let mut parser = Parser {
+        chars: pattern.chars().collect(),
+        pos: 0,
+    };
+    let mut alternatives = parser.alternatives()?;
+    if parser.pos < parser.chars.len() {
+        return Err("unmatched ')'".to_string());
+    }
+    Ok(if alternatives.len() == 1 {
+        alternatives.remove(0)
+    } else {
+        vec![Node::Group(alternatives)]
+    })
+}
+
+/// Tokens for a `&'static [mik_sdk::typed::pattern::Node]` constant.
+pub fn sequence_tokens(nodes: &[Node]) -> TokenStream2 {
+    let nodes = nodes.iter().map(Node::to_tokens);
+    quote! { &[#(#nodes),*] }
+}
+
+impl Node {
+    fn to_tokens(&self) -> TokenStream2 {
+        let path = quote! { mik_sdk::typed::pattern::Node };
+        match self {
+            Self::Char(c) => quote! { #path::Char(#c) },
+            Self::Any => quote! { #path::Any },
+            Self::Class { negated, ranges } => {
+                let ranges = ranges.iter().map(|(start, end)| quote! { (#start, #end) });
+                quote! { #path::Class { negated: #negated, ranges: &[#(#ranges),*] } }
+            },
+            Self::Start => quote! { #path::Start },
+            Self::End => quote! { #path::End },
+            Self::Group(alternatives) => {
+                let alternatives = alternatives.iter().map(|nodes| sequence_tokens(nodes));
+                quote! { #path::Group(&[#(#alternatives),*]) }
+            },
+            Self::Repeat { node, min, max } => {
+                let node = node.to_tokens();
+                let max = max.map_or_else(|| quote! { None }, |max| quote! { Some(#max) });
+                quote! { #path::Repeat { node: &#node, min: #min, max: #max } }
+            },
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let len = text.chars().count();
+        let matches = self
+            .chars
+            .get(self.pos..self.pos + len)
+            .is_some_and(|slice| slice.iter().copied().eq(text.chars()));
+        if matches {
+            self.pos += len;
+        }
+        matches
+    }
+
+    /// `sequence ('|' sequence)*`, up to an unconsumed `)` or the end.
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.eat("|") {
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            let node = match self.quantifier()? {
+                Some(_) if matches!(atom, Node::Start | Node::End) => {
+                    return Err(format!("nothing to repeat before position {}", self.pos));
+                },
+                Some((min, max)) => Node::Repeat {
+                    node: Box::new(atom),
+                    min,
+                    max,
+                },
+                None => atom,
+            };
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        let Some(c) = self.next() else {
+            return Err("unexpected end of pattern".to_string());
+        };
+        Ok(match c {
+            '^' => Node::Start,
+            '$' => Node::End,
+            '.' => Node::Any,
+            '(' => {
+                if self.peek() == Some('?') {
+                    self.group_prefix()?;
+                }
+                let alternatives = self.alternatives()?;
+                if !self.eat(")") {
+                    return Err(format!("unclosed '(' at position {start}"));
+                }
+                Node::Group(alternatives)
+            },
+            '[' => self.class(start)?,
+            '\\' => match self.escape()? {
+                Escape::Char(c) => Node::Char(c),
+                Escape::Class(ranges, negated) => Node::Class {
+                    negated,
+                    ranges: ranges.to_vec(),
+                },
+            },
+            '*' | '+' | '?' => {
+                return Err(format!(
+                    "nothing to repeat before '{c}' at position {start}"
+                ));
+            },
+            '{' => {
+                self.pos = start;
+                if self.quantifier()?.is_some() {
+                    return Err(format!("nothing to repeat before '{{' at position {start}"));
+                }
+                self.pos = start + 1;
+                Node::Char('{')
+            },
+            c => Node::Char(c),
+        })
+    }
+
+    /// `?:` or `?<name>` after a `(`.
+    fn group_prefix(&mut self) -> Result<(), String> {
+        if self.eat("?:") {
+            return Ok(());
+        }
+        if self.eat("?<") && !matches!(self.peek(), Some('=' | '!')) {
+            // Named group: the name doesn't affect matching
+            while self.next().ok_or("unterminated group name")? != '>' {}
+            return Ok(());
+        }
+        Err("lookahead and lookbehind are not supported".to_string())
+    }
+
+    /// `*`, `+`, `?` or a braced count, with an optional lazy `?`.
+    fn quantifier(&mut self) -> Result<Option<(u32, Option<u32>)>, String> {
+        let start = self.pos;
+        let bounds = match self.next() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                if let Some(bounds) = self.braced_count()? {
+                    bounds
+                } else {
+                    // Not a count, so `{` is a literal
+                    self.pos = start;
+                    return Ok(None);
+                }
+            },
+            _ => {
+                self.pos = start;
+                return Ok(None);
+            },
+        };
+        self.eat("?");
+        Ok(Some(bounds))
+    }
+
+    /// `n}`, `n,}` or `n,m}` after a `{`.
+    fn braced_count(&mut self) -> Result<Option<(u32, Option<u32>)>, String> {
+        let Some(min) = self.number()? else {
+            return Ok(None);
+        };
+        let max = if self.eat(",") {
+            self.number()?
+        } else {
+            Some(min)
+        };
+        if !self.eat("}") {
+            return Ok(None);
+        }
+        if max.is_some_and(|max| max < min) {
+            return Err(format!(
+                "quantifier {{{min},{}}} is out of order",
+                max.unwrap_or(0)
+            ));
+        }
+        Ok(Some((min, max)))
+    }
+
+    fn number(&mut self) -> Result<Option<u32>, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        digits
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("repetition count {digits} is too large"))
+    }
+
+    fn class(&mut self, start: usize) -> Result<Node, String> {
+        let negated = self.eat("^");
+        let mut ranges = Vec::new();
+        loop {
+            let item = match self.next() {
+                None => return Err(format!("unclosed '[' at position {start}")),
+                Some(']') => break,
+                Some('\\') => self.escape()?,
+                Some(c) => Escape::Char(c),
+            };
+            let low = match item {
+                Escape::Class(_, true) => {
+                    return Err("\\D, \\W and \\S are not supported inside [...]".to_string());
+                },
+                Escape::Class(class, false) => {
+                    ranges.extend_from_slice(class);
+                    continue;
+                },
+                Escape::Char(c) => c,
+            };
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.pos += 1;
+                let high = match self.next() {
+                    Some('\\') => self.escape()?,
+                    Some(c) => Escape::Char(c),
+                    None => return Err(format!("unclosed '[' at position {start}")),
+                };
+                let Escape::Char(high) = high else {
+                    return Err("a class escape can't end a range".to_string());
+                };
+                if high < low {
+                    return Err(format!("range '{low}-{high}' is out of order"));
+                }
+                ranges.push((low, high));
+            } else {
+                ranges.push((low, low));
+            }
+        }
+        if ranges.is_empty() && !negated {
+            return Err("'[]' matches nothing".to_string());
+        }
+        Ok(Node::Class { negated, ranges })
+    }
+
+    /// The escape after a `\`.
+    fn escape(&mut self) -> Result<Escape, String> {
+        let c = self
+            .next()
+            .ok_or_else(|| "pattern ends with '\\'".to_string())?;
+        Ok(match c {
+            'd' => Escape::Class(DIGIT, false),
+            'D' => Escape::Class(DIGIT, true),
+            'w' => Escape::Class(WORD, false),
+            'W' => Escape::Class(WORD, true),
+            's' => Escape::Class(SPACE, false),
+            'S' => Escape::Class(SPACE, true),
+            'n' => Escape::Char('\n'),
+            'r' => Escape::Char('\r'),
+            't' => Escape::Char('\t'),
+            'f' => Escape::Char('\u{c}'),
+            'v' => Escape::Char('\u{b}'),
+            '0' if !self.peek().is_some_and(|c| c.is_ascii_digit()) => Escape::Char('\0'),
+            'x' => Escape::Char(self.hex(2)?),
+            'u' => Escape::Char(self.hex(4)?),
+            '1'..='9' => return Err("backreferences are not supported".to_string()),
+            'b' | 'B' => return Err("word boundaries (\\b, \\B) are not supported".to_string()),
+            c if c.is_ascii_alphanumeric() => return Err(format!("unknown escape '\\{c}'")),
+            c => Escape::Char(c),
+        })
+    }
+
+    fn hex(&mut self, len: usize) -> Result<char, String> {
+        let digits: String = self.chars.iter().skip(self.pos).take(len).collect();
+        let code = (digits.len() == len && digits.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| u32::from_str_radix(&digits, 16).ok())
+            .flatten()
+            .and_then(char::from_u32)
+            .ok_or_else(|| format!("invalid hex escape '{digits}'"))?;
+        self.pos += len;
+        Ok(code)
+    }
+}
+
+enum Escape {
+    Char(char),
+    /// Character class escape, and whether it is negated.
+    Class(&'static [(char, char)], bool),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(ranges: &[(char, char)]) -> Node {
+        Node::Class {
+            negated: false,
+            ranges: ranges.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_parse_anchored_class() {
+        assert_eq!(
+            parse("^[a-z0-9-]+$").unwrap(),
+            vec![
+                Node::Start,
+                Node::Repeat {
+                    node: Box::new(class(&[('a', 'z'), ('0', '9'), ('-', '-')])),
+                    min: 1,
+                    max: None,
+                },
+                Node::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_counts() {
+        let count = |pattern: &str| match parse(pattern).unwrap().remove(0) {
+            Node::Repeat { min, max, .. } => (min, max),
+            other => panic!("expected repeat, got {other:?}"),
+        };
+        assert_eq!(count("a{3}"), (3, Some(3)));
+        assert_eq!(count("a{2,}"), (2, None));
+        assert_eq!(count("a{2,5}"), (2, Some(5)));
+        assert_eq!(count("a*?"), (0, None));
+        assert_eq!(count("a?"), (0, Some(1)));
+        // Not a count: literal braces
+        assert_eq!(
+            parse("a{x}").unwrap(),
+            vec![
+                Node::Char('a'),
+                Node::Char('{'),
+                Node::Char('x'),
+                Node::Char('}')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_alternation_and_groups() {
+        assert_eq!(
+            parse("a|bc").unwrap(),
+            vec![Node::Group(vec![
+                vec![Node::Char('a')],
+                vec![Node::Char('b'), Node::Char('c')]
+            ])]
+        );
+        assert_eq!(
+            parse("(?:x)(?<name>y)").unwrap(),
+            vec![
+                Node::Group(vec![vec![Node::Char('x')]]),
+                Node::Group(vec![vec![Node::Char('y')]]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        assert_eq!(
+            parse(r"\d\.\x41é[\w-]").unwrap(),
+            vec![
+                class(DIGIT),
+                Node::Char('.'),
+                Node::Char('A'),
+                Node::Char('é'),
+                class(&[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z'), ('-', '-')]),
+            ]
+        );
+        assert_eq!(
+            parse(r"\S").unwrap(),
+            vec![Node::Class {
+                negated: true,
+                ranges: SPACE.to_vec()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for pattern in [
+            "(abc", "abc)", "[abc", "*a", "a{3,1}", "^*", r"\1", r"\b", "(?=a)", r"[\D]", "[z-a]",
+            "[]", r"\q", r"\x4", "a\\",
+        ] {
+            assert!(parse(pattern).is_err(), "{pattern}");
+        }
+    }
+}
@@ -1,4 +1,5 @@
-//! PascalCase to snake_case conversion utility.
+//! Case conversions for JSON names: PascalCase to snake_case, and the
+//! `#[mik(rename_all = "...")]` rules.
 
 /// Convert PascalCase to snake_case.
 ///
@@ -38,9 +39,94 @@ pub fn pascal_to_snake_case(s: &str) -> String {
     result
 }
 
+/// Valid `rename_all` values, as spelled by serde.
+pub const RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// A `#[mik(rename_all = "...")]` naming convention.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Parse one of [`RENAME_RULES`].
+    pub fn parse(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Rename a snake_case struct field.
+    ///
+    /// Examples with `user_id`: `camelCase` → `userId`, `PascalCase` → `UserId`,
+    /// `kebab-case` → `user-id`, `UPPERCASE` → `USER_ID`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field.split('_').map(capitalize).collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            },
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Rename a PascalCase enum variant.
+    ///
+    /// Examples with `SuperAdmin`: `camelCase` → `superAdmin`,
+    /// `lowercase` → `superadmin`, `SCREAMING_SNAKE_CASE` → `SUPER_ADMIN`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        let snake = pascal_to_snake_case(variant);
+        match self {
+            Self::Lower => snake.replace('_', ""),
+            Self::Upper => snake.replace('_', "").to_ascii_uppercase(),
+            Self::Pascal => variant.to_string(),
+            rule => rule.apply_to_field(&snake),
+        }
+    }
+}
+
+/// Uppercase the first character of `word`.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_ascii_uppercase().to_string() + chars.as_str()
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::pascal_to_snake_case;
+    use super::{RENAME_RULES, RenameRule, pascal_to_snake_case};
 
     #[test]
     fn test_pascal_to_snake_case_simple() {
@@ -80,4 +166,55 @@ mod tests {
         assert_eq!(pascal_to_snake_case("Status2"), "status2");
         assert_eq!(pascal_to_snake_case("OAuth2Token"), "o_auth2_token");
     }
+
+    #[test]
+    fn test_rename_rule_parse() {
+        for rule in RENAME_RULES {
+            assert!(RenameRule::parse(rule).is_some(), "{rule}");
+        }
+        assert_eq!(RenameRule::parse("camelcase"), None);
+    }
+
+    #[test]
+    fn test_rename_rule_fields() {
+        let cases = [
+            (RenameRule::Lower, "user_id"),
+            (RenameRule::Upper, "USER_ID"),
+            (RenameRule::Pascal, "UserId"),
+            (RenameRule::Camel, "userId"),
+            (RenameRule::Snake, "user_id"),
+            (RenameRule::ScreamingSnake, "USER_ID"),
+            (RenameRule::Kebab, "user-id"),
+            (RenameRule::ScreamingKebab, "USER-ID"),
+        ];
+        for (rule, expected) in cases {
+            assert_eq!(rule.apply_to_field("user_id"), expected, "{rule:?}");
+        }
+        assert_eq!(RenameRule::Camel.apply_to_field("id"), "id");
+        assert_eq!(
+            RenameRule::Camel.apply_to_field("created_at_ms"),
+            "createdAtMs"
+        );
+    }
+
+    #[test]
+    fn test_rename_rule_variants() {
+        let cases = [
+            (RenameRule::Lower, "superadmin"),
+            (RenameRule::Upper, "SUPERADMIN"),
+            (RenameRule::Pascal, "SuperAdmin"),
+            (RenameRule::Camel, "superAdmin"),
+            (RenameRule::Snake, "super_admin"),
+            (RenameRule::ScreamingSnake, "SUPER_ADMIN"),
+            (RenameRule::Kebab, "super-admin"),
+            (RenameRule::ScreamingKebab, "SUPER-ADMIN"),
+        ];
+        for (rule, expected) in cases {
+            assert_eq!(rule.apply_to_variant("SuperAdmin"), expected, "{rule:?}");
+        }
+        assert_eq!(
+            RenameRule::Camel.apply_to_variant("HTTPRequest"),
+            "httpRequest"
+        );
+    }
 }
@@ -0,0 +1,600 @@
+//! Data-carrying and tagged enum implementation for #[derive(Type)].
+//!
+//! Variants may be unit, newtype (`Card(CardDetails)`) or struct
+//! (`Card { last4: String }`). How variants are told apart in JSON follows
+//! serde's representations:
+//!
+//! | Attribute                                | `Card { last4 }` as JSON                      |
+//! |------------------------------------------|-----------------------------------------------|
+//! | (none)                                   | `{"card": {"last4": "4242"}}`                 |
+//! | `#[mik(tag = "kind")]`                   | `{"kind": "card", "last4": "4242"}`           |
+//! | `#[mik(tag = "kind", content = "data")]` | `{"kind": "card", "data": {"last4": "4242"}}` |
+//! | `#[mik(untagged)]`                       | `{"last4": "4242"}`                           |
+//!
+//! Every variant becomes its own OpenAPI component (`PaymentCard`), and the
+//! enum schema is a `oneOf` over them, with a `discriminator` when tagged.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Fields, Ident};
+
+use super::fields::{
+    FieldsCode, extract_custom_type_ident, generate_fields, is_custom_type, nested_schemas_impl,
+};
+use super::validation::generate_nested_validation;
+use crate::derive::TypeAttrs;
+use crate::openapi::utoipa::{JsonFieldDef, enum_schema, object_schema_json, schema_to_json};
+use crate::type_registry::get_openapi_schema;
+
+/// How variants are told apart in JSON.
+#[derive(Clone, Copy)]
+pub enum Tagging<'a> {
+    /// `{"variant": content}`, unit variants as `"variant"`
+    External,
+    /// `{"tag": "variant", ...fields}`
+    Internal { tag: &'a str },
+    /// `{"tag": "variant", "content": content}`
+    Adjacent { tag: &'a str, content: &'a str },
+    /// `content`, unit variants as `null`
+    Untagged,
+}
+
+impl Tagging<'_> {
+    /// Prefix for validation error paths inside a variant's content.
+    const fn content_path<'v>(&'v self, json_name: &'v str) -> Option<&'v str> {
+        match self {
+            Self::External => Some(json_name),
+            Self::Adjacent { content, .. } => Some(content),
+            Self::Internal { .. } | Self::Untagged => None,
+        }
+    }
+}
+
+/// What a variant holds.
+enum VariantData<'a> {
+    Unit,
+    Newtype(&'a syn::Type),
+    Struct(Box<FieldsCode>),
+}
+
+struct Variant<'a> {
+    ident: &'a Ident,
+    json_name: String,
+    data: VariantData<'a>,
+    /// Field names of a struct variant
+    fields: Vec<Ident>,
+}
+
+impl Variant<'_> {
+    /// Match pattern binding the variant's fields by reference.
+    fn pattern(&self) -> TokenStream2 {
+        let ident = self.ident;
+        let fields = &self.fields;
+        match &self.data {
+            VariantData::Unit => quote! { Self::#ident },
+            VariantData::Newtype(_) => quote! { Self::#ident(__field_0) },
+            VariantData::Struct(_) => {
+                let bindings = fields
+                    .iter()
+                    .map(|field| format_ident!("__field_{}", field));
+                quote! { Self::#ident { #(#fields: #bindings),* } }
+            },
+        }
+    }
+}
+
+/// Generate FromJson, ToJson, Validate, and OpenApiSchema implementations for
+/// enums with data or a tagging attribute.
+#[allow(clippy::too_many_lines)]
+pub fn derive_data_enum_impl(
+    input: &DeriveInput,
+    data_enum: &syn::DataEnum,
+    variant_names: Vec<String>,
+    tagging: Tagging<'_>,
+) -> TokenStream {
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    let mut variants = Vec::new();
+    for (variant, json_name) in data_enum.variants.iter().zip(variant_names) {
+        let data = match &variant.fields {
+            Fields::Unit => VariantData::Unit,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                if matches!(tagging, Tagging::Internal { .. }) && !is_custom_type(ty) {
+                    return syn::Error::new_spanned(
+                        ty,
+                        format!(
+                            "Internally tagged variants can only wrap a struct type.\n\
+                             \n\
+                             The tag is added next to the fields of the inner object, so\n\
+                             `{ty}` has nowhere to put it.\n\
+                             \n\
+                             \u{2705} Use a struct variant or another tagging:\n\
+                               {variant} {{ value: {ty} }}\n\
+                               #[mik(tag = \"kind\", content = \"data\")]",
+                            ty = quote!(#ty),
+                            variant = variant.ident,
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                VariantData::Newtype(ty)
+            },
+            Fields::Unnamed(_) => {
+                return syn::Error::new_spanned(
+                    variant,
+                    format!(
+                        "Tuple variants must hold exactly one value.\n\
+                         \n\
+                         \u{274C} What you have:\n\
+                           {ident}(..)\n\
+                         \n\
+                         \u{2705} What you need:\n\
+                           {ident}(MyStruct)\n\
+                           {ident} {{ first: String, second: i32 }}",
+                        ident = variant.ident
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            },
+            Fields::Named(fields) => {
+                let path = tagging.content_path(&json_name);
+                let code = match generate_fields(
+                    &fields.named,
+                    &|field| {
+                        let binding = format_ident!("__field_{}", field);
+                        quote! { (*#binding) }
+                    },
+                    path,
+                    &TypeAttrs::default(),
+                ) {
+                    Ok(code) => code,
+                    Err(e) => return e.to_compile_error().into(),
+                };
+                if let Tagging::Internal { tag } = tagging
+                    && code.schema.iter().any(|field| field.name == tag)
+                {
+                    return syn::Error::new_spanned(
+                        variant,
+                        format!(
+                            "Field `{tag}` clashes with the enum tag.\n\
+                             \n\
+                             \u{2705} Rename the field or the tag:\n\
+                               #[field(rename = \"...\")]\n\
+                               #[mik(tag = \"...\")]"
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                VariantData::Struct(Box::new(code))
+            },
+        };
+        variants.push(Variant {
+            ident: &variant.ident,
+            json_name,
+            data,
+            fields: variant
+                .fields
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect(),
+        });
+    }
+
+    let valid_values_str = variants
+        .iter()
+        .map(|v| format!("\"{}\"", v.json_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let unknown_variant = |field: &str| {
+        quote! {
+            Err(mik_sdk::typed::ParseError::custom(
+                #field,
+                format!(
+                    "unknown enum variant \"{}\". Valid values: {}",
+                    __other,
+                    #valid_values_str
+                )
+            ))
+        }
+    };
+
+    // ========================================================================
+    // FromJson
+    // ========================================================================
+
+    // Expression parsing one variant from `content` (a `&JsonValue`) into `Result<Self, _>`
+    let parse_variant = |variant: &Variant<'_>, content: &TokenStream2| {
+        let ident = variant.ident;
+        match &variant.data {
+            VariantData::Unit => quote! { Ok(Self::#ident) },
+            VariantData::Newtype(ty) => quote! {
+                <#ty as mik_sdk::typed::FromJson>::from_json(#content).map(Self::#ident)
+            },
+            VariantData::Struct(code) => {
+                let fields = &code.from_json;
+                quote! {
+                    {
+                        let __value: &mik_sdk::json::JsonValue = #content;
+                        Ok(Self::#ident {
+                            #(#fields),*
+                        })
+                    }
+                }
+            },
+        }
+    };
+    // Read the tag of an internally or adjacently tagged enum into `__tag`
+    let read_tag = |tag: &str| {
+        quote! {
+            let __tag = __value.get(#tag);
+            if __tag.is_null() {
+                return Err(mik_sdk::typed::ParseError::missing(#tag));
+            }
+            let __tag = __tag.str().ok_or_else(|| {
+                mik_sdk::typed::ParseError::type_mismatch(#tag, "string")
+            })?;
+        }
+    };
+
+    let from_json_body = match tagging {
+        Tagging::External => {
+            let unit_arms = variants
+                .iter()
+                .filter(|v| matches!(v.data, VariantData::Unit))
+                .map(|v| {
+                    let json_name = &v.json_name;
+                    let ident = v.ident;
+                    quote! { #json_name => Ok(Self::#ident), }
+                });
+            let data_arms = variants
+                .iter()
+                .filter(|v| !matches!(v.data, VariantData::Unit))
+                .map(|v| {
+                    let json_name = &v.json_name;
+                    let parse = parse_variant(v, &quote! { &__content });
+                    quote! { #json_name => #parse, }
+                });
+            let unknown = unknown_variant("value");
+            quote! {
+                if let Some(__s) = __value.str() {
+                    return match __s.as_str() {
+                        #(#unit_arms)*
+                        __other => #unknown,
+                    };
+                }
+                let __keys = __value.keys();
+                let [__key] = __keys.as_slice() else {
+                    return Err(mik_sdk::typed::ParseError::type_mismatch(
+                        "value",
+                        "object with a single variant key",
+                    ));
+                };
+                let __content = __value.get(__key);
+                match __key.as_str() {
+                    #(#data_arms)*
+                    __other => #unknown,
+                }
+            }
+        },
+        Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => {
+            let content = if let Tagging::Adjacent { content, .. } = tagging {
+                quote! { &__value.get(#content) }
+            } else {
+                quote! { __value }
+            };
+            let arms = variants.iter().map(|v| {
+                let json_name = &v.json_name;
+                let parse = parse_variant(v, &content);
+                quote! { #json_name => #parse, }
+            });
+            let read_tag = read_tag(tag);
+            let unknown = unknown_variant(tag);
+            quote! {
+                #read_tag
+                match __tag.as_str() {
+                    #(#arms)*
+                    __other => #unknown,
+                }
+            }
+        },
+        Tagging::Untagged => {
+            // First variant that parses wins
+            let attempts = variants.iter().map(|v| {
+                if matches!(v.data, VariantData::Unit) {
+                    let ident = v.ident;
+                    return quote! {
+                        if __value.is_null() {
+                            return Ok(Self::#ident);
+                        }
+                    };
+                }
+                let parse = parse_variant(v, &quote! { __value });
+                quote! {
+                    let __attempt = || -> Result<Self, mik_sdk::typed::ParseError> { #parse };
+                    if let Ok(__parsed) = __attempt() {
+                        return Ok(__parsed);
+                    }
+                }
+            });
+            let message = format!("value does not match any variant of {name_str}");
+            quote! {
+                #(#attempts)*
+                Err(mik_sdk::typed::ParseError::custom("value", #message))
+            }
+        },
+    };
+
+    // ========================================================================
+    // FromForm
+    // ========================================================================
+
+    // Only internally tagged enums are flat objects, so only they map onto form
+    // fields (and can be flattened into a struct that is read from a form)
+    let from_form_impl = if let Tagging::Internal { tag } = tagging {
+        let arms = variants.iter().map(|v| {
+            let json_name = &v.json_name;
+            let ident = v.ident;
+            let parse = match &v.data {
+                VariantData::Unit => quote! { Ok(Self::#ident) },
+                VariantData::Newtype(ty) => quote! {
+                    <#ty as mik_sdk::typed::FromForm>::from_form(__form).map(Self::#ident)
+                },
+                VariantData::Struct(code) => {
+                    let fields = &code.from_form;
+                    quote! { Ok(Self::#ident { #(#fields),* }) }
+                },
+            };
+            quote! { #json_name => #parse, }
+        });
+        let unknown = unknown_variant(tag);
+        quote! {
+            impl mik_sdk::typed::FromForm for #name {
+                fn from_form(__form: &mik_sdk::typed::FormData) -> Result<Self, mik_sdk::typed::ParseError> {
+                    let __tag = __form
+                        .value(#tag)
+                        .ok_or_else(|| mik_sdk::typed::ParseError::missing(#tag))?;
+                    match __tag {
+                        #(#arms)*
+                        __other => #unknown,
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // ========================================================================
+    // ToJson
+    // ========================================================================
+
+    let to_json_arms = variants.iter().map(|v| {
+        let pattern = v.pattern();
+        let json_name = &v.json_name;
+        let content = match &v.data {
+            VariantData::Unit => None,
+            VariantData::Newtype(_) => Some(quote! { mik_sdk::json::ToJson::to_json(__field_0) }),
+            VariantData::Struct(code) => {
+                Some(code.to_json_object(&quote! { mik_sdk::json::obj() }))
+            },
+        };
+        let tag_obj =
+            |tag: &str| quote! { mik_sdk::json::obj().set(#tag, mik_sdk::json::str(#json_name)) };
+        let json = match (tagging, &v.data, content) {
+            (Tagging::External, _, None) => quote! { mik_sdk::json::str(#json_name) },
+            (Tagging::Untagged, _, None) => quote! { mik_sdk::json::null() },
+            (Tagging::External, _, Some(content)) => {
+                quote! { mik_sdk::json::obj().set(#json_name, #content) }
+            },
+            (Tagging::Untagged, _, Some(content)) => content,
+            (Tagging::Internal { tag } | Tagging::Adjacent { tag, .. }, _, None) => tag_obj(tag),
+            (Tagging::Internal { tag }, VariantData::Struct(code), Some(_)) => {
+                code.to_json_object(&tag_obj(tag))
+            },
+            (Tagging::Internal { tag }, _, Some(content)) => {
+                quote! { #content.set(#tag, mik_sdk::json::str(#json_name)) }
+            },
+            (Tagging::Adjacent { tag, content: key }, _, Some(content)) => {
+                let tag_obj = tag_obj(tag);
+                quote! { #tag_obj.set(#key, #content) }
+            },
+        };
+        quote! { #pattern => #json, }
+    });
+
+    // ========================================================================
+    // Validate
+    // ========================================================================
+
+    let mut validate_arms = Vec::new();
+    let mut collect_arms = Vec::new();
+    for v in &variants {
+        let pattern = v.pattern();
+        let path = tagging.content_path(&v.json_name);
+        let (checks, collect) = match &v.data {
+            VariantData::Unit => continue,
+            VariantData::Struct(code) => {
+                (code.validation_checks.clone(), code.collect_checks.clone())
+            },
+            VariantData::Newtype(ty) => {
+                // The variant is its inner value, so `validate` checks it too
+                let checks = if is_custom_type(ty) {
+                    let with_path = path.map(|path| quote! { .map_err(|e| e.with_path(#path)) });
+                    vec![quote! {
+                        mik_sdk::typed::Validate::validate(__field_0) #with_path ?;
+                    }]
+                } else {
+                    Vec::new()
+                };
+                let collect =
+                    generate_nested_validation(ty, &quote! { (*__field_0) }, path.unwrap_or(""));
+                (checks, collect.into_iter().collect())
+            },
+        };
+        if !checks.is_empty() {
+            validate_arms.push(quote! { #pattern => { #(#checks)* } });
+        }
+        if !collect.is_empty() {
+            collect_arms.push(quote! { #pattern => { #(#collect)* } });
+        }
+    }
+    let match_arms = |arms: &[TokenStream2]| {
+        if arms.is_empty() {
+            return quote! {};
+        }
+        let rest = (arms.len() < variants.len()).then(|| quote! { _ => {} });
+        quote! {
+            match self {
+                #(#arms)*
+                #rest
+            }
+        }
+    };
+    let validate_match = match_arms(&validate_arms);
+    let validate_all = if collect_arms.is_empty() {
+        quote! {}
+    } else {
+        let collect_match = match_arms(&collect_arms);
+        quote! {
+            fn validate_all(&self) -> Result<(), mik_sdk::typed::ValidationErrors> {
+                let mut __errors = mik_sdk::typed::ValidationErrors::new();
+                #collect_match
+                __errors.into_result()
+            }
+        }
+    };
+
+    // ========================================================================
+    // OpenApiSchema
+    // ========================================================================
+
+    let mut nested_types: Vec<Ident> = Vec::new();
+    let mut components = Vec::new();
+    for v in &variants {
+        let tag_field = |tag: &str| JsonFieldDef {
+            name: tag.to_string(),
+            schema_json: schema_to_json(&enum_schema(&[v.json_name.as_str()])),
+            required: true,
+            x_attrs: Vec::new(),
+            deprecated: false,
+        };
+        let content_field = |name: &str, schema_json: String| JsonFieldDef {
+            name: name.to_string(),
+            schema_json,
+            required: true,
+            x_attrs: Vec::new(),
+            deprecated: false,
+        };
+        let content = match &v.data {
+            VariantData::Unit => None,
+            VariantData::Newtype(ty) => {
+                nested_types.extend(extract_custom_type_ident(ty));
+                Some(get_openapi_schema(ty))
+            },
+            VariantData::Struct(code) => {
+                nested_types.extend(code.nested_types.iter().cloned());
+                Some(code.object_schema(Vec::new()))
+            },
+        };
+        let schema = match (tagging, &v.data, content) {
+            (Tagging::External, _, None) => schema_to_json(&enum_schema(&[v.json_name.as_str()])),
+            (Tagging::Untagged, _, None) => r#"{"nullable":true,"enum":[null]}"#.to_string(),
+            (Tagging::External, _, Some(content)) => {
+                object_schema_json(vec![content_field(&v.json_name, content)])
+            },
+            (Tagging::Untagged, _, Some(content)) => content,
+            (Tagging::Internal { tag } | Tagging::Adjacent { tag, .. }, _, None) => {
+                object_schema_json(vec![tag_field(tag)])
+            },
+            (Tagging::Internal { tag }, VariantData::Struct(code), Some(_)) => {
+                code.object_schema(vec![tag_field(tag)])
+            },
+            (Tagging::Internal { tag }, _, Some(content)) => format!(
+                r#"{{"allOf":[{content},{}]}}"#,
+                object_schema_json(vec![tag_field(tag)])
+            ),
+            (Tagging::Adjacent { tag, content: key }, _, Some(content)) => {
+                object_schema_json(vec![tag_field(tag), content_field(key, content)])
+            },
+        };
+        components.push((format!("{name_str}{}", v.ident), schema));
+    }
+    let mut unique_types: Vec<Ident> = Vec::new();
+    for ty in nested_types {
+        if !unique_types.contains(&ty) {
+            unique_types.push(ty);
+        }
+    }
+
+    let refs: Vec<String> = components
+        .iter()
+        .map(|(component, _)| format!(r##"{{"$ref":"#/components/schemas/{component}"}}"##))
+        .collect();
+    let discriminator = match tagging {
+        Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => {
+            let mapping: Vec<String> = variants
+                .iter()
+                .zip(&components)
+                .map(|(v, (component, _))| {
+                    format!(r##""{}":"#/components/schemas/{component}""##, v.json_name)
+                })
+                .collect();
+            format!(
+                r#","discriminator":{{"propertyName":"{tag}","mapping":{{{}}}}}"#,
+                mapping.join(",")
+            )
+        },
+        Tagging::External | Tagging::Untagged => String::new(),
+    };
+    let openapi_schema = format!(r#"{{"oneOf":[{}]{discriminator}}}"#, refs.join(","));
+    let nested_schemas_impl = nested_schemas_impl(&unique_types, &components);
+
+    let tokens = quote! {
+        impl mik_sdk::typed::FromJson for #name {
+            fn from_json(__value: &mik_sdk::json::JsonValue) -> Result<Self, mik_sdk::typed::ParseError> {
+                #from_json_body
+            }
+        }
+
+        #from_form_impl
+
+        impl mik_sdk::json::ToJson for #name {
+            fn to_json(&self) -> mik_sdk::json::JsonValue {
+                match self {
+                    #(#to_json_arms)*
+                }
+            }
+        }
+
+        impl mik_sdk::typed::Validate for #name {
+            fn validate(&self) -> Result<(), mik_sdk::typed::ValidationError> {
+                #validate_match
+                Ok(())
+            }
+
+            #validate_all
+        }
+
+        impl mik_sdk::typed::OpenApiSchema for #name {
+            fn openapi_schema() -> &'static str {
+                #openapi_schema
+            }
+
+            fn schema_name() -> &'static str {
+                #name_str
+            }
+
+            fn nested_schemas() -> &'static str {
+                #nested_schemas_impl
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
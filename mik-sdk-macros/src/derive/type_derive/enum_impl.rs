@@ -5,56 +5,75 @@ use quote::quote;
 use syn::{DeriveInput, Fields};
 
 use super::case::pascal_to_snake_case;
-use crate::derive::parse_field_attrs;
+use super::data_enum::{Tagging, derive_data_enum_impl};
+use crate::derive::{parse_field_attrs, parse_type_attrs};
 use crate::openapi::utoipa::{enum_schema, schema_to_json};
 
 /// Generate FromJson, ToJson, Validate, and OpenApiSchema implementations for enums.
+///
+/// Plain unit enums are JSON strings; enums with data or a tagging attribute
+/// are handed to [`derive_data_enum_impl`].
 #[allow(clippy::too_many_lines)]
 pub fn derive_enum_type_impl(input: &DeriveInput, data_enum: &syn::DataEnum) -> TokenStream {
     let name = &input.ident;
     let name_str = name.to_string();
 
+    let type_attrs = match parse_type_attrs(&input.attrs) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    if type_attrs.deny_unknown_fields || type_attrs.default {
+        return syn::Error::new_spanned(
+            input,
+            "deny_unknown_fields and default only apply to structs.\n\
+             \n\
+             \u{2705} Use them on a struct:\n\
+               #[derive(Type)]\n\
+               #[mik(deny_unknown_fields, default)]\n\
+               struct Settings { theme: String }",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let tagging = match (&type_attrs.tag, &type_attrs.content) {
+        (Some(tag), Some(content)) => Tagging::Adjacent { tag, content },
+        (Some(tag), None) => Tagging::Internal { tag },
+        _ if type_attrs.untagged => Tagging::Untagged,
+        _ => Tagging::External,
+    };
+
     // Collect variant info: (variant_ident, json_name)
     let mut variants_info: Vec<(&syn::Ident, String)> = Vec::new();
 
     for variant in &data_enum.variants {
-        // Only support unit variants (no fields)
-        if !matches!(variant.fields, Fields::Unit) {
-            return syn::Error::new_spanned(
-                variant,
-                format!(
-                    "Enum variants must be unit variants (no fields).\n\
-                     \n\
-                     {} What you have:\n\
-                       {}(..)\n\
-                     \n\
-                     {} What you need:\n\
-                       {}\n\
-                     \n\
-                     For complex enums with data, consider using separate types.",
-                    '\u{274C}', // X mark
-                    variant.ident,
-                    '\u{2705}', // checkmark
-                    variant.ident
-                ),
-            )
-            .to_compile_error()
-            .into();
-        }
-
         // Check for #[field(rename = "...")] attribute
         let attrs = match parse_field_attrs(&variant.attrs) {
             Ok(attrs) => attrs,
             Err(e) => return e.to_compile_error().into(),
         };
 
-        let json_name = attrs
-            .rename
-            .unwrap_or_else(|| pascal_to_snake_case(&variant.ident.to_string()));
+        let variant_name = variant.ident.to_string();
+        let json_name = attrs.rename.unwrap_or_else(|| {
+            type_attrs.rename_all.map_or_else(
+                || pascal_to_snake_case(&variant_name),
+                |rule| rule.apply_to_variant(&variant_name),
+            )
+        });
 
         variants_info.push((&variant.ident, json_name));
     }
 
+    // Variants with data, or a tagging attribute, need the full representation
+    let is_plain = matches!(tagging, Tagging::External)
+        && data_enum
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.fields, Fields::Unit));
+    if !is_plain {
+        let names = variants_info.into_iter().map(|(_, name)| name).collect();
+        return derive_data_enum_impl(input, data_enum, names, tagging);
+    }
+
     // Generate FromJson match arms
     let from_json_arms: Vec<_> = variants_info
         .iter()
@@ -0,0 +1,577 @@
+//! Named-field code generation shared by structs and struct enum variants.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Field, Ident};
+
+use super::form::generate_form_field;
+use super::validation::{generate_nested_validation, generate_validation_checks};
+use crate::derive::{
+    TypeAttrs, get_inner_type, is_option_type, parse_field_attrs, rust_type_to_json_getter,
+    rust_type_to_name,
+};
+use crate::openapi::utoipa::{
+    FieldConstraints, JsonFieldDef, apply_constraints, flattened_schema_json, object_schema_json,
+    schema_to_json,
+};
+use crate::type_registry::{
+    get_inner_type as registry_get_inner_type, get_openapi_schema, lookup_type,
+};
+
+/// Generated pieces for a set of named fields.
+#[derive(Default)]
+pub struct FieldsCode {
+    /// `name: <expr>` initializers reading from `__value: &JsonValue`.
+    pub from_json: Vec<TokenStream2>,
+    /// `name: <expr>` initializers reading from `__form: &FormData`.
+    pub from_form: Vec<TokenStream2>,
+    /// Statements adding the fields to a `__json` object.
+    pub to_json: Vec<TokenStream2>,
+    /// OpenAPI properties.
+    pub schema: Vec<JsonFieldDef>,
+    /// Schemas of `#[field(flatten)]` types, merged into the object schema.
+    pub flattened: Vec<String>,
+    /// JSON keys of the fields, for `deny_unknown_fields`.
+    pub json_keys: Vec<String>,
+    /// Checks returning the first `ValidationError` (for `validate`).
+    pub validation_checks: Vec<TokenStream2>,
+    /// Checks pushing onto `__errors` (for `validate_all`).
+    pub collect_checks: Vec<TokenStream2>,
+    /// Custom types referenced by the fields, for `nested_schemas`.
+    pub nested_types: Vec<Ident>,
+}
+
+impl FieldsCode {
+    /// Expression building the JSON object: `base` plus every field.
+    pub fn to_json_object(&self, base: &TokenStream2) -> TokenStream2 {
+        let steps = &self.to_json;
+        if steps.is_empty() {
+            return base.clone();
+        }
+        quote! {
+            {
+                let mut __json = #base;
+                #(#steps)*
+                __json
+            }
+        }
+    }
+
+    /// Object schema with the `leading` properties, then the fields, merged
+    /// with any flattened types.
+    pub fn object_schema(&self, leading: Vec<JsonFieldDef>) -> String {
+        let mut fields = leading;
+        fields.extend(self.schema.iter().cloned());
+        flattened_schema_json(object_schema_json(fields), &self.flattened)
+    }
+}
+
+/// Generate parsing, serialization, validation and schema code for `fields`.
+///
+/// `access` turns a field name into a place expression for its value
+/// (`self.name` for structs, `(*__name)` in an enum match arm). Validation
+/// errors are reported under `path.field` when `path` is set. `container`
+/// supplies `rename_all`, and with `default` missing fields are read from a
+/// `__default: Self` binding.
+#[allow(clippy::too_many_lines)]
+pub fn generate_fields(
+    fields: &Punctuated<Field, Comma>,
+    access: &dyn Fn(&Ident) -> TokenStream2,
+    path: Option<&str>,
+    container: &TypeAttrs,
+) -> syn::Result<FieldsCode> {
+    let mut code = FieldsCode::default();
+
+    for field in fields {
+        let Some(field_name) = field.ident.as_ref() else {
+            continue;
+        };
+        let field_ty = &field.ty;
+        let attrs = parse_field_attrs(&field.attrs)?;
+        let value = access(field_name);
+        let field_path = path.map_or_else(
+            || field_name.to_string(),
+            |path| format!("{path}.{field_name}"),
+        );
+
+        let json_key = attrs.rename.clone().unwrap_or_else(|| {
+            let name = field_name.to_string();
+            container
+                .rename_all
+                .map_or_else(|| name.clone(), |rule| rule.apply_to_field(&name))
+        });
+        let is_optional = is_option_type(field_ty);
+
+        // Skipped fields never touch JSON or the schema
+        if attrs.skip {
+            let fill = if container.default {
+                quote! { __default.#field_name }
+            } else {
+                quote! { ::std::default::Default::default() }
+            };
+            code.from_json.push(quote! { #field_name: #fill });
+            code.from_form.push(quote! { #field_name: #fill });
+            continue;
+        }
+        if attrs.flatten {
+            flatten_field(&mut code, field, &value, path, container)?;
+            continue;
+        }
+        code.json_keys.push(json_key.clone());
+
+        // Generate to_json field serialization
+        // ToJson trait handles Option/Vec/nested types automatically
+        let set = quote! {
+            __json = __json.set(#json_key, mik_sdk::json::ToJson::to_json(&#value));
+        };
+        code.to_json.push(match &attrs.skip_if {
+            Some(skip_if) => quote! {
+                if !#skip_if(&#value) {
+                    #set
+                }
+            },
+            None => set,
+        });
+
+        // Generate from_form field extraction (same keys as JSON)
+        let form_field = generate_form_field(field_ty, &json_key);
+        if container.default {
+            code.from_form.push(quote! {
+                #field_name: if __form.values(#json_key).is_empty() && __form.files(#json_key).is_empty() {
+                    __default.#field_name
+                } else {
+                    #form_field
+                }
+            });
+        } else {
+            code.from_form.push(quote! { #field_name: #form_field });
+        }
+
+        // Generate from_json field extraction
+        let parse = if is_optional {
+            let inner_ty = get_inner_type(field_ty);
+            let inner_getter = inner_ty.and_then(rust_type_to_json_getter);
+
+            if let Some(getter) = inner_getter {
+                // Simple type inside Option - use getter method
+                let type_name = inner_ty.map_or("value", rust_type_to_name);
+                quote! {
+                    {
+                        let v = __value.get(#json_key);
+                        if v.is_null() {
+                            None
+                        } else {
+                            Some(v #getter .ok_or_else(|| mik_sdk::typed::ParseError::type_mismatch(#json_key, #type_name))?)
+                        }
+                    }
+                }
+            } else if let Some(inner) = inner_ty {
+                // Complex type inside Option - use FromJson trait
+                quote! {
+                    {
+                        let v = __value.get(#json_key);
+                        if v.is_null() {
+                            None
+                        } else {
+                            Some(<#inner as mik_sdk::typed::FromJson>::from_json(&v)?)
+                        }
+                    }
+                }
+            } else {
+                // Could not extract inner type from Option - emit compile error
+                let field_name_str = field_name.to_string();
+                return Err(syn::Error::new_spanned(
+                    field_ty,
+                    format!(
+                        "Can't figure out what type is inside Option for '{field_name_str}'.\n\
+                         \n\
+                         \u{2705} Use a concrete type:\n\
+                         pub {field_name_str}: Option<String>,     // text\n\
+                         pub {field_name_str}: Option<i32>,        // number\n\
+                         pub {field_name_str}: Option<bool>,       // true/false\n\
+                         pub {field_name_str}: Option<MyStruct>,   // your own type\n\
+                         \n\
+                         \u{274C} These won't work:\n\
+                         pub {field_name_str}: Option<impl Trait>, // too abstract\n\
+                         pub {field_name_str}: Option<_>,          // can't infer type"
+                    ),
+                ));
+            }
+        } else {
+            rust_type_to_json_getter(field_ty).map_or_else(
+                // Complex type (Vec, custom struct, etc.) - use FromJson trait
+                || {
+                    quote! {
+                        <#field_ty as mik_sdk::typed::FromJson>::from_json(&__value.get(#json_key))?
+                    }
+                },
+                // Simple type - use getter method
+                |getter| {
+                    quote! {
+                        __value.get(#json_key) #getter
+                            .ok_or_else(|| mik_sdk::typed::ParseError::missing(#json_key))?
+                    }
+                },
+            )
+        };
+        // Absent keys take the value from `Default` (an explicit null is still parsed)
+        if container.default {
+            code.from_json.push(quote! {
+                #field_name: if __value.path_exists(&[#json_key]) {
+                    #parse
+                } else {
+                    __default.#field_name
+                }
+            });
+        } else {
+            code.from_json.push(quote! { #field_name: #parse });
+        }
+
+        // Generate OpenAPI property using utoipa
+        // First, get the base schema JSON (used for validation type detection)
+        let base_schema_json = get_openapi_schema(field_ty);
+
+        // Track nested custom types for OpenAPI schema collection
+        if let Some(custom_ident) = extract_custom_type_ident(field_ty)
+            && !code.nested_types.iter().any(|t| t == &custom_ident)
+        {
+            code.nested_types.push(custom_ident);
+        }
+
+        // Build constraints from field attributes
+        let constraints = FieldConstraints {
+            min: attrs.min,
+            max: attrs.max,
+            format: attrs.format.clone(),
+            pattern: attrs.pattern.clone(),
+            description: attrs.docs.clone(),
+            x_attrs: attrs.x_attrs.clone(),
+            deprecated: attrs.deprecated,
+        };
+
+        // Determine if this is a string type for constraint application
+        let is_string_type = base_schema_json.contains("\"type\":\"string\"");
+        let is_array_type = base_schema_json.contains("\"type\":\"array\"");
+
+        // Build the field schema with constraints applied
+        let field_schema = if constraints.min.is_some()
+            || constraints.max.is_some()
+            || constraints.format.is_some()
+            || constraints.pattern.is_some()
+            || constraints.description.is_some()
+        {
+            // Apply constraints using utoipa ObjectBuilder
+            build_schema_with_constraints(field_ty, &constraints, is_string_type, is_array_type)
+        } else {
+            // No constraints - use the base schema directly
+            base_schema_json.clone()
+        };
+
+        // Add field definition for object_schema_json (preserves nullable)
+        code.schema.push(JsonFieldDef {
+            name: json_key.clone(),
+            schema_json: field_schema,
+            // Matches parsing: `skip_if` only affects output
+            required: !is_optional && !container.default,
+            x_attrs: attrs.x_attrs.clone(),
+            deprecated: attrs.deprecated,
+        });
+
+        // Generate validation checks (still uses base_schema_json for type detection):
+        // `validate` stops at the first error, `validate_all` collects every one
+        generate_validation_checks(
+            &attrs,
+            &value,
+            &field_path,
+            is_optional,
+            &base_schema_json,
+            &|error| quote! { return Err(#error); },
+            &mut code.validation_checks,
+        );
+        generate_validation_checks(
+            &attrs,
+            &value,
+            &field_path,
+            is_optional,
+            &base_schema_json,
+            &|error| quote! { __errors.push(#error); },
+            &mut code.collect_checks,
+        );
+        code.collect_checks
+            .extend(generate_nested_validation(field_ty, &value, &field_path));
+    }
+
+    Ok(code)
+}
+
+/// A `#[field(flatten)]` field: parsed from, and serialized into, the parent
+/// object, with its violations reported as the parent's own.
+fn flatten_field(
+    code: &mut FieldsCode,
+    field: &Field,
+    value: &TokenStream2,
+    path: Option<&str>,
+    container: &TypeAttrs,
+) -> syn::Result<()> {
+    let field_name = &field.ident;
+    let field_ty = &field.ty;
+    if !is_custom_type(field_ty) {
+        return Err(syn::Error::new_spanned(
+            field_ty,
+            "flatten needs a type that derives Type.\n\
+             \n\
+             \u{2705} Correct:\n\
+             #[field(flatten)]\n\
+             pub audit: AuditFields,   // #[derive(Type)] struct\n\
+             \n\
+             \u{274C} Wrong:\n\
+             #[field(flatten)]\n\
+             pub audit: Option<AuditFields>,",
+        ));
+    }
+    if container.deny_unknown_fields {
+        return Err(syn::Error::new_spanned(
+            field,
+            "flatten can't be combined with deny_unknown_fields.\n\
+             \n\
+             The keys of the flattened type aren't known when the parent is\n\
+             derived, so unknown keys can't be told apart from them.",
+        ));
+    }
+
+    code.from_json.push(quote! {
+        #field_name: <#field_ty as mik_sdk::typed::FromJson>::from_json(__value)?
+    });
+    code.from_form.push(quote! {
+        #field_name: <#field_ty as mik_sdk::typed::FromForm>::from_form(__form)?
+    });
+    code.to_json.push(quote! {
+        let __flat = mik_sdk::json::ToJson::to_json(&#value);
+        for __key in __flat.keys() {
+            __json = __json.set(&__key, __flat.get(&__key));
+        }
+    });
+
+    code.flattened.push(get_openapi_schema(field_ty));
+    if let Some(custom_ident) = extract_custom_type_ident(field_ty)
+        && !code.nested_types.contains(&custom_ident)
+    {
+        code.nested_types.push(custom_ident);
+    }
+
+    let with_path = path.map(|path| quote! { .map_err(|e| e.with_path(#path)) });
+    code.validation_checks.push(quote! {
+        mik_sdk::typed::Validate::validate(&#value) #with_path ?;
+    });
+    code.collect_checks.extend(generate_nested_validation(
+        field_ty,
+        value,
+        path.unwrap_or(""),
+    ));
+    Ok(())
+}
+
+/// Body of `nested_schemas()`: `extra` component schemas, then the schemas of
+/// `nested_types` and everything they reference.
+pub fn nested_schemas_impl(nested_types: &[Ident], extra: &[(String, String)]) -> TokenStream2 {
+    if nested_types.is_empty() && extra.is_empty() {
+        return quote! { "" };
+    }
+
+    let extra_parts: Vec<String> = extra
+        .iter()
+        .map(|(name, schema)| format!("\"{name}\":{schema}"))
+        .collect();
+    let extra_json = extra_parts.join(",");
+
+    // Generate code that builds nested schemas at compile time
+    let nested_calls: Vec<TokenStream2> = nested_types
+        .iter()
+        .map(|ty| {
+            let ty_str = ty.to_string();
+            quote! {
+                // Add this type's schema
+                if !__parts.is_empty() {
+                    __parts.push(',');
+                }
+                // Use fully qualified write! to avoid format_push_string clippy warning
+                let _ = ::std::fmt::Write::write_fmt(
+                    &mut __parts,
+                    ::std::format_args!(
+                        "\"{}\":{}",
+                        #ty_str,
+                        <#ty as mik_sdk::typed::OpenApiSchema>::openapi_schema()
+                    )
+                );
+                // Add transitive nested schemas
+                let __nested = <#ty as mik_sdk::typed::OpenApiSchema>::nested_schemas();
+                if !__nested.is_empty() {
+                    __parts.push(',');
+                    __parts.push_str(__nested);
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        {
+            static __NESTED: ::std::sync::LazyLock<::std::string::String> = ::std::sync::LazyLock::new(|| {
+                let mut __parts = ::std::string::String::from(#extra_json);
+                #(#nested_calls)*
+                __parts
+            });
+            &__NESTED
+        }
+    }
+}
+
+/// Build a schema with constraints applied using utoipa.
+///
+/// This function handles applying min/max/format/pattern/description constraints
+/// to a field schema. It uses utoipa's `ObjectBuilder` for type-safe schema construction.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn build_schema_with_constraints(
+    ty: &syn::Type,
+    constraints: &FieldConstraints,
+    is_string: bool,
+    is_array: bool,
+) -> String {
+    use utoipa::openapi::{ArrayBuilder, ObjectBuilder};
+
+    // Get base schema JSON and parse the type
+    let base_json = get_openapi_schema(ty);
+
+    // For arrays, we need to handle minItems/maxItems specially
+    if is_array {
+        // Parse and rebuild with array constraints
+        let inner_ty = get_inner_type(ty);
+        let items_schema = inner_ty.map_or_else(
+            || {
+                ObjectBuilder::new()
+                    .schema_type(utoipa::openapi::schema::SchemaType::Type(
+                        utoipa::openapi::Type::Object,
+                    ))
+                    .build()
+                    .into()
+            },
+            |inner| {
+                let inner_json = get_openapi_schema(inner);
+                serde_json::from_str(&inner_json).unwrap_or_else(|_| {
+                    utoipa::openapi::RefOr::T(
+                        ObjectBuilder::new()
+                            .schema_type(utoipa::openapi::schema::SchemaType::Type(
+                                utoipa::openapi::Type::Object,
+                            ))
+                            .build()
+                            .into(),
+                    )
+                })
+            },
+        );
+
+        let mut arr_builder = ArrayBuilder::new().items(items_schema);
+
+        if let Some(min) = constraints.min {
+            arr_builder = arr_builder.min_items(Some(min as usize));
+        }
+        if let Some(max) = constraints.max {
+            arr_builder = arr_builder.max_items(Some(max as usize));
+        }
+
+        let arr_schema: utoipa::openapi::Schema = arr_builder.build().into();
+        return schema_to_json(&arr_schema);
+    }
+
+    // Check if this is a nullable (Option) type
+    let is_nullable = base_json.contains("\"nullable\":true");
+
+    // For $ref types, we can't easily add constraints via utoipa
+    if base_json.contains("\"$ref\"") {
+        return base_json;
+    }
+
+    // For non-array types, use ObjectBuilder with apply_constraints
+    let mut builder = ObjectBuilder::new();
+
+    // Set the type based on base schema
+    if base_json.contains("\"type\":\"string\"") {
+        builder = builder.schema_type(utoipa::openapi::schema::SchemaType::Type(
+            utoipa::openapi::Type::String,
+        ));
+    } else if base_json.contains("\"type\":\"integer\"") {
+        builder = builder.schema_type(utoipa::openapi::schema::SchemaType::Type(
+            utoipa::openapi::Type::Integer,
+        ));
+    } else if base_json.contains("\"type\":\"number\"") {
+        builder = builder.schema_type(utoipa::openapi::schema::SchemaType::Type(
+            utoipa::openapi::Type::Number,
+        ));
+    } else if base_json.contains("\"type\":\"boolean\"") {
+        builder = builder.schema_type(utoipa::openapi::schema::SchemaType::Type(
+            utoipa::openapi::Type::Boolean,
+        ));
+    } else {
+        builder = builder.schema_type(utoipa::openapi::schema::SchemaType::Type(
+            utoipa::openapi::Type::Object,
+        ));
+    }
+
+    // Apply constraints using the utoipa helper
+    builder = apply_constraints(builder, constraints, is_string);
+
+    let schema: utoipa::openapi::Schema = builder.build().into();
+    let schema_json = schema_to_json(&schema);
+
+    // Re-add nullable if this was an Option type
+    if is_nullable {
+        crate::openapi::utoipa::make_nullable_json(&schema_json)
+    } else {
+        schema_json
+    }
+}
+
+/// Extract the custom type identifier from a field type.
+///
+/// Returns `Some(Ident)` if the type is a custom type (not a primitive or built-in).
+/// Handles `Option<T>` and `Vec<T>` wrappers to extract the inner custom type.
+pub fn extract_custom_type_ident(ty: &syn::Type) -> Option<Ident> {
+    if let syn::Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        let name = segment.ident.to_string();
+
+        // Handle wrapper types - extract inner type
+        if name == "Option" || name == "Vec" {
+            if let Some(inner) = registry_get_inner_type(ty) {
+                return extract_custom_type_ident(inner);
+            }
+            return None;
+        }
+
+        // Check if it's a known primitive type
+        if lookup_type(&name).is_some() {
+            return None;
+        }
+
+        // It's a custom type - return the ident
+        return Some(segment.ident.clone());
+    }
+    None
+}
+
+/// Whether `ty` is a user type rather than a primitive, `Option` or `Vec`.
+pub fn is_custom_type(ty: &syn::Type) -> bool {
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path.path.segments.last().is_some_and(|segment| {
+        let name = segment.ident.to_string();
+        name != "Option" && name != "Vec" && lookup_type(&name).is_none()
+    })
+}
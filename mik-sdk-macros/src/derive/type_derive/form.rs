@@ -0,0 +1,140 @@
+//! Form decoding code generation for #[derive(Type)].
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Type;
+
+use crate::derive::{get_inner_type, is_option_type, rust_type_to_name};
+use crate::type_registry::lookup_type;
+
+/// How a field is read from `FormData`.
+enum FormField<'a> {
+    /// `bool` - a checkbox, absent means `false`
+    Flag,
+    /// Registry scalar parsed with `FromStr`
+    Scalar(&'a Type),
+    /// `Upload` file part
+    Upload,
+    /// Anything else - the field value parsed as JSON
+    Json(&'a Type),
+}
+
+impl<'a> FormField<'a> {
+    fn of(ty: &'a Type) -> Self {
+        match type_ident(ty).as_deref() {
+            Some("bool") => Self::Flag,
+            Some("Upload") => Self::Upload,
+            Some(name) if lookup_type(name).is_some() => Self::Scalar(ty),
+            _ => Self::Json(ty),
+        }
+    }
+}
+
+/// Generate the initializer expression of one field inside `from_form`.
+pub fn generate_form_field(ty: &Type, key: &str) -> TokenStream2 {
+    let missing = quote! { mik_sdk::typed::ParseError::missing(#key) };
+
+    if is_option_type(ty) {
+        let Some(inner) = get_inner_type(ty) else {
+            return quote! { None };
+        };
+        return match FormField::of(inner) {
+            FormField::Flag => quote! { __form.flag(#key)? },
+            FormField::Upload => quote! { __form.file(#key).cloned() },
+            FormField::Scalar(inner) => {
+                let parse = parse_scalar(inner, key);
+                quote! {
+                    match __form.value(#key) {
+                        Some(__v) if !__v.is_empty() => Some(#parse?),
+                        _ => None,
+                    }
+                }
+            },
+            FormField::Json(inner) => {
+                let parse = parse_json(inner, key);
+                quote! {
+                    match __form.value(#key) {
+                        Some(__v) if !__v.is_empty() => Some(#parse),
+                        _ => None,
+                    }
+                }
+            },
+        };
+    }
+
+    if type_ident(ty).as_deref() == Some("Vec")
+        && let Some(inner) = get_inner_type(ty)
+    {
+        match FormField::of(inner) {
+            FormField::Upload => {
+                return quote! { __form.files(#key).into_iter().cloned().collect() };
+            },
+            FormField::Scalar(inner) => {
+                let parse = parse_scalar(inner, key);
+                return quote! {
+                    __form.values(#key)
+                        .into_iter()
+                        .map(|__v| #parse)
+                        .collect::<Result<_, mik_sdk::typed::ParseError>>()?
+                };
+            },
+            // Vec<bool> and Vec<Struct> fall through to JSON
+            FormField::Flag | FormField::Json(_) => {},
+        }
+    }
+
+    match FormField::of(ty) {
+        FormField::Flag => quote! { __form.flag(#key)?.unwrap_or(false) },
+        FormField::Upload => quote! { __form.file(#key).cloned().ok_or_else(|| #missing)? },
+        FormField::Scalar(ty) => {
+            let parse = parse_scalar(ty, key);
+            quote! {
+                match __form.value(#key) {
+                    Some(__v) => #parse?,
+                    None => return Err(#missing),
+                }
+            }
+        },
+        FormField::Json(ty) => {
+            let parse = parse_json(ty, key);
+            quote! {
+                match __form.value(#key) {
+                    Some(__v) => #parse,
+                    None => return Err(#missing),
+                }
+            }
+        },
+    }
+}
+
+/// `__v` parsed with `FromStr`, as a `Result` with failures mapped to a type mismatch.
+fn parse_scalar(ty: &Type, key: &str) -> TokenStream2 {
+    let type_name = rust_type_to_name(ty);
+    quote! {
+        __v.parse::<#ty>().map_err(|_|
+            mik_sdk::typed::ParseError::type_mismatch(#key, #type_name)
+        )
+    }
+}
+
+/// Parse `__v` as JSON and decode it with `FromJson`.
+fn parse_json(ty: &Type, key: &str) -> TokenStream2 {
+    quote! {
+        match mik_sdk::json::try_parse(__v.as_bytes()) {
+            Some(__json) => <#ty as mik_sdk::typed::FromJson>::from_json(&__json)?,
+            None => return Err(mik_sdk::typed::ParseError::invalid_format(#key, __v)),
+        }
+    }
+}
+
+/// The last path segment of a type, e.g. `Vec` for `std::vec::Vec<T>`.
+fn type_ident(ty: &Type) -> Option<String> {
+    if let Type::Path(type_path) = ty {
+        return type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string());
+    }
+    None
+}
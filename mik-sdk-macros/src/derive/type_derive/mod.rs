@@ -1,13 +1,19 @@
 //! #[derive(Type)] implementation for JSON body/response types.
 //!
 //! This module is split into submodules for maintainability:
-//! - `case`: PascalCase to snake_case conversion
+//! - `case`: PascalCase to snake_case conversion and `rename_all` rules
+//! - `data_enum`: Data-carrying and tagged enum implementation
 //! - `enum_impl`: Enum derive implementation
+//! - `fields`: Named-field code shared by structs and struct variants
+//! - `form`: Form/multipart decoding code generation
 //! - `struct_impl`: Struct derive implementation
 //! - `validation`: Validation code generation
 
-mod case;
+pub mod case;
+mod data_enum;
 mod enum_impl;
+mod fields;
+mod form;
 mod struct_impl;
 mod validation;
 
@@ -1,90 +1,241 @@
 //! Validation code generation for #[derive(Type)].
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{format_ident, quote};
 
-use crate::derive::FieldAttrs;
+use syn::Type;
+
+use crate::constants::CHECKED_FORMATS;
+use crate::derive::{FieldAttrs, regex};
+use crate::type_registry::{get_inner_type, lookup_type};
 
 /// Generate validation check code for a field.
+///
+/// `value` is the field as a place expression (`self.name`, or `(*name)` in a
+/// match arm) and `field_path` the name reported in errors. `fail` turns a
+/// `ValidationError` expression into the statement run when a check fails:
+/// returning it from `validate`, or collecting it in `validate_all`.
 pub fn generate_validation_checks(
     attrs: &FieldAttrs,
-    field_name: &syn::Ident,
+    value: &TokenStream2,
+    field_path: &str,
     is_optional: bool,
     base_schema: &str,
+    fail: &dyn Fn(TokenStream2) -> TokenStream2,
     validation_checks: &mut Vec<TokenStream2>,
 ) {
     if let Some(min) = attrs.min {
-        let field_name_str = field_name.to_string();
+        let fail = fail(quote! { mik_sdk::typed::ValidationError::min(#field_path, #min) });
         if is_optional {
             // Validate optional fields when Some
             if base_schema.contains("string") {
                 validation_checks.push(quote! {
-                    if let Some(ref __val) = self.#field_name {
+                    if let Some(ref __val) = #value {
                         if __val.len() < #min as usize {
-                            return Err(mik_sdk::typed::ValidationError::min(#field_name_str, #min));
+                            #fail
                         }
                     }
                 });
             } else {
                 // Use i128 for safe comparison across all integer types (avoids u64 -> i64 overflow)
                 validation_checks.push(quote! {
-                    if let Some(__val) = self.#field_name {
+                    if let Some(__val) = #value {
                         if (__val as i128) < (#min as i128) {
-                            return Err(mik_sdk::typed::ValidationError::min(#field_name_str, #min));
+                            #fail
                         }
                     }
                 });
             }
         } else if base_schema.contains("string") {
             validation_checks.push(quote! {
-                if self.#field_name.len() < #min as usize {
-                    return Err(mik_sdk::typed::ValidationError::min(#field_name_str, #min));
+                if #value.len() < #min as usize {
+                    #fail
                 }
             });
         } else {
             // Use i128 for safe comparison across all integer types (avoids u64 -> i64 overflow)
             validation_checks.push(quote! {
-                if (self.#field_name as i128) < (#min as i128) {
-                    return Err(mik_sdk::typed::ValidationError::min(#field_name_str, #min));
+                if (#value as i128) < (#min as i128) {
+                    #fail
                 }
             });
         }
     }
     if let Some(max) = attrs.max {
-        let field_name_str = field_name.to_string();
+        let fail = fail(quote! { mik_sdk::typed::ValidationError::max(#field_path, #max) });
         if is_optional {
             // Validate optional fields when Some
             if base_schema.contains("string") {
                 validation_checks.push(quote! {
-                    if let Some(ref __val) = self.#field_name {
+                    if let Some(ref __val) = #value {
                         if __val.len() > #max as usize {
-                            return Err(mik_sdk::typed::ValidationError::max(#field_name_str, #max));
+                            #fail
                         }
                     }
                 });
             } else {
                 // Use i128 for safe comparison across all integer types (avoids u64 -> i64 overflow)
                 validation_checks.push(quote! {
-                    if let Some(__val) = self.#field_name {
+                    if let Some(__val) = #value {
                         if (__val as i128) > (#max as i128) {
-                            return Err(mik_sdk::typed::ValidationError::max(#field_name_str, #max));
+                            #fail
                         }
                     }
                 });
             }
         } else if base_schema.contains("string") {
             validation_checks.push(quote! {
-                if self.#field_name.len() > #max as usize {
-                    return Err(mik_sdk::typed::ValidationError::max(#field_name_str, #max));
+                if #value.len() > #max as usize {
+                    #fail
                 }
             });
         } else {
             // Use i128 for safe comparison across all integer types (avoids u64 -> i64 overflow)
             validation_checks.push(quote! {
-                if (self.#field_name as i128) > (#max as i128) {
-                    return Err(mik_sdk::typed::ValidationError::max(#field_name_str, #max));
+                if (#value as i128) > (#max as i128) {
+                    #fail
                 }
             });
         }
     }
+    // Arrays of strings have an item type of "string" too
+    if base_schema.contains("\"type\":\"string\"") && !base_schema.contains("\"type\":\"array\"") {
+        generate_string_checks(
+            attrs,
+            value,
+            field_path,
+            is_optional,
+            fail,
+            validation_checks,
+        );
+    }
+}
+
+/// `format` and `pattern` checks for a string field.
+fn generate_string_checks(
+    attrs: &FieldAttrs,
+    value: &TokenStream2,
+    field_path: &str,
+    is_optional: bool,
+    fail: &dyn Fn(TokenStream2) -> TokenStream2,
+    validation_checks: &mut Vec<TokenStream2>,
+) {
+    let mut checks = Vec::new();
+
+    // Other formats are documentation only
+    if let Some(format) = attrs
+        .format
+        .as_deref()
+        .filter(|format| CHECKED_FORMATS.contains(format))
+    {
+        let check = format_ident!("is_{}", format.replace('-', "_"));
+        let fail = fail(quote! { mik_sdk::typed::ValidationError::format(#field_path, #format) });
+        checks.push(quote! {
+            if !mik_sdk::typed::formats::#check(__val) {
+                #fail
+            }
+        });
+    }
+    if let Some(pattern) = &attrs.pattern {
+        // Syntax was checked by parse_field_attrs
+        let Ok(nodes) = regex::parse(pattern) else {
+            return;
+        };
+        let nodes = regex::sequence_tokens(&nodes);
+        let mismatch =
+            fail(quote! { mik_sdk::typed::ValidationError::pattern(#field_path, #pattern) });
+        let message = format!("`{field_path}` is too complex to check against pattern: {pattern}");
+        let limit = fail(quote! {
+            mik_sdk::typed::ValidationError::custom(#field_path, "pattern_limit", #message)
+        });
+        checks.push(quote! {
+            {
+                const __PATTERN: &[mik_sdk::typed::pattern::Node] = #nodes;
+                match mik_sdk::typed::pattern::is_match(__PATTERN, __val) {
+                    Ok(true) => {}
+                    Ok(false) => { #mismatch }
+                    // Too much backtracking for this value
+                    Err(_) => { #limit }
+                }
+            }
+        });
+    }
+
+    if checks.is_empty() {
+        return;
+    }
+    if is_optional {
+        validation_checks.push(quote! {
+            if let Some(ref __val) = #value {
+                #(#checks)*
+            }
+        });
+    } else {
+        validation_checks.push(quote! {
+            {
+                let __val = &#value;
+                #(#checks)*
+            }
+        });
+    }
+}
+
+/// Code adding the `validate_all` errors of nested values to `__errors`.
+///
+/// Handles `T`, `Option<T>` and `Vec<T>` (and combinations) where `T` is not a
+/// primitive; `Vec` elements get indexed paths like `items[3]`. Returns `None`
+/// when the field holds nothing to walk.
+pub fn generate_nested_validation(
+    field_ty: &Type,
+    value: &TokenStream2,
+    field_path: &str,
+) -> Option<TokenStream2> {
+    nested_check(field_ty, &quote! { &#value }, &quote! { #field_path }, 0)
+}
+
+fn nested_check(
+    ty: &Type,
+    value: &TokenStream2,
+    path: &TokenStream2,
+    depth: usize,
+) -> Option<TokenStream2> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let name = type_path.path.segments.last()?.ident.to_string();
+
+    if name == "Option" {
+        let item = format_ident!("__item{}", depth);
+        let check = nested_check(get_inner_type(ty)?, &quote! { #item }, path, depth + 1)?;
+        return Some(quote! {
+            if let Some(#item) = #value {
+                #check
+            }
+        });
+    }
+    if name == "Vec" {
+        let index = format_ident!("__index{}", depth);
+        let item = format_ident!("__item{}", depth);
+        let item_path = format_ident!("__path{}", depth);
+        let check = nested_check(
+            get_inner_type(ty)?,
+            &quote! { #item },
+            &quote! { &#item_path },
+            depth + 1,
+        )?;
+        return Some(quote! {
+            for (#index, #item) in (#value).iter().enumerate() {
+                let #item_path = ::std::format!("{}[{}]", #path, #index);
+                #check
+            }
+        });
+    }
+    if lookup_type(&name).is_some() {
+        return None;
+    }
+    Some(quote! {
+        if let Err(__nested) = mik_sdk::typed::Validate::validate_all(#value) {
+            __errors.nest(#path, __nested);
+        }
+    })
 }
@@ -115,6 +115,12 @@ pub fn accepted(input: TokenStream) -> TokenStream {
     response::accepted_impl(input)
 }
 
+/// Return 200 OK with a `text/event-stream` body of Server-Sent Events.
+#[proc_macro]
+pub fn sse(input: TokenStream) -> TokenStream {
+    response::sse_impl(input)
+}
+
 // ============================================================================
 // DX Macros
 // ============================================================================
@@ -162,6 +168,9 @@ pub fn ids(input: TokenStream) -> TokenStream {
 ///     DELETE "/users/{id}" => delete_user(path: Id),
 /// }
 /// ```
+///
+/// Handlers of `-> T` routes return `T` or `Result<T, E>`, serialized as JSON
+/// with the route's status; other handlers return any `IntoResponse`.
 #[proc_macro]
 pub fn routes(input: TokenStream) -> TokenStream {
     schema::routes_impl(input)
@@ -173,7 +182,8 @@ pub fn routes(input: TokenStream) -> TokenStream {
 
 /// Derive macro for JSON body types.
 ///
-/// Generates `FromJson`, `Validate`, and `OpenApiSchema` implementations.
+/// Generates `FromJson`, `ToJson`, `Validate`, and `OpenApiSchema` implementations,
+/// plus `FromForm` for structs (used by `form:` and `multipart:` route inputs).
 ///
 /// ```ignore
 /// #[derive(Type)]
@@ -187,7 +197,27 @@ pub fn routes(input: TokenStream) -> TokenStream {
 ///     pub age: Option<i32>,
 /// }
 /// ```
-#[proc_macro_derive(Type, attributes(field))]
+///
+/// `#[mik(rename_all = "camelCase")]` renames struct fields, or enum
+/// variants. Structs also take `#[mik(deny_unknown_fields)]` to reject extra
+/// keys and `#[mik(default)]` to fill missing fields from `Default`.
+/// Fields take `#[field(flatten)]` to inline a nested Type, `#[field(skip)]`
+/// to leave them out of JSON, and `#[field(skip_if = "Option::is_none")]` to
+/// omit them from output.
+///
+/// Enums may carry data in newtype or struct variants. They are externally
+/// tagged by default; `#[mik(tag = "kind")]`, `#[mik(tag = "t", content = "c")]`
+/// and `#[mik(untagged)]` select the other serde-style representations.
+///
+/// ```ignore
+/// #[derive(Type)]
+/// #[mik(tag = "kind")]
+/// pub enum Payment {
+///     Card { last4: String },
+///     Iban { iban: String },
+/// }
+/// ```
+#[proc_macro_derive(Type, attributes(field, mik))]
 pub fn derive_type(input: TokenStream) -> TokenStream {
     derive::derive_type_impl(input)
 }
@@ -213,6 +243,43 @@ pub fn derive_query(input: TokenStream) -> TokenStream {
     derive::derive_query_impl(input)
 }
 
+/// Derive macro for typed request headers.
+///
+/// Generates `FromHeaders` implementation. Header names are matched
+/// case-insensitively and default to the field name with `_` replaced by `-`.
+///
+/// ```ignore
+/// #[derive(Headers)]
+/// pub struct AuthHeaders {
+///     #[field(rename = "X-Api-Key")]
+///     pub api_key: String,
+///
+///     pub x_tenant_id: Option<u64>, // reads "x-tenant-id"
+/// }
+/// ```
+#[proc_macro_derive(Headers, attributes(field))]
+pub fn derive_headers(input: TokenStream) -> TokenStream {
+    derive::derive_headers_impl(input)
+}
+
+/// Derive macro for typed request cookies.
+///
+/// Generates `FromCookies` implementation.
+///
+/// ```ignore
+/// #[derive(Cookies)]
+/// pub struct SessionCookies {
+///     pub session_id: String,
+///
+///     #[field(default = "en")]
+///     pub lang: String,
+/// }
+/// ```
+#[proc_macro_derive(Cookies, attributes(field))]
+pub fn derive_cookies(input: TokenStream) -> TokenStream {
+    derive::derive_cookies_impl(input)
+}
+
 /// Derive macro for path parameter types.
 ///
 /// Generates `FromPath` implementation.
@@ -10,7 +10,9 @@ use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::quote;
 
 use super::utoipa::problem_details_json;
-use crate::schema::types::{InputSource, RouteDef, RoutesDef};
+use crate::derive::escape_json_string;
+use crate::schema::pattern::{PathParam, openapi_path, parse_segments, path_params};
+use crate::schema::types::{HttpMethod, InputSource, RouteDef, RoutesDef};
 
 // =============================================================================
 // STATUS CODE HELPERS
@@ -80,22 +82,24 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
         });
     }
 
-    // Request body reference
-    if let Some(body_input) = route
-        .inputs
-        .iter()
-        .find(|i| matches!(i.source, InputSource::Body))
-    {
+    // Request body reference, with the media type of the body source
+    if let Some((body_input, media_type)) = route.inputs.iter().find_map(|i| match i.source {
+        InputSource::Body => Some((i, "application/json")),
+        InputSource::Form => Some((i, "application/x-www-form-urlencoded")),
+        InputSource::Multipart => Some((i, "multipart/form-data")),
+        _ => None,
+    }) {
         let type_name = body_input.type_name.to_string();
         parts.push(quote! {
             __parts.push(::std::format!(
-                "\"requestBody\":{{\"required\":true,\"content\":{{\"application/json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/{}\"}}}}}}}}",
+                "\"requestBody\":{{\"required\":true,\"content\":{{\"{}\":{{\"schema\":{{\"$ref\":\"#/components/schemas/{}\"}}}}}}}}",
+                #media_type,
                 #type_name
             ));
         });
     }
 
-    // Parameters (path + query) - collected from trait methods
+    // Parameters (path, query, header, cookie) - collected from trait methods
     let path_input = route
         .inputs
         .iter()
@@ -104,17 +108,95 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
         .inputs
         .iter()
         .find(|i| matches!(i.source, InputSource::Query));
+    // Header and cookie parameters, in declaration order
+    let other_params_code: Vec<TokenStream2> = route
+        .inputs
+        .iter()
+        .filter_map(|input| {
+            let type_name = &input.type_name;
+            match input.source {
+                InputSource::Headers => Some(quote! {
+                    <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_header_params()
+                }),
+                InputSource::Cookies => Some(quote! {
+                    <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_cookie_params()
+                }),
+                InputSource::Path
+                | InputSource::Body
+                | InputSource::Form
+                | InputSource::Multipart
+                | InputSource::Query => None,
+            }
+        })
+        .collect();
 
-    if path_input.is_some() || query_input.is_some() {
-        let path_params_code = path_input.map_or_else(
-            || quote! { let __path_params: &str = "[]"; },
-            |input| {
+    // Parameters declared by the pattern itself, with constraint schemas
+    let pattern_params = route
+        .patterns
+        .first()
+        .and_then(|pattern| parse_segments(pattern).ok())
+        .map(|segments| path_params(&segments))
+        .unwrap_or_default();
+    let pattern_param_json = |name: &str, schema: &str| {
+        format!(
+            r#"{{"name":"{}","in":"path","required":true,"schema":{schema}}}"#,
+            escape_json_string(name)
+        )
+    };
+
+    if path_input.is_some()
+        || query_input.is_some()
+        || !other_params_code.is_empty()
+        || !pattern_params.is_empty()
+    {
+        let constrained: Vec<&PathParam> = pattern_params
+            .iter()
+            .filter(|param| param.constrained)
+            .collect();
+
+        let path_params_code = match path_input {
+            // No Path type - describe the parameters from the pattern
+            None => {
+                let entries: Vec<String> = pattern_params
+                    .iter()
+                    .map(|param| pattern_param_json(&param.name, &param.schema))
+                    .collect();
+                let params_json = format!("[{}]", entries.join(","));
+                quote! { let __path_params: &str = #params_json; }
+            },
+            Some(input) if constrained.is_empty() => {
                 let type_name = &input.type_name;
                 quote! {
                     let __path_params: &str = <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_path_params();
                 }
             },
-        );
+            // Pattern constraints override the Path type's schema for those parameters
+            Some(input) => {
+                let type_name = &input.type_name;
+                let names = constrained.iter().map(|param| &param.name);
+                let entries = constrained
+                    .iter()
+                    .map(|param| pattern_param_json(&param.name, &param.schema));
+                quote! {
+                    let __path_params_owned: ::std::string::String = {
+                        let mut __entries: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                        if let ::std::option::Option::Some(__declared) = mik_sdk::json::try_parse(
+                            <super::#type_name as mik_sdk::typed::OpenApiSchema>::openapi_path_params().as_bytes(),
+                        ) {
+                            for __i in 0..__declared.len().unwrap_or(0) {
+                                let __param = __declared.at(__i);
+                                if !matches!(__param.get("name").str().as_deref(), ::std::option::Option::Some(#(#names)|*)) {
+                                    __entries.push(__param.to_string());
+                                }
+                            }
+                        }
+                        #(__entries.push(#entries.to_string());)*
+                        ::std::format!("[{}]", __entries.join(","))
+                    };
+                    let __path_params: &str = &__path_params_owned;
+                }
+            },
+        };
 
         let query_params_code = query_input.map_or_else(
             || quote! { let __query_params: &str = "[]"; },
@@ -130,17 +212,14 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
             {
                 #path_params_code
                 #query_params_code
-                // Merge path and query params (strip brackets and combine)
-                let __path_inner = __path_params.trim_start_matches('[').trim_end_matches(']');
-                let __query_inner = __query_params.trim_start_matches('[').trim_end_matches(']');
-                let __all_params = match (__path_inner.is_empty(), __query_inner.is_empty()) {
-                    (true, true) => ::std::string::String::new(),
-                    (false, true) => __path_inner.to_string(),
-                    (true, false) => __query_inner.to_string(),
-                    (false, false) => ::std::format!("{},{}", __path_inner, __query_inner),
-                };
+                // Merge the parameter arrays (strip brackets and combine)
+                let __all_params: ::std::vec::Vec<&str> = [__path_params, __query_params #(, #other_params_code)*]
+                    .iter()
+                    .map(|__params| __params.trim_start_matches('[').trim_end_matches(']'))
+                    .filter(|__inner| !__inner.is_empty())
+                    .collect();
                 if !__all_params.is_empty() {
-                    __parts.push(::std::format!("\"parameters\":[{}]", __all_params));
+                    __parts.push(::std::format!("\"parameters\":[{}]", __all_params.join(",")));
                 }
             }
         });
@@ -149,23 +228,36 @@ fn generate_method_entry_code(route: &RouteDef, default_tag: Option<&str>) -> To
     // Response - includes success and error responses
     let status_code = route.status_code;
     let status_description = status_code_description(status_code);
+    // `#[etag]` routes may answer 304 to conditional requests
+    let not_modified = if route.etag.is_some() {
+        "\"304\":{\"description\":\"Not Modified\"},"
+    } else {
+        ""
+    };
 
     if let Some(ref output_type) = route.output_type {
-        let output_str = output_type.to_string();
+        let schema_ref = format!("{{\"$ref\":\"#/components/schemas/{output_type}\"}}");
+        let output_str = if route.output_list {
+            format!("{{\"type\":\"array\",\"items\":{schema_ref}}}")
+        } else {
+            schema_ref
+        };
         parts.push(quote! {
             __parts.push(::std::format!(
-                "\"responses\":{{\"{}\":{{\"description\":\"{}\",\"content\":{{\"application/json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/{}\"}}}}}}}},\"4XX\":{{\"description\":\"Client Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}},\"5XX\":{{\"description\":\"Server Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}}}}",
+                "\"responses\":{{\"{}\":{{\"description\":\"{}\",\"content\":{{\"application/json\":{{\"schema\":{}}}}}}},{}\"4XX\":{{\"description\":\"Client Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}},\"5XX\":{{\"description\":\"Server Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}}}}",
                 #status_code,
                 #status_description,
-                #output_str
+                #output_str,
+                #not_modified
             ));
         });
     } else {
         parts.push(quote! {
             __parts.push(::std::format!(
-                "\"responses\":{{\"{}\":{{\"description\":\"{}\"}},\"4XX\":{{\"description\":\"Client Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}},\"5XX\":{{\"description\":\"Server Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}}}}",
+                "\"responses\":{{\"{}\":{{\"description\":\"{}\"}},{}\"4XX\":{{\"description\":\"Client Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}},\"5XX\":{{\"description\":\"Server Error\",\"content\":{{\"application/problem+json\":{{\"schema\":{{\"$ref\":\"#/components/schemas/ProblemDetails\"}}}}}}}}}}",
                 #status_code,
-                #status_description
+                #status_description,
+                #not_modified
             ));
         });
     }
@@ -210,12 +302,18 @@ fn generate_paths_code(routes: &[RouteDef], default_tag: Option<&str>) -> TokenS
 
     // Group routes by path
     let mut paths: HashMap<String, Vec<&RouteDef>> = HashMap::new();
-    for route in routes {
+    // OpenAPI 3.0 path items only have fields for the standard methods
+    for route in routes
+        .iter()
+        .filter(|route| !matches!(route.method, HttpMethod::Custom(_)))
+    {
+        // OpenAPI path templates have no constraints or catch-all markers
         let path = route
             .patterns
             .first()
-            .map_or("/", std::string::String::as_str);
-        paths.entry(path.to_string()).or_default().push(route);
+            .and_then(|pattern| parse_segments(pattern).ok())
+            .map_or_else(|| "/".to_string(), |segments| openapi_path(&segments));
+        paths.entry(path).or_default().push(route);
     }
 
     // Generate code for each path
@@ -10,7 +10,7 @@
 
 use utoipa::openapi::{
     ArrayBuilder, ObjectBuilder, RefOr, Schema,
-    schema::{SchemaFormat, SchemaType},
+    schema::{KnownFormat, SchemaFormat, SchemaType},
 };
 
 // ============================================================================
@@ -46,6 +46,13 @@ pub fn rust_type_to_schema(type_name: &str) -> RefOr<Schema> {
                 .build()
                 .into(),
         ),
+        "Upload" => RefOr::T(
+            ObjectBuilder::new()
+                .schema_type(SchemaType::Type(utoipa::openapi::Type::String))
+                .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)))
+                .build()
+                .into(),
+        ),
         // Custom type - reference to schema
         custom => RefOr::Ref(utoipa::openapi::Ref::from_schema_name(custom)),
     }
@@ -168,6 +175,7 @@ pub fn object_schema(fields: Vec<FieldDef>) -> Schema {
 }
 
 /// A field definition using raw JSON strings (preserves nullable).
+#[derive(Clone)]
 pub struct JsonFieldDef {
     pub name: String,
     pub schema_json: String,
@@ -268,6 +276,26 @@ pub fn object_schema_json(fields: Vec<JsonFieldDef>) -> String {
     }
 }
 
+/// Merge an object schema with the schemas of `#[field(flatten)]` fields.
+///
+/// Flattened types are usually `$ref`s whose properties aren't known when the
+/// parent is derived, so the result is `{"allOf": [object, flattened...]}`.
+pub fn flattened_schema_json(object_json: String, flattened: &[String]) -> String {
+    if flattened.is_empty() {
+        return object_json;
+    }
+    format!(r#"{{"allOf":[{object_json},{}]}}"#, flattened.join(","))
+}
+
+/// Close an object schema from [`object_schema_json`] to other properties
+/// (`"additionalProperties": false`).
+pub fn deny_additional_properties_json(schema_json: &str) -> String {
+    schema_json.strip_suffix('}').map_or_else(
+        || schema_json.to_string(),
+        |open| format!(r#"{open},"additionalProperties":false}}"#),
+    )
+}
+
 // ============================================================================
 // RFC 7807 PROBLEM DETAILS
 // ============================================================================
@@ -363,6 +391,14 @@ mod tests {
         assert!(json.contains("\"type\":\"integer\""));
     }
 
+    #[test]
+    fn test_upload_schema_is_binary_string() {
+        let schema = rust_type_to_schema("Upload");
+        let json = ref_or_schema_to_json(&schema);
+        assert!(json.contains("\"type\":\"string\""));
+        assert!(json.contains("\"format\":\"binary\""));
+    }
+
     #[test]
     fn test_enum_schema() {
         let schema = enum_schema(&["active", "inactive"]);
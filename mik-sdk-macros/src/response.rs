@@ -1108,3 +1108,85 @@ pub fn accepted_impl(input: TokenStream) -> TokenStream {
 
     TokenStream::from(tokens)
 }
+
+/// Return a 200 `text/event-stream` response of Server-Sent Events.
+///
+/// The argument is any expression yielding `mik_sdk::sse::Event`s.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mik_sdk::sse::Event;
+///
+/// sse!([
+///     Event::new().event("status").data("ready"),
+///     Event::new().id("2").json(&json::obj().set("progress", json::int(50))),
+/// ])
+///
+/// // With custom headers
+/// sse!(events, headers: {
+///     "X-Accel-Buffering": "no"
+/// })
+/// ```
+struct SseInput {
+    events: Expr,
+    headers: Option<HeadersBlock>,
+}
+
+impl Parse for SseInput {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        if input.is_empty() {
+            return Err(input.error(
+                "Expected events.\n\
+                 \n\
+                 Correct syntax:\n\
+                 sse!([Event::new().data(\"hello\")])",
+            ));
+        }
+        let events: Expr = input.parse()?;
+
+        let headers = if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let ident: syn::Ident = input.parse()?;
+            if ident != "headers" {
+                return Err(syn::Error::new_spanned(ident, "Expected 'headers' keyword"));
+            }
+            input.parse::<Token![:]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { events, headers })
+    }
+}
+
+pub fn sse_impl(input: TokenStream) -> TokenStream {
+    let SseInput { events, headers } = parse_macro_input!(input as SseInput);
+
+    let base_headers = vec![
+        quote! {
+            (
+                ::mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                ::mik_sdk::constants::MIME_EVENT_STREAM.to_string()
+            )
+        },
+        quote! {
+            (
+                ::mik_sdk::constants::HEADER_CACHE_CONTROL.to_string(),
+                "no-cache".to_string()
+            )
+        },
+    ];
+    let headers_code = generate_headers_code(base_headers, headers.as_ref());
+
+    let tokens = quote! {
+        handler::Response {
+            status: 200,
+            headers: #headers_code,
+            body: Some(::mik_sdk::sse::encode(#events).into_bytes()),
+        }
+    };
+
+    TokenStream::from(tokens)
+}
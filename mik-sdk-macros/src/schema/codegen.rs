@@ -1,109 +1,53 @@
-//! Route matching and handler wrapper code generation.
+//! Handler wrapper code generation.
+//!
+//! Route matching itself lives in [`super::trie`].
 
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 
-use super::types::{InputSource, RouteDef, TypedInput};
+use super::types::{EtagMode, InputSource, RouteDef, TypedInput};
 
 // =============================================================================
-// CODE GENERATION - ROUTE MATCHING
+// CODE GENERATION - HANDLER WRAPPERS
 // =============================================================================
 
-pub fn extract_param_names(pattern: &str) -> Vec<String> {
-    let mut params = Vec::new();
-    let mut chars = pattern.chars();
-
-    while let Some(c) = chars.next() {
-        if c == '{' {
-            let mut name = String::new();
-            for c in chars.by_ref() {
-                if c == '}' {
-                    break;
-                }
-                name.push(c);
-            }
-            if !name.is_empty() {
-                params.push(name);
-            }
+/// A `400 Bad Request` problem response with the given `detail` expression.
+fn bad_request(detail: &TokenStream2) -> TokenStream2 {
+    quote! {
+        handler::Response {
+            status: 400,
+            headers: vec![
+                (
+                    mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                    mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
+                )
+            ],
+            body: Some(mik_sdk::json::obj()
+                .set("type", mik_sdk::json::str("about:blank"))
+                .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
+                .set("status", mik_sdk::json::int(400))
+                .set("detail", mik_sdk::json::str(#detail))
+                .to_bytes()),
         }
     }
-
-    params
 }
 
-pub fn generate_pattern_matcher(pattern: &str) -> TokenStream2 {
-    let params = extract_param_names(pattern);
+#[allow(clippy::too_many_lines)] // One parser per input source
+pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<TokenStream2>) {
+    let mut parsing = Vec::new();
+    let mut args = Vec::new();
 
-    if params.is_empty() {
+    let parse_error = bad_request(&quote! { &e.to_string() });
+    let invalid_json = bad_request(&quote! { "Invalid JSON body" });
+    let missing_body = bad_request(&quote! { "Request body required" });
+    // Bodies that parse but break `#[field]` constraints get a 422 listing every violation
+    let validate = |var_name: &proc_macro2::Ident, type_name: &syn::Ident| {
         quote! {
-            (|| -> Option<::std::collections::HashMap<String, String>> {
-                if __mik_path == #pattern {
-                    Some(::std::collections::HashMap::new())
-                } else {
-                    None
-                }
-            })()
-        }
-    } else {
-        let segments: Vec<&str> = pattern.split('/').collect();
-        let segment_count = segments.len();
-
-        let mut checks = Vec::new();
-        let mut extractions = Vec::new();
-
-        for (i, segment) in segments.iter().enumerate() {
-            if segment.starts_with('{') && segment.ends_with('}') {
-                // Parameter segment - URL decode the value
-                let param_name = &segment[1..segment.len() - 1];
-                extractions.push(quote! {
-                    let __mik_raw_param = __mik_segments[#i];
-                    // URL decode the path parameter. If decoding fails (malformed percent-encoding),
-                    // fall back to the raw value. This is intentional: invalid encoding shouldn't
-                    // crash the handler, and the raw value will either match the route or not.
-                    let __mik_decoded_param = mik_sdk::url_decode(__mik_raw_param)
-                        .unwrap_or_else(|_| __mik_raw_param.to_string());
-                    __mik_params.insert(#param_name.to_string(), __mik_decoded_param);
-                });
-            } else if !segment.is_empty() {
-                checks.push(quote! {
-                    __mik_segments[#i] == #segment
-                });
-            } else if i > 0 {
-                checks.push(quote! {
-                    __mik_segments[#i].is_empty()
-                });
+            if let Err(e) = <#type_name as mik_sdk::typed::Validate>::validate_all(&#var_name) {
+                return Self::__mik_respond(mik_sdk::response::ApiError::from(e));
             }
         }
-
-        let all_checks = if checks.is_empty() {
-            quote! { true }
-        } else {
-            quote! { #(#checks)&&* }
-        };
-
-        quote! {
-            (|| -> Option<::std::collections::HashMap<String, String>> {
-                let __mik_segments: Vec<&str> = __mik_path.split('/').collect();
-                if __mik_segments.len() == #segment_count && #all_checks {
-                    let mut __mik_params = ::std::collections::HashMap::new();
-                    #(#extractions)*
-                    Some(__mik_params)
-                } else {
-                    None
-                }
-            })()
-        }
-    }
-}
-
-// =============================================================================
-// CODE GENERATION - HANDLER WRAPPERS
-// =============================================================================
-
-#[allow(clippy::too_many_lines)] // Complex input parsing with many type cases
-pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<TokenStream2>) {
-    let mut parsing = Vec::new();
-    let mut args = Vec::new();
+    };
 
     for (i, input) in inputs.iter().enumerate() {
         let var_name = format_ident!("__mik_input_{}", i);
@@ -114,26 +58,9 @@ pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<
                 parsing.push(quote! {
                     let #var_name = match <#type_name as mik_sdk::typed::FromPath>::from_params(&__mik_params) {
                         Ok(v) => v,
-                        Err(e) => {
-                            return handler::Response {
-                                status: 400,
-                                headers: vec![
-                                    (
-                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                    )
-                                ],
-                                body: Some(mik_sdk::json::obj()
-                                    .set("type", mik_sdk::json::str("about:blank"))
-                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                    .set("status", mik_sdk::json::int(400))
-                                    .set("detail", mik_sdk::json::str(&e.to_string()))
-                                    .to_bytes()),
-                            };
-                        }
+                        Err(e) => return #parse_error,
                     };
                 });
-                args.push(quote! { #var_name });
             },
             InputSource::Body => {
                 parsing.push(quote! {
@@ -143,64 +70,35 @@ pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<
                                 Some(json) => {
                                     match <#type_name as mik_sdk::typed::FromJson>::from_json(&json) {
                                         Ok(v) => v,
-                                        Err(e) => {
-                                            return handler::Response {
-                                                status: 400,
-                                                headers: vec![
-                                                    (
-                                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                                    )
-                                                ],
-                                                body: Some(mik_sdk::json::obj()
-                                                    .set("type", mik_sdk::json::str("about:blank"))
-                                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                                    .set("status", mik_sdk::json::int(400))
-                                                    .set("detail", mik_sdk::json::str(&e.to_string()))
-                                                    .to_bytes()),
-                                            };
-                                        }
+                                        Err(e) => return #parse_error,
                                     }
                                 }
-                                None => {
-                                    return handler::Response {
-                                        status: 400,
-                                        headers: vec![
-                                            (
-                                                mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                                mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                            )
-                                        ],
-                                        body: Some(mik_sdk::json::obj()
-                                            .set("type", mik_sdk::json::str("about:blank"))
-                                            .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                            .set("status", mik_sdk::json::int(400))
-                                            .set("detail", mik_sdk::json::str("Invalid JSON body"))
-                                            .to_bytes()),
-                                    };
-                                }
+                                None => return #invalid_json,
                             }
                         }
-                        None => {
-                            return handler::Response {
-                                status: 400,
-                                headers: vec![
-                                    (
-                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                    )
-                                ],
-                                body: Some(mik_sdk::json::obj()
-                                    .set("type", mik_sdk::json::str("about:blank"))
-                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                    .set("status", mik_sdk::json::int(400))
-                                    .set("detail", mik_sdk::json::str("Request body required"))
-                                    .to_bytes()),
-                            };
+                        None => return #missing_body,
+                    };
+                });
+                parsing.push(validate(&var_name, type_name));
+            },
+            InputSource::Form | InputSource::Multipart => {
+                let form = if matches!(input.source, InputSource::Form) {
+                    quote! { __mik_req.form_data() }
+                } else {
+                    quote! {
+                        match __mik_req.multipart() {
+                            Ok(parts) => mik_sdk::typed::FormData::from_parts(&parts),
+                            Err(e) => return #parse_error,
                         }
+                    }
+                };
+                parsing.push(quote! {
+                    let #var_name = match <#type_name as mik_sdk::typed::FromForm>::from_form(&#form) {
+                        Ok(v) => v,
+                        Err(e) => return #parse_error,
                     };
                 });
-                args.push(quote! { #var_name });
+                parsing.push(validate(&var_name, type_name));
             },
             InputSource::Query => {
                 parsing.push(quote! {
@@ -224,52 +122,57 @@ pub fn generate_input_parsing(inputs: &[TypedInput]) -> (Vec<TokenStream2>, Vec<
                         .unwrap_or_default();
                     let #var_name = match <#type_name as mik_sdk::typed::FromQuery>::from_query(&__mik_query_params) {
                         Ok(v) => v,
-                        Err(e) => {
-                            return handler::Response {
-                                status: 400,
-                                headers: vec![
-                                    (
-                                        mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
-                                        mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
-                                    )
-                                ],
-                                body: Some(mik_sdk::json::obj()
-                                    .set("type", mik_sdk::json::str("about:blank"))
-                                    .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(400)))
-                                    .set("status", mik_sdk::json::int(400))
-                                    .set("detail", mik_sdk::json::str(&e.to_string()))
-                                    .to_bytes()),
-                            };
-                        }
+                        Err(e) => return #parse_error,
+                    };
+                });
+            },
+            InputSource::Headers => {
+                parsing.push(quote! {
+                    let #var_name = match <#type_name as mik_sdk::typed::FromHeaders>::from_headers(__mik_req.headers()) {
+                        Ok(v) => v,
+                        Err(e) => return #parse_error,
+                    };
+                });
+            },
+            InputSource::Cookies => {
+                parsing.push(quote! {
+                    let #var_name = match <#type_name as mik_sdk::typed::FromCookies>::from_cookies(__mik_req.cookies()) {
+                        Ok(v) => v,
+                        Err(e) => return #parse_error,
                     };
                 });
-                args.push(quote! { #var_name });
             },
         }
+        args.push(quote! { #var_name });
     }
 
     (parsing, args)
 }
 
-pub fn generate_route_block(route: &RouteDef) -> TokenStream2 {
+/// Generate the dispatch arm for a matched route.
+///
+/// Runs after the trie walk has selected route `index`: builds the `Request`,
+/// runs the route's `before` hooks, parses typed inputs (returning 400 on
+/// failure, or 422 when a body breaks its constraints), calls the handler,
+/// converts its return value through `IntoResponse` (or `IntoTypedResponse`
+/// when the route declares `-> T`) and passes the response through the `after`
+/// hooks.
+pub fn generate_route_arm(index: usize, route: &RouteDef) -> TokenStream2 {
     let handler = &route.handler;
-    let method_check = route.method.to_method_check();
-
-    let pattern_checks: Vec<TokenStream2> = route
-        .patterns
-        .iter()
-        .map(|pattern_str| {
-            let matcher = generate_pattern_matcher(pattern_str);
-            quote! {
-                if let Some(__mik_params) = #matcher {
-                    return Some(__mik_params);
-                }
-            }
-        })
-        .collect();
 
     let (input_parsing, input_args) = generate_input_parsing(&route.inputs);
 
+    // FromPath reads the params map, so keep a copy when a path input is declared
+    let params = if route
+        .inputs
+        .iter()
+        .any(|input| matches!(input.source, InputSource::Path))
+    {
+        quote! { __mik_params.clone() }
+    } else {
+        quote! { __mik_params }
+    };
+
     // Build handler call with typed inputs + &Request
     let handler_call = if input_args.is_empty() {
         quote! { #handler(&__mik_req) }
@@ -277,26 +180,81 @@ pub fn generate_route_block(route: &RouteDef) -> TokenStream2 {
         quote! { #handler(#(#input_args),*, &__mik_req) }
     };
 
-    quote! {
-        if __mik_method == #method_check {
-            let __mik_try_match = || -> Option<::std::collections::HashMap<String, String>> {
-                #(#pattern_checks)*
-                None
+    // `-> T` routes must return `T` (or `Result<T, E>`), serialized with the route status
+    let handler_call = match &route.output_type {
+        Some(output) => {
+            let output = if route.output_list {
+                quote! { ::std::vec::Vec<#output> }
+            } else {
+                quote! { #output }
             };
+            let status = route.status_code;
+            // Point type mismatches at the handler name in routes!
+            quote_spanned! {handler.span()=>
+                mik_sdk::response::IntoTypedResponse::<#output>::into_typed_response(#handler_call, #status)
+            }
+        },
+        None => handler_call,
+    };
 
-            if let Some(__mik_params) = __mik_try_match() {
-                let __mik_req = mik_sdk::Request::new(
-                    __mik_method.clone(),
-                    __mik_raw.path.clone(),
-                    __mik_raw.headers.clone(),
-                    __mik_raw.body.clone(),
-                    __mik_params.clone(),
-                );
+    // `#[etag]` routes tag successful responses and answer fresh requests with 304
+    let handler_call = match route.etag {
+        Some(EtagMode::Strong) => {
+            quote! { mik_sdk::conditional::with_etag(&__mik_req, #handler_call) }
+        },
+        Some(EtagMode::Weak) => {
+            quote! { mik_sdk::conditional::with_weak_etag(&__mik_req, #handler_call) }
+        },
+        None => handler_call,
+    };
+
+    let build_request = quote! {
+        let __mik_req = mik_sdk::Request::new(
+            __mik_method,
+            __mik_raw.path,
+            __mik_raw.headers,
+            __mik_raw.body,
+            #params,
+        );
+    };
+
+    if route.before.is_empty() && route.after.is_empty() {
+        return quote! {
+            #index => {
+                #build_request
 
                 #(#input_parsing)*
 
-                return #handler_call;
+                return Self::__mik_respond(#handler_call);
             }
+        };
+    }
+
+    // Hooks: `before` may short-circuit; `after` sees every response of the route,
+    // including early returns from `before` and input parsing errors
+    let before = &route.before;
+    let after = &route.after;
+
+    quote! {
+        #index => {
+            #build_request
+
+            let __mik_response = (|| -> handler::Response {
+                #(
+                    if let ::std::option::Option::Some(__mik_early) = #before(&__mik_req) {
+                        return __mik_early;
+                    }
+                )*
+
+                #(#input_parsing)*
+
+                Self::__mik_respond(#handler_call)
+            })();
+            #(
+                let __mik_response = #after(&__mik_req, __mik_response);
+            )*
+
+            return __mik_response;
         }
     }
 }
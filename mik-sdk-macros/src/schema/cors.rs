@@ -0,0 +1,231 @@
+//! The `cors: { ... }` section of `routes!`.
+//!
+//! Parsed into a [`CorsDef`] and emitted as a `const` [`mik_sdk::cors::Cors`]
+//! policy that the generated dispatcher consults for every request.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Ident, LitBool, LitInt, LitStr, Result, Token,
+    parse::{Parse, ParseStream},
+};
+
+use super::types::parse_custom_method;
+use crate::constants::VALID_ROUTE_METHODS;
+use crate::errors::did_you_mean;
+
+/// Keys accepted inside `cors: { ... }`.
+const VALID_CORS_KEYS: &[&str] = &["origins", "methods", "headers", "credentials", "max_age"];
+
+/// A parsed `cors: { ... }` section.
+#[derive(Default)]
+pub struct CorsDef {
+    origins: Option<Vec<String>>,
+    methods: Option<Vec<String>>,
+    headers: Option<Vec<String>>,
+    credentials: bool,
+    max_age: Option<u32>,
+}
+
+impl Parse for CorsDef {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let content;
+        syn::braced!(content in input);
+        let mut def = Self::default();
+        let mut credentials_key = None;
+
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+
+            match key.to_string().as_str() {
+                "origins" => {
+                    let origins = parse_str_list(&content)?;
+                    for origin in &origins {
+                        validate_origin(origin)?;
+                    }
+                    def.origins = Some(origins.iter().map(LitStr::value).collect());
+                },
+                "methods" => {
+                    let methods = parse_method_list(&content)?;
+                    def.methods = Some(methods);
+                },
+                "headers" => {
+                    let headers = parse_str_list(&content)?;
+                    def.headers = Some(headers.iter().map(LitStr::value).collect());
+                },
+                "credentials" => {
+                    def.credentials = content.parse::<LitBool>()?.value;
+                    credentials_key = Some(key.clone());
+                },
+                "max_age" => def.max_age = Some(content.parse::<LitInt>()?.base10_parse()?),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!(
+                            "Unknown cors option '{other}'.{}\n\
+                             \n\
+                             Valid options: {}",
+                            did_you_mean(other, VALID_CORS_KEYS),
+                            VALID_CORS_KEYS.join(", ")
+                        ),
+                    ));
+                },
+            }
+
+            if content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+            }
+        }
+
+        // Credentials for any origin would let every site act as the user
+        if let Some(key) = credentials_key
+            && def.credentials
+            && def
+                .origins
+                .as_ref()
+                .is_none_or(|origins| origins.iter().any(|origin| origin == "*"))
+        {
+            return Err(syn::Error::new_spanned(
+                key,
+                "CORS credentials need an explicit list of origins.\n\
+                 \n\
+                 With \"*\" any website could make requests with the user's cookies.\n\
+                 \n\
+                 Correct syntax: cors: { origins: [\"https://app.example.com\"], credentials: true }",
+            ));
+        }
+
+        Ok(def)
+    }
+}
+
+impl CorsDef {
+    /// Generate the `mik_sdk::cors::Cors` constructor expression.
+    pub fn to_tokens(&self) -> TokenStream2 {
+        let mut policy = quote! { mik_sdk::cors::Cors::new() };
+
+        if let Some(origins) = &self.origins {
+            policy = quote! { #policy.origins(&[#(#origins),*]) };
+        }
+        if let Some(methods) = &self.methods {
+            policy = quote! { #policy.methods(&[#(#methods),*]) };
+        }
+        if let Some(headers) = &self.headers {
+            policy = quote! { #policy.headers(&[#(#headers),*]) };
+        }
+        if self.credentials {
+            policy = quote! { #policy.credentials(true) };
+        }
+        if let Some(max_age) = self.max_age {
+            policy = quote! { #policy.max_age(#max_age) };
+        }
+
+        policy
+    }
+}
+
+/// Parse `"value"` or `["a", "b"]`.
+fn parse_str_list(input: ParseStream<'_>) -> Result<Vec<LitStr>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let items = content.parse_terminated(<LitStr as Parse>::parse, Token![,])?;
+        Ok(items.into_iter().collect())
+    } else {
+        Ok(vec![input.parse()?])
+    }
+}
+
+/// Parse `GET` or `[GET, "PROPFIND"]` into method names as sent on the wire.
+///
+/// Methods are written as in routes: standard ones as identifiers, extension
+/// methods as string literals.
+fn parse_method_list(input: ParseStream<'_>) -> Result<Vec<String>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let items = content.parse_terminated(parse_method, Token![,])?;
+        Ok(items.into_iter().collect())
+    } else {
+        Ok(vec![parse_method(input)?])
+    }
+}
+
+/// Parse one method of a `methods` list.
+fn parse_method(input: ParseStream<'_>) -> Result<String> {
+    if input.peek(LitStr) {
+        let token: LitStr = input.parse()?;
+        return Ok(parse_custom_method(&token)?.wire_name());
+    }
+
+    let ident: Ident = input.parse()?;
+    let method = ident.to_string().to_uppercase();
+    if VALID_ROUTE_METHODS.contains(&method.as_str()) {
+        Ok(method)
+    } else {
+        Err(syn::Error::new_spanned(
+            &ident,
+            format!(
+                "Invalid HTTP method '{ident}' in cors methods.{}\n\
+                 \n\
+                 Valid methods: {}\n\
+                 Other methods are written as string literals: methods: [GET, \"PROPFIND\"]",
+                did_you_mean(&method, VALID_ROUTE_METHODS),
+                VALID_ROUTE_METHODS.join(", ")
+            ),
+        ))
+    }
+}
+
+/// Origins are `"*"` or `scheme://host[:port]`, compared verbatim against the
+/// browser's `Origin` header - so no path and no trailing slash.
+fn validate_origin(origin: &LitStr) -> Result<()> {
+    let value = origin.value();
+    if value == "*" {
+        return Ok(());
+    }
+
+    let Some((scheme, host)) = value.split_once("://") else {
+        return Err(syn::Error::new(
+            origin.span(),
+            format!(
+                "CORS origin \"{value}\" must include a scheme, e.g. \"https://{value}\", or be \"*\""
+            ),
+        ));
+    };
+    if host.is_empty() || host.contains('/') {
+        return Err(syn::Error::new(
+            origin.span(),
+            format!(
+                "CORS origin \"{value}\" must not contain a path. Did you mean \"{scheme}://{}\"?",
+                host.split('/').next().unwrap_or_default()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_methods_accept_trace_and_custom_methods() {
+        let def: CorsDef = syn::parse_str(r#"{ methods: [get, TRACE, "PROPFIND"] }"#).unwrap();
+        assert_eq!(
+            def.methods.unwrap(),
+            ["GET", "TRACE", "PROPFIND"].map(String::from)
+        );
+
+        let def: CorsDef = syn::parse_str(r#"{ methods: "MKCOL" }"#).unwrap();
+        assert_eq!(def.methods.unwrap(), ["MKCOL"]);
+    }
+
+    #[test]
+    fn test_methods_reject_invalid_methods() {
+        assert!(syn::parse_str::<CorsDef>("{ methods: [GET, FETCH] }").is_err());
+        assert!(syn::parse_str::<CorsDef>(r#"{ methods: ["BAD METHOD"] }"#).is_err());
+    }
+}
@@ -12,6 +12,9 @@
 //! ```
 
 pub mod codegen;
+pub mod cors;
+pub mod pattern;
+pub mod trie;
 pub mod types;
 
 use proc_macro::TokenStream;
@@ -19,9 +22,11 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::parse_macro_input;
 
+use crate::constants::MAX_CUSTOM_METHODS;
 use crate::openapi::generate_openapi_json;
-use codegen::generate_route_block;
-use types::RoutesDef;
+use codegen::generate_route_arm;
+use trie::{build_trie, generate_allow_header, generate_trie_walk};
+use types::{HttpMethod, RoutesDef};
 
 // =============================================================================
 // MAIN IMPLEMENTATION
@@ -54,16 +59,77 @@ pub fn routes_impl(input: TokenStream) -> TokenStream {
         }
     }
 
-    let route_blocks: Vec<TokenStream2> = defs.routes.iter().map(generate_route_block).collect();
+    let custom_methods = HttpMethod::custom_methods(&defs.routes);
+    if custom_methods.len() > MAX_CUSTOM_METHODS {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!(
+                "Too many custom HTTP methods: {} (at most {MAX_CUSTOM_METHODS} per routes! block)",
+                custom_methods.len()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let trie_walk = generate_trie_walk(&build_trie(&defs.routes));
+    let allow_header = generate_allow_header(&custom_methods);
+    let route_arms: Vec<TokenStream2> = defs
+        .routes
+        .iter()
+        .enumerate()
+        .map(|(index, route)| generate_route_arm(index, route))
+        .collect();
 
     let openapi_static = generate_openapi_json(&defs);
 
+    // CORS: capture the Origin before dispatch, answer preflights for routed
+    // paths, and stamp Access-Control-* headers on every response
+    let (cors_const, cors_origin, cors_apply, cors_preflight) = defs.cors.as_ref().map_or_else(
+        || (quote! {}, quote! {}, quote! {}, quote! {}),
+        |cors| {
+            let policy = cors.to_tokens();
+            (
+                quote! {
+                    const __MIK_CORS: mik_sdk::cors::Cors = #policy;
+                },
+                quote! {
+                    let __mik_origin = __mik_raw
+                        .headers
+                        .iter()
+                        .find(|(__mik_name, _)| __mik_name.eq_ignore_ascii_case(mik_sdk::cors::HEADER_ORIGIN))
+                        .map(|(_, __mik_value)| __mik_value.clone());
+                },
+                quote! {
+                    Self::__MIK_CORS.apply(__mik_origin.as_deref(), &mut __mik_response.headers);
+                },
+                quote! {
+                    // Preflight for a routed path - answer without running any handler
+                    if __mik_allowed != 0 && __mik_method == mik_sdk::Method::Options {
+                        let __mik_allow = #allow_header;
+                        if let ::std::option::Option::Some(__mik_preflight) =
+                            Self::__MIK_CORS.preflight(&__mik_raw.headers, &__mik_allow)
+                        {
+                            let mut __mik_headers = vec![(mik_sdk::constants::HEADER_ALLOW.to_string(), __mik_allow)];
+                            __mik_headers.extend(__mik_preflight);
+                            return handler::Response {
+                                status: 204,
+                                headers: __mik_headers,
+                                body: None,
+                            };
+                        }
+                    }
+                },
+            )
+        },
+    );
+
     let tokens = quote! {
         // Compile-time check: ensure bindings module is properly configured.
         // If you see an error here, make sure you have:
         //   1. `mod bindings;` at the top of your lib.rs
         //   2. Generated bindings via cargo-component build
-        //   3. The bindings module exports `mik::core::handler::{Guest, Response, RequestData, Method}`
+        //   3. The bindings module exports `mik::core::handler::{Guest, GuestBodyStream, BodyStream, Response, RequestData, Method}`
         const _: () = {
             // This const assertion verifies the Guest trait is accessible
             fn __mik_check_bindings_setup() {
@@ -133,11 +199,138 @@ pub fn routes_impl(input: TokenStream) -> TokenStream {
             println!("Generated openapi.json ({} bytes)", __mik_schema::json().len());
         }
 
+        /// Run a request through the router in-process and return the response.
+        ///
+        /// Exercises routing, typed input parsing, validation, hooks and CORS
+        /// exactly like the WASM export, without a WASM toolchain or runtime.
+        ///
+        /// # Example
+        /// ```ignore
+        /// use mik_sdk::testing::TestRequest;
+        ///
+        /// __mik_test(TestRequest::get("/users/42")).assert_status(200);
+        /// ```
+        ///
+        /// Only available on native targets (excluded from WASM builds).
+        #[cfg(not(target_arch = "wasm32"))]
+        #[allow(dead_code)]
+        pub fn __mik_test(
+            request: impl ::std::convert::Into<mik_sdk::testing::TestRequest>,
+        ) -> mik_sdk::testing::TestResponse {
+            let (__mik_method, __mik_path, __mik_headers, __mik_body) = request.into().into_parts();
+            let __mik_method = match __mik_method {
+                mik_sdk::Method::Get => handler::Method::Get,
+                mik_sdk::Method::Post => handler::Method::Post,
+                mik_sdk::Method::Put => handler::Method::Put,
+                mik_sdk::Method::Patch => handler::Method::Patch,
+                mik_sdk::Method::Delete => handler::Method::Delete,
+                mik_sdk::Method::Head => handler::Method::Head,
+                mik_sdk::Method::Options => handler::Method::Options,
+                mik_sdk::Method::Trace => handler::Method::Trace,
+                mik_sdk::Method::Other(__mik_name) => handler::Method::Other(__mik_name),
+                __mik_other => panic!("routes! cannot dispatch {__mik_other} requests"),
+            };
+            let __mik_response = <Handler as handler::Guest>::handle(handler::RequestData {
+                method: __mik_method,
+                path: __mik_path,
+                headers: __mik_headers,
+                body: __mik_body,
+            });
+            // Join streamed chunks into the body, as the client would receive it
+            let __mik_body = match mik_sdk::stream::__take_pending() {
+                ::std::option::Option::Some(__mik_chunks) => {
+                    let mut __mik_joined = __mik_response.body.unwrap_or_default();
+                    while let ::std::option::Option::Some(__mik_chunk) = __mik_chunks.next_chunk() {
+                        __mik_joined.extend(__mik_chunk);
+                    }
+                    ::std::option::Option::Some(__mik_joined)
+                }
+                ::std::option::Option::None => __mik_response.body,
+            };
+            mik_sdk::testing::TestResponse::new(__mik_response.status, __mik_response.headers, __mik_body)
+        }
+
+        // Handlers may return `handler::Response`, `Result<T, E>` or anything else
+        // implementing `IntoResponse`
+        impl mik_sdk::response::IntoResponse for handler::Response {
+            fn into_response(self) -> mik_sdk::response::ResponseParts {
+                mik_sdk::response::ResponseParts::new(self.status, self.headers, self.body)
+            }
+        }
+
+        // `Streaming` bodies are exported as the `body-stream` resource
+        impl handler::GuestBodyStream for mik_sdk::stream::BodyChunks {
+            fn next_chunk(&self) -> ::std::option::Option<::std::vec::Vec<u8>> {
+                mik_sdk::stream::BodyChunks::next_chunk(self)
+            }
+        }
+
         struct Handler;
 
         impl Guest for Handler {
+            type BodyStream = mik_sdk::stream::BodyChunks;
+
             fn handle(__mik_raw: handler::RequestData) -> handler::Response {
-                let __mik_method = match __mik_raw.method {
+                // Drop a streamed body an earlier request left behind
+                let _ = mik_sdk::stream::__take_pending();
+                let __mik_is_head = matches!(__mik_raw.method, handler::Method::Head);
+                #cors_origin
+                let mut __mik_response = Self::__mik_dispatch(__mik_raw);
+                let __mik_streamed = mik_sdk::stream::__take_marker(&mut __mik_response.headers);
+                #cors_apply
+
+                // HEAD responses keep the headers (and length) of the GET response, never a body
+                if __mik_is_head {
+                    if let ::std::option::Option::Some(__mik_body) = __mik_response.body.take() {
+                        let __mik_has_length = __mik_response.headers.iter().any(|(__mik_name, _)| {
+                            __mik_name.eq_ignore_ascii_case(mik_sdk::constants::HEADER_CONTENT_LENGTH)
+                        });
+                        if !__mik_has_length {
+                            __mik_response.headers.push((
+                                mik_sdk::constants::HEADER_CONTENT_LENGTH.to_string(),
+                                __mik_body.len().to_string(),
+                            ));
+                        }
+                    }
+                }
+
+                // A streamed body only follows the bodiless response that produced it,
+                // not a replacement from hooks or a 304
+                if !__mik_streamed
+                    || __mik_is_head
+                    || __mik_response.body.is_some()
+                    || matches!(__mik_response.status, 100..=199 | 204 | 304)
+                {
+                    let _ = mik_sdk::stream::__take_pending();
+                }
+
+                __mik_response
+            }
+
+            fn take_body_stream() -> ::std::option::Option<handler::BodyStream> {
+                mik_sdk::stream::__take_pending().map(handler::BodyStream::new)
+            }
+        }
+
+        impl Handler {
+            #cors_const
+
+            fn __mik_respond(__mik_value: impl mik_sdk::response::IntoResponse) -> handler::Response {
+                let mut __mik_parts = mik_sdk::response::IntoResponse::into_response(__mik_value);
+                if __mik_parts.__is_streamed() {
+                    __mik_parts
+                        .headers
+                        .push((mik_sdk::stream::__STREAM_MARKER.to_string(), ::std::string::String::new()));
+                }
+                handler::Response {
+                    status: __mik_parts.status,
+                    headers: __mik_parts.headers,
+                    body: __mik_parts.body,
+                }
+            }
+
+            fn __mik_dispatch(__mik_raw: handler::RequestData) -> handler::Response {
+                let __mik_method = match &__mik_raw.method {
                     handler::Method::Get => mik_sdk::Method::Get,
                     handler::Method::Post => mik_sdk::Method::Post,
                     handler::Method::Put => mik_sdk::Method::Put,
@@ -145,11 +338,59 @@ pub fn routes_impl(input: TokenStream) -> TokenStream {
                     handler::Method::Delete => mik_sdk::Method::Delete,
                     handler::Method::Head => mik_sdk::Method::Head,
                     handler::Method::Options => mik_sdk::Method::Options,
+                    handler::Method::Trace => mik_sdk::Method::Trace,
+                    handler::Method::Other(__mik_name) => mik_sdk::Method::Other(__mik_name.clone()),
                 };
 
                 let __mik_path = __mik_raw.path.split('?').next().unwrap_or(&__mik_raw.path);
 
-                #(#route_blocks)*
+                // Walk the route trie once; params are only collected for the winning route
+                let mut __mik_allowed: u64 = 0;
+                let __mik_matched: ::std::option::Option<(usize, ::std::collections::HashMap<String, String>)> = #trie_walk;
+
+                if let ::std::option::Option::Some((__mik_route, __mik_params)) = __mik_matched {
+                    match __mik_route {
+                        #(#route_arms)*
+                        _ => {}
+                    }
+                }
+
+                #cors_preflight
+
+                // Path matched without an explicit OPTIONS route - list the allowed methods
+                if __mik_allowed != 0 && __mik_method == mik_sdk::Method::Options {
+                    return handler::Response {
+                        status: 204,
+                        headers: vec![(mik_sdk::constants::HEADER_ALLOW.to_string(), #allow_header)],
+                        body: None,
+                    };
+                }
+
+                // Path matched but method did not - return 405 with the allowed methods
+                if __mik_allowed != 0 {
+                    return handler::Response {
+                        status: 405,
+                        headers: vec![
+                            (
+                                mik_sdk::constants::HEADER_CONTENT_TYPE.to_string(),
+                                mik_sdk::constants::MIME_PROBLEM_JSON.to_string()
+                            ),
+                            (
+                                mik_sdk::constants::HEADER_ALLOW.to_string(),
+                                #allow_header
+                            )
+                        ],
+                        body: Some(mik_sdk::json::obj()
+                            .set("type", mik_sdk::json::str("about:blank"))
+                            .set("title", mik_sdk::json::str(mik_sdk::constants::status_title(405)))
+                            .set("status", mik_sdk::json::int(405))
+                            .set("detail", mik_sdk::json::str(&format!(
+                                "Method {} not allowed for this route",
+                                __mik_method.as_str()
+                            )))
+                            .to_bytes()),
+                    };
+                }
 
                 // No route matched - return 404
                 handler::Response {
@@ -0,0 +1,419 @@
+//! Route pattern parsing.
+//!
+//! A pattern is split on `/` into segments. Besides literal text, a whole
+//! segment may be:
+//!
+//! - `{name}` - any single segment,
+//! - `{name:constraint}` - a single segment accepted by the constraint,
+//! - `{*name}` - the rest of the path, slashes included (last segment only).
+//!
+//! Constraints are `int`, `uuid`, or a character class repeated with `+` or
+//! `*` (e.g. `[a-z0-9-]+`, `[^.]+`). They are checked against the raw
+//! (still percent-encoded) segment.
+
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+
+use crate::derive::escape_json_string;
+use crate::errors::did_you_mean;
+
+/// Named constraints accepted after `:` in a path parameter.
+const VALID_CONSTRAINTS: &[&str] = &["int", "uuid"];
+
+/// A single `/`-separated segment of a route pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Literal text that must match the request segment exactly.
+    Literal(String),
+    /// Whole-segment `{name}` or `{name:constraint}` parameter.
+    Param(String, Option<Constraint>),
+    /// Trailing `{*name}` parameter capturing the rest of the path.
+    CatchAll(String),
+}
+
+/// A constraint a parameter segment must satisfy to match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constraint {
+    /// Optional `-` followed by ASCII digits.
+    Int,
+    /// Hyphenated UUID (`8-4-4-4-12` hex digits).
+    Uuid,
+    /// Character class repeated with `+` (non-empty) or `*`.
+    Class {
+        /// Original class text, e.g. `[a-z-]+`.
+        source: String,
+        /// Inclusive byte ranges listed in the class.
+        ranges: Vec<(u8, u8)>,
+        /// `[^...]` - match bytes outside the ranges.
+        negated: bool,
+        /// `*` repetition - the segment may be empty.
+        allow_empty: bool,
+    },
+}
+
+/// Split a route pattern into segments.
+///
+/// Empty segments are kept so that `"/"`, `""` and `"/users/"` stay distinct.
+/// Returns a human-readable error for malformed parameters.
+pub fn parse_segments(pattern: &str) -> Result<Vec<Segment>, String> {
+    let raw: Vec<&str> = pattern.split('/').collect();
+    let last = raw.len() - 1;
+
+    raw.iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            if segment.len() < 2 || !segment.starts_with('{') || !segment.ends_with('}') {
+                return Ok(Segment::Literal((*segment).to_string()));
+            }
+            let inner = &segment[1..segment.len() - 1];
+
+            if let Some(name) = inner.strip_prefix('*') {
+                if index != last {
+                    return Err(format!(
+                        "Catch-all parameter '{{*{name}}}' must be the last segment of \"{pattern}\".\n\
+                         \n\
+                         Example: GET \"/files/{{*key}}\" => get_file"
+                    ));
+                }
+                if name.is_empty() {
+                    return Err(format!(
+                        "Catch-all parameter in \"{pattern}\" needs a name.\n\
+                         \n\
+                         Example: GET \"/files/{{*key}}\" => get_file"
+                    ));
+                }
+                return Ok(Segment::CatchAll(name.to_string()));
+            }
+
+            let name = inner.split_once(':').map_or(inner, |(name, _)| name);
+            if name.is_empty() {
+                return Err(format!(
+                    "Path parameter '{{{inner}}}' in \"{pattern}\" needs a name.\n\
+                     \n\
+                     Example: GET \"/users/{{id}}\" => get_user"
+                ));
+            }
+
+            match inner.split_once(':') {
+                None => Ok(Segment::Param(inner.to_string(), None)),
+                Some((name, constraint)) => {
+                    let constraint = parse_constraint(constraint).map_err(|reason| {
+                        format!(
+                            "Invalid constraint in '{{{inner}}}' of \"{pattern}\": {reason}\n\
+                             \n\
+                             Supported constraints:\n\
+                             - {{id:int}}          64-bit integer, optional leading '-'\n\
+                             - {{id:uuid}}         hyphenated UUID\n\
+                             - {{slug:[a-z0-9-]+}} character class with + or *"
+                        )
+                    })?;
+                    Ok(Segment::Param(name.to_string(), Some(constraint)))
+                },
+            }
+        })
+        .collect()
+}
+
+fn parse_constraint(text: &str) -> Result<Constraint, String> {
+    match text {
+        "int" => return Ok(Constraint::Int),
+        "uuid" => return Ok(Constraint::Uuid),
+        _ => {},
+    }
+
+    let Some(body) = text.strip_prefix('[') else {
+        return Err(format!(
+            "unknown constraint '{text}'.{}",
+            did_you_mean(text, VALID_CONSTRAINTS)
+        ));
+    };
+    let (class, allow_empty) = if let Some(class) = body.strip_suffix("]+") {
+        (class, false)
+    } else if let Some(class) = body.strip_suffix("]*") {
+        (class, true)
+    } else {
+        return Err(format!(
+            "character class '{text}' must end with ']+' or ']*'."
+        ));
+    };
+    let (class, negated) = class
+        .strip_prefix('^')
+        .map_or((class, false), |class| (class, true));
+
+    if !class.is_ascii() {
+        return Err(format!(
+            "character class '{text}' may only contain ASCII characters."
+        ));
+    }
+
+    // Unescape first so that `\-` and `\]` are literal
+    let mut chars: Vec<(u8, bool)> = Vec::new();
+    let mut bytes = class.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'\\' {
+            let escaped = bytes
+                .next()
+                .ok_or_else(|| format!("character class '{text}' ends with '\\'."))?;
+            chars.push((escaped, true));
+        } else {
+            chars.push((byte, false));
+        }
+    }
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, _) = chars[i];
+        if let (Some((b'-', false)), Some(&(end, _))) = (chars.get(i + 1), chars.get(i + 2)) {
+            if end < start {
+                return Err(format!(
+                    "range '{}-{}' in '{text}' is out of order.",
+                    start as char, end as char
+                ));
+            }
+            ranges.push((start, end));
+            i += 3;
+        } else {
+            ranges.push((start, start));
+            i += 1;
+        }
+    }
+
+    if ranges.is_empty() {
+        return Err(format!("character class '{text}' is empty."));
+    }
+
+    Ok(Constraint::Class {
+        source: text.to_string(),
+        ranges,
+        negated,
+        allow_empty,
+    })
+}
+
+impl Constraint {
+    /// Generate a `bool` expression checking the `&str` bound to `segment`.
+    pub fn generate_check(&self, segment: &proc_macro2::Ident) -> TokenStream2 {
+        match self {
+            // Only values the `int64` schema allows, so an overflowing segment
+            // falls through to the next route instead of failing in the handler
+            Self::Int => quote! {
+                !#segment.starts_with('+') && #segment.parse::<i64>().is_ok()
+            },
+            Self::Uuid => quote! {
+                #segment.len() == 36
+                    && #segment.bytes().enumerate().all(|(__mik_i, __mik_b)| match __mik_i {
+                        8 | 13 | 18 | 23 => __mik_b == b'-',
+                        _ => __mik_b.is_ascii_hexdigit(),
+                    })
+            },
+            Self::Class {
+                ranges,
+                negated,
+                allow_empty,
+                ..
+            } => {
+                let arms = ranges.iter().map(|&(start, end)| {
+                    let start_lit = Literal::u8_suffixed(start);
+                    if start == end {
+                        quote! { #start_lit }
+                    } else {
+                        let end_lit = Literal::u8_suffixed(end);
+                        quote! { #start_lit..=#end_lit }
+                    }
+                });
+                let non_empty = if *allow_empty {
+                    quote! {}
+                } else {
+                    quote! { !#segment.is_empty() && }
+                };
+                quote! {
+                    #non_empty #segment.bytes().all(|__mik_b| matches!(__mik_b, #(#arms)|*) != #negated)
+                }
+            },
+        }
+    }
+
+    /// OpenAPI schema JSON describing values accepted by the constraint.
+    pub fn openapi_schema(&self) -> String {
+        match self {
+            Self::Int => r#"{"type":"integer","format":"int64"}"#.to_string(),
+            Self::Uuid => r#"{"type":"string","format":"uuid"}"#.to_string(),
+            Self::Class { source, .. } => format!(
+                r#"{{"type":"string","pattern":"{}"}}"#,
+                escape_json_string(&format!("^{source}$"))
+            ),
+        }
+    }
+}
+
+/// A path parameter as described in OpenAPI.
+pub struct PathParam {
+    pub(crate) name: String,
+    /// OpenAPI schema JSON.
+    pub(crate) schema: String,
+    /// Whether the schema comes from a pattern constraint.
+    pub(crate) constrained: bool,
+}
+
+/// Path parameters of a pattern with their OpenAPI schemas.
+///
+/// Unconstrained and catch-all parameters are plain strings.
+pub fn path_params(segments: &[Segment]) -> Vec<PathParam> {
+    segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Literal(_) => None,
+            Segment::Param(name, None) | Segment::CatchAll(name) => Some(PathParam {
+                name: name.clone(),
+                schema: r#"{"type":"string"}"#.to_string(),
+                constrained: false,
+            }),
+            Segment::Param(name, Some(constraint)) => Some(PathParam {
+                name: name.clone(),
+                schema: constraint.openapi_schema(),
+                constrained: true,
+            }),
+        })
+        .collect()
+}
+
+/// The OpenAPI path template for a pattern (`{id:int}` and `{*rest}` become
+/// `{id}` and `{rest}`).
+pub fn openapi_path(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Literal(text) => text.clone(),
+            Segment::Param(name, _) | Segment::CatchAll(name) => format!("{{{name}}}"),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+#[allow(clippy::literal_string_with_formatting_args)] // Route patterns like "/{id:int}"
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_segments_literal_and_params() {
+        assert_eq!(
+            parse_segments("/users/{id}/posts").unwrap(),
+            vec![
+                Segment::Literal(String::new()),
+                Segment::Literal("users".to_string()),
+                Segment::Param("id".to_string(), None),
+                Segment::Literal("posts".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_segments_keeps_empty_segments() {
+        assert_eq!(parse_segments("").unwrap().len(), 1);
+        assert_eq!(parse_segments("/").unwrap().len(), 2);
+        assert_eq!(parse_segments("/users/").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_segments_partial_braces_are_literal() {
+        assert_eq!(
+            parse_segments("file.{ext}").unwrap()[0],
+            Segment::Literal("file.{ext}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_named_constraints() {
+        let segments = parse_segments("/{a:int}/{b:uuid}").unwrap();
+        assert_eq!(
+            segments[1],
+            Segment::Param("a".to_string(), Some(Constraint::Int))
+        );
+        assert_eq!(
+            segments[2],
+            Segment::Param("b".to_string(), Some(Constraint::Uuid))
+        );
+    }
+
+    #[test]
+    fn test_parse_class_constraint() {
+        let segments = parse_segments("/{slug:[a-z0-9\\--]*}").unwrap();
+        let Segment::Param(
+            name,
+            Some(Constraint::Class {
+                ranges,
+                negated,
+                allow_empty,
+                ..
+            }),
+        ) = &segments[1]
+        else {
+            panic!("expected class constraint");
+        };
+        assert_eq!(name, "slug");
+        assert_eq!(
+            ranges,
+            &[(b'a', b'z'), (b'0', b'9'), (b'-', b'-'), (b'-', b'-')]
+        );
+        assert!(!negated);
+        assert!(allow_empty);
+
+        let segments = parse_segments("/{name:[^.]+}").unwrap();
+        assert!(matches!(
+            &segments[1],
+            Segment::Param(
+                _,
+                Some(Constraint::Class {
+                    negated: true,
+                    allow_empty: false,
+                    ..
+                })
+            )
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_constraints() {
+        let err = parse_segments("/{id:itn}").unwrap_err();
+        assert!(err.contains("Did you mean 'int'"), "{err}");
+        assert!(parse_segments("/{id:[a-z]}").is_err());
+        assert!(parse_segments("/{id:[]+}").is_err());
+        assert!(parse_segments("/{id:[z-a]+}").is_err());
+    }
+
+    #[test]
+    fn test_parse_unnamed_param() {
+        let err = parse_segments("/users/{}").unwrap_err();
+        assert!(err.contains("needs a name"), "{err}");
+        assert!(parse_segments("/users/{:int}").is_err());
+    }
+
+    #[test]
+    fn test_parse_catch_all() {
+        assert_eq!(
+            parse_segments("/files/{*key}").unwrap()[2],
+            Segment::CatchAll("key".to_string())
+        );
+        assert!(parse_segments("/files/{*key}/meta").is_err());
+        assert!(parse_segments("/files/{*}").is_err());
+    }
+
+    #[test]
+    fn test_openapi_path_strips_constraints() {
+        let segments = parse_segments("/users/{id:int}/files/{*path}").unwrap();
+        assert_eq!(openapi_path(&segments), "/users/{id}/files/{path}");
+    }
+
+    #[test]
+    fn test_class_openapi_schema_is_escaped() {
+        let segments = parse_segments("/{v:[a-z\\-]+}").unwrap();
+        let params = path_params(&segments);
+        assert_eq!(
+            params[0].schema,
+            r#"{"type":"string","pattern":"^[a-z\\-]+$"}"#
+        );
+        assert!(params[0].constrained);
+    }
+}
@@ -0,0 +1,412 @@
+//! Compile-time segment trie for route dispatch.
+//!
+//! Every route pattern is split on `/` and inserted into a trie when the
+//! `routes!` macro expands. The generated dispatcher then walks the request path
+//! exactly once:
+//!
+//! - literal children are tried first (a `match` on the segment text),
+//! - constrained `{param:...}` children are tried next, in declaration order,
+//!   then the plain `{param}` child, so `/users/me` wins over `/users/{id:int}`,
+//!   which wins over `/users/{name}`, regardless of declaration order,
+//! - a `{*rest}` catch-all is tried last and captures the remaining path,
+//! - a branch that dead-ends falls through to its siblings (backtracking).
+//!
+//! Path parameters are kept as borrowed `&str` slices during the walk and are
+//! only decoded into a `HashMap` once a leaf with a matching method is reached.
+//! Leaves whose path matches but whose method does not record their methods in
+//! an allowed-methods bitmask, which drives the 405 `Allow` header and the
+//! automatic `OPTIONS` response.
+
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+
+use super::pattern::{Constraint, Segment, parse_segments};
+use super::types::{HttpMethod, RouteDef};
+
+// =============================================================================
+// TRIE CONSTRUCTION
+// =============================================================================
+
+/// A route reachable at a trie node.
+pub struct Leaf {
+    /// Index of the route in `RoutesDef::routes`.
+    pub(crate) route: usize,
+    pub(crate) method: HttpMethod,
+    /// The method's bit in the allowed-methods mask.
+    pub(crate) bit: u64,
+    /// Parameter names with the generated variable holding their raw value.
+    pub(crate) params: Vec<(String, Ident)>,
+}
+
+/// A node in the segment trie.
+#[derive(Default)]
+pub struct TrieNode {
+    /// Literal children in declaration order.
+    pub(crate) literals: Vec<(String, Self)>,
+    /// Parameter children, one per distinct constraint (names only matter at
+    /// the leaves).
+    pub(crate) params: Vec<(Option<Constraint>, Self)>,
+    /// Child for a trailing `{*rest}` segment; only has leaves.
+    pub(crate) catch_all: Option<Box<Self>>,
+    /// Routes whose pattern ends at this node.
+    pub(crate) leaves: Vec<Leaf>,
+}
+
+impl TrieNode {
+    fn insert(&mut self, segments: &[Segment], leaf: Leaf) {
+        match segments.split_first() {
+            None => self.leaves.push(leaf),
+            Some((Segment::Literal(text), rest)) => {
+                let index = if let Some(index) = self.literals.iter().position(|(t, _)| t == text) {
+                    index
+                } else {
+                    self.literals.push((text.clone(), Self::default()));
+                    self.literals.len() - 1
+                };
+                self.literals[index].1.insert(rest, leaf);
+            },
+            Some((Segment::Param(_, constraint), rest)) => {
+                let index =
+                    if let Some(index) = self.params.iter().position(|(c, _)| c == constraint) {
+                        index
+                    } else {
+                        self.params.push((constraint.clone(), Self::default()));
+                        self.params.len() - 1
+                    };
+                self.params[index].1.insert(rest, leaf);
+            },
+            Some((Segment::CatchAll(_), _)) => {
+                self.catch_all
+                    .get_or_insert_with(Box::default)
+                    .leaves
+                    .push(leaf);
+            },
+        }
+    }
+}
+
+/// Build the dispatch trie for all routes (including alternative patterns).
+pub fn build_trie(routes: &[RouteDef]) -> TrieNode {
+    let mut root = TrieNode::default();
+    let custom = HttpMethod::custom_methods(routes);
+
+    for (index, route) in routes.iter().enumerate() {
+        for pattern in &route.patterns {
+            // Patterns are validated when the route is parsed
+            let segments = parse_segments(pattern).unwrap_or_default();
+            let params = segments
+                .iter()
+                .enumerate()
+                .filter_map(|(depth, segment)| match segment {
+                    Segment::Param(name, _) => Some((name.clone(), segment_ident(depth))),
+                    Segment::CatchAll(name) => Some((name.clone(), tail_ident(depth))),
+                    Segment::Literal(_) => None,
+                })
+                .collect();
+            root.insert(
+                &segments,
+                Leaf {
+                    route: index,
+                    method: route.method.clone(),
+                    bit: route.method.bit(&custom),
+                    params,
+                },
+            );
+        }
+    }
+
+    root
+}
+
+// =============================================================================
+// CODE GENERATION
+// =============================================================================
+
+fn segment_ident(depth: usize) -> Ident {
+    format_ident!("__mik_seg_{}", depth)
+}
+
+fn tail_ident(depth: usize) -> Ident {
+    format_ident!("__mik_tail_{}", depth)
+}
+
+/// Generate the trie walk.
+///
+/// The generated expression evaluates to
+/// `Option<(usize, HashMap<String, String>)>`: the index of the matched route
+/// and its decoded path parameters. Expects `__mik_path: &str`,
+/// `__mik_method: mik_sdk::Method` and a mutable `__mik_allowed: u64` in scope;
+/// the latter collects [`HttpMethod::bit`]s of every route whose path matched.
+pub fn generate_trie_walk(root: &TrieNode) -> TokenStream2 {
+    let body = generate_node(root, 0);
+
+    quote! {
+        '__mik_walk: {
+            let __mik_rest: ::std::option::Option<&str> = ::std::option::Option::Some(__mik_path);
+            #body
+            ::std::option::Option::None
+        }
+    }
+}
+
+fn generate_node(node: &TrieNode, depth: usize) -> TokenStream2 {
+    let leaves = generate_leaves(&node.leaves);
+
+    if node.literals.is_empty() && node.params.is_empty() && node.catch_all.is_none() {
+        return quote! {
+            if __mik_rest.is_none() {
+                #leaves
+            }
+        };
+    }
+
+    let segment = segment_ident(depth);
+
+    let literal_match = if node.literals.is_empty() {
+        quote! {}
+    } else {
+        let arms = node.literals.iter().map(|(text, child)| {
+            let child_code = generate_node(child, depth + 1);
+            quote! { #text => { #child_code } }
+        });
+        quote! {
+            match #segment {
+                #(#arms)*
+                _ => {}
+            }
+        }
+    };
+
+    // Constrained params first (declaration order), the plain param last
+    let constrained = node.params.iter().filter(|(c, _)| c.is_some());
+    let plain = node.params.iter().filter(|(c, _)| c.is_none());
+    let param_code = constrained.chain(plain).map(|(constraint, child)| {
+        let child_code = generate_node(child, depth + 1);
+        match constraint {
+            Some(constraint) => {
+                let check = constraint.generate_check(&segment);
+                quote! {
+                    if #check {
+                        #child_code
+                    }
+                }
+            },
+            None => child_code,
+        }
+    });
+
+    let segment_code = if node.literals.is_empty() && node.params.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let (#segment, __mik_rest) = match __mik_remaining.split_once('/') {
+                ::std::option::Option::Some((__mik_head, __mik_tail)) => {
+                    (__mik_head, ::std::option::Option::Some(__mik_tail))
+                }
+                ::std::option::Option::None => (__mik_remaining, ::std::option::Option::None),
+            };
+            #literal_match
+            #(#param_code)*
+        }
+    };
+
+    let catch_all_code = node.catch_all.as_ref().map(|child| {
+        let tail = tail_ident(depth);
+        let tail_leaves = generate_leaves(&child.leaves);
+        quote! {
+            let #tail = __mik_remaining;
+            #tail_leaves
+        }
+    });
+
+    quote! {
+        match __mik_rest {
+            ::std::option::Option::None => {
+                #leaves
+            }
+            ::std::option::Option::Some(__mik_remaining) => {
+                { #segment_code }
+                #catch_all_code
+            }
+        }
+    }
+}
+
+fn generate_params(leaf: &Leaf) -> TokenStream2 {
+    if leaf.params.is_empty() {
+        return quote! { ::std::collections::HashMap::new() };
+    }
+
+    let capacity = leaf.params.len();
+    let inserts = leaf.params.iter().map(|(name, segment)| {
+        quote! {
+            // URL decode the path parameter. If decoding fails (malformed percent-encoding),
+            // fall back to the raw value. This is intentional: invalid encoding shouldn't
+            // crash the handler, and the raw value will either match the route or not.
+            __mik_params.insert(
+                #name.to_string(),
+                mik_sdk::url_decode(#segment).unwrap_or_else(|_| #segment.to_string()),
+            );
+        }
+    });
+    quote! {
+        {
+            let mut __mik_params = ::std::collections::HashMap::with_capacity(#capacity);
+            #(#inserts)*
+            __mik_params
+        }
+    }
+}
+
+/// Generate the method checks for the routes ending at a node.
+///
+/// Besides the declared methods, every path answers `OPTIONS` (handled by the
+/// caller once the walk finds no route) and every `GET` route also answers
+/// `HEAD`, unless an explicit route for that method exists.
+fn generate_leaves(leaves: &[Leaf]) -> TokenStream2 {
+    if leaves.is_empty() {
+        return quote! {};
+    }
+
+    let checks = leaves.iter().map(|leaf| {
+        let method_check = leaf.method.to_method_check();
+        let route = leaf.route;
+        let params = generate_params(leaf);
+
+        quote! {
+            if #method_check {
+                break '__mik_walk ::std::option::Option::Some((#route, #params));
+            }
+        }
+    });
+
+    // Implicit HEAD: run the GET handler, the body is stripped by the caller
+    let has_head = leaves
+        .iter()
+        .any(|leaf| matches!(leaf.method, HttpMethod::Head));
+    let implicit_head = leaves
+        .iter()
+        .find(|leaf| matches!(leaf.method, HttpMethod::Get))
+        .filter(|_| !has_head)
+        .map(|leaf| {
+            let route = leaf.route;
+            let params = generate_params(leaf);
+            quote! {
+                if __mik_method == mik_sdk::Method::Head {
+                    break '__mik_walk ::std::option::Option::Some((#route, #params));
+                }
+            }
+        });
+
+    let mut mask = leaves
+        .iter()
+        .fold(HttpMethod::Options.bit(&[]), |mask, leaf| mask | leaf.bit);
+    if implicit_head.is_some() {
+        mask |= HttpMethod::Head.bit(&[]);
+    }
+
+    quote! {
+        #(#checks)*
+        #implicit_head
+        __mik_allowed |= #mask;
+    }
+}
+
+/// Generate the `Allow` header value for the collected `__mik_allowed` mask.
+///
+/// The generated expression evaluates to a `String` such as
+/// `"GET, HEAD, DELETE, OPTIONS"`; custom methods (see
+/// [`HttpMethod::custom_methods`]) are listed after the standard ones.
+pub fn generate_allow_header(custom: &[HttpMethod]) -> TokenStream2 {
+    let pushes = HttpMethod::ALL.iter().chain(custom).map(|method| {
+        let bit = method.bit(custom);
+        let name = method.wire_name();
+        quote! {
+            if __mik_allowed & #bit != 0 {
+                if !__mik_allow.is_empty() {
+                    __mik_allow.push_str(", ");
+                }
+                __mik_allow.push_str(#name);
+            }
+        }
+    });
+
+    quote! {
+        {
+            let mut __mik_allow = ::std::string::String::new();
+            #(#pushes)*
+            __mik_allow
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::literal_string_with_formatting_args)] // Route patterns like "/{id:int}"
+mod tests {
+    use super::*;
+
+    fn trie(patterns: &[&str]) -> TrieNode {
+        let mut root = TrieNode::default();
+        for (route, pattern) in patterns.iter().enumerate() {
+            root.insert(
+                &parse_segments(pattern).unwrap(),
+                Leaf {
+                    route,
+                    method: HttpMethod::Get,
+                    bit: HttpMethod::Get.bit(&[]),
+                    params: Vec::new(),
+                },
+            );
+        }
+        root
+    }
+
+    #[test]
+    fn test_insert_shares_literal_prefixes() {
+        let root = trie(&["/users", "/users/{id}", "/users/me"]);
+
+        // Root has a single "" child, which has a single "users" child
+        assert_eq!(root.literals.len(), 1);
+        let users = &root.literals[0].1.literals[0].1;
+        assert_eq!(users.leaves.len(), 1);
+        assert_eq!(users.literals.len(), 1);
+        assert_eq!(users.literals[0].0, "me");
+        assert_eq!(users.params.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_merges_param_names_at_same_depth() {
+        let root = trie(&["/a/{x}", "/a/{y}/b"]);
+
+        let a = &root.literals[0].1.literals[0].1;
+        assert_eq!(a.params.len(), 1);
+        let param = &a.params[0].1;
+        assert_eq!(param.leaves.len(), 1);
+        assert_eq!(param.literals.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_separates_params_by_constraint() {
+        let root = trie(&["/a/{x}", "/a/{y:int}", "/a/{z:int}/b", "/a/{*rest}"]);
+
+        let a = &root.literals[0].1.literals[0].1;
+        assert_eq!(a.params.len(), 2);
+        assert_eq!(a.params[0].0, None);
+        assert_eq!(a.params[1].0, Some(Constraint::Int));
+        assert_eq!(a.params[1].1.leaves.len(), 1);
+        assert_eq!(a.params[1].1.literals.len(), 1);
+        assert_eq!(a.catch_all.as_ref().unwrap().leaves.len(), 1);
+    }
+
+    #[test]
+    fn test_custom_method_bits_follow_standard_methods() {
+        let custom = [
+            HttpMethod::Custom("PROPFIND".to_string()),
+            HttpMethod::Custom("PURGE".to_string()),
+        ];
+        assert_eq!(HttpMethod::Get.bit(&custom), 1);
+        assert_eq!(HttpMethod::Trace.bit(&custom), 1 << 7);
+        assert_eq!(custom[0].bit(&custom), 1 << 8);
+        assert_eq!(custom[1].bit(&custom), 1 << 9);
+    }
+}
@@ -7,17 +7,27 @@ use syn::{
     parse::{Parse, ParseStream},
 };
 
-use crate::constants::VALID_HTTP_METHODS;
+use super::cors::CorsDef;
+use super::pattern::{Segment, parse_segments};
+use crate::constants::VALID_ROUTE_METHODS;
 use crate::errors::did_you_mean;
 
 /// Valid input sources for route handlers.
-const VALID_INPUT_SOURCES: &[&str] = &["path", "body", "query"];
+const VALID_INPUT_SOURCES: &[&str] = &[
+    "path",
+    "body",
+    "form",
+    "multipart",
+    "query",
+    "headers",
+    "cookies",
+];
 
 // =============================================================================
 // TYPES
 // =============================================================================
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum HttpMethod {
     Get,
     Post,
@@ -26,10 +36,15 @@ pub enum HttpMethod {
     Delete,
     Head,
     Options,
+    Trace,
+    /// Extension method token, case preserved (e.g. `PROPFIND`)
+    Custom(String),
 }
 
 impl HttpMethod {
-    pub(crate) const fn as_str(&self) -> &'static str {
+    /// Lowercase name for standard methods (the `OpenAPI` key), the token for
+    /// custom ones.
+    pub(crate) fn as_str(&self) -> &str {
         match self {
             Self::Get => "get",
             Self::Post => "post",
@@ -38,28 +53,93 @@ impl HttpMethod {
             Self::Delete => "delete",
             Self::Head => "head",
             Self::Options => "options",
+            Self::Trace => "trace",
+            Self::Custom(name) => name,
         }
     }
 
-    pub(crate) fn to_method_check(&self) -> TokenStream2 {
+    /// Name as sent on the wire and listed in an `Allow` header.
+    pub(crate) fn wire_name(&self) -> String {
         match self {
-            Self::Get => quote! { mik_sdk::Method::Get },
-            Self::Post => quote! { mik_sdk::Method::Post },
-            Self::Put => quote! { mik_sdk::Method::Put },
-            Self::Patch => quote! { mik_sdk::Method::Patch },
-            Self::Delete => quote! { mik_sdk::Method::Delete },
-            Self::Head => quote! { mik_sdk::Method::Head },
-            Self::Options => quote! { mik_sdk::Method::Options },
+            Self::Custom(name) => name.clone(),
+            standard => standard.as_str().to_uppercase(),
+        }
+    }
+
+    /// Standard methods, in the order they are listed in an `Allow` header.
+    pub(crate) const ALL: [Self; 8] = [
+        Self::Get,
+        Self::Head,
+        Self::Post,
+        Self::Put,
+        Self::Patch,
+        Self::Delete,
+        Self::Options,
+        Self::Trace,
+    ];
+
+    /// Custom methods used by `routes`, in declaration order, without repeats.
+    pub(crate) fn custom_methods(routes: &[RouteDef]) -> Vec<Self> {
+        let mut custom: Vec<Self> = Vec::new();
+        for route in routes {
+            if matches!(route.method, Self::Custom(_)) && !custom.contains(&route.method) {
+                custom.push(route.method.clone());
+            }
         }
+        custom
+    }
+
+    /// Bit recording this method in a generated allowed-methods mask.
+    ///
+    /// Standard methods take the low bits in [`Self::ALL`] order; custom
+    /// methods follow in the order of `custom` (see [`Self::custom_methods`]).
+    pub(crate) fn bit(&self, custom: &[Self]) -> u64 {
+        let index = Self::ALL
+            .iter()
+            .chain(custom)
+            .position(|method| method == self)
+            .unwrap_or_default();
+        1 << index
+    }
+
+    /// Boolean expression testing `__mik_method` against this method.
+    pub(crate) fn to_method_check(&self) -> TokenStream2 {
+        let variant = match self {
+            Self::Get => quote! { Get },
+            Self::Post => quote! { Post },
+            Self::Put => quote! { Put },
+            Self::Patch => quote! { Patch },
+            Self::Delete => quote! { Delete },
+            Self::Head => quote! { Head },
+            Self::Options => quote! { Options },
+            Self::Trace => quote! { Trace },
+            Self::Custom(name) => {
+                return quote! {
+                    matches!(&__mik_method, mik_sdk::Method::Other(__mik_name) if __mik_name == #name)
+                };
+            },
+        };
+        quote! { __mik_method == mik_sdk::Method::#variant }
     }
 }
 
 /// Input source for typed parameters
 #[derive(Clone)]
 pub enum InputSource {
-    Path,  // from URL path params
-    Body,  // from JSON body
-    Query, // from query string
+    Path,      // from URL path params
+    Body,      // from JSON body
+    Form,      // from form-urlencoded body
+    Multipart, // from multipart/form-data body
+    Query,     // from query string
+    Headers,   // from request headers
+    Cookies,   // from the Cookie header
+}
+
+/// Entity tag mode from `#[etag]` / `#[etag(weak)]`
+#[derive(Clone, Copy)]
+pub enum EtagMode {
+    Strong,
+    Weak,
 }
 
 /// A typed input parameter for a handler
@@ -76,6 +156,8 @@ pub struct RouteDef {
     pub(crate) handler: Ident,
     pub(crate) inputs: Vec<TypedInput>,
     pub(crate) output_type: Option<Ident>,
+    /// The handler returns `Vec<output_type>`
+    pub(crate) output_list: bool,
     /// Operation summary from doc comment
     pub(crate) summary: Option<String>,
     /// Tag override from #[tag = "..."] attribute
@@ -84,6 +166,12 @@ pub struct RouteDef {
     pub(crate) deprecated: bool,
     /// HTTP status code for success response (default: 200)
     pub(crate) status_code: u16,
+    /// Tag responses with an `ETag` and answer fresh requests with 304
+    pub(crate) etag: Option<EtagMode>,
+    /// `before:` hooks in effect for this route, outermost block first
+    pub(crate) before: Vec<syn::Path>,
+    /// `after:` hooks in effect for this route, innermost block first
+    pub(crate) after: Vec<syn::Path>,
 }
 
 /// All routes in the macro
@@ -91,6 +179,8 @@ pub struct RoutesDef {
     pub(crate) routes: Vec<RouteDef>,
     /// Global tag for all routes (from #[tag = "..."] at top of block)
     pub(crate) default_tag: Option<String>,
+    /// CORS policy from a top-level `cors: { ... }` section
+    pub(crate) cors: Option<CorsDef>,
 }
 
 impl RouteDef {
@@ -136,40 +226,302 @@ impl Parse for RoutesDef {
         let mut routes = Vec::new();
         let mut default_tag = None;
 
-        // Check for global #[tag = "..."] at the start
-        while input.peek(Token![#]) {
-            let attrs: Vec<Attribute> = input.call(Attribute::parse_outer)?;
-            for attr in attrs {
-                if attr.path().is_ident("tag") {
-                    let value: LitStr = attr.parse_args()?;
-                    default_tag = Some(value.value());
-                }
-            }
+        // Global #[tag = "..."] at the start; other attributes belong to the first route
+        while peek_tag_attr(input) {
+            let pound_token = input.parse()?;
+            let content;
+            let bracket_token = syn::bracketed!(content in input);
+            let attr = Attribute {
+                pound_token,
+                style: syn::AttrStyle::Outer,
+                bracket_token,
+                meta: content.parse()?,
+            };
+            default_tag = Some(parse_tag(&attr)?);
         }
 
-        while !input.is_empty() {
-            let route = parse_route(input)?;
-            routes.push(route);
-
-            if input.peek(Token![,]) {
-                input.parse::<Token![,]>()?;
-            }
-        }
+        let mut cors = None;
+        parse_block(input, &Scope::default(), &mut routes, &mut cors)?;
 
         Ok(Self {
             routes,
             default_tag,
+            cors,
         })
     }
 }
 
+/// Settings a block passes down to its routes and nested groups.
+#[derive(Clone, Default)]
+struct Scope {
+    /// Pattern prefix from enclosing `group "/prefix"` blocks
+    prefix: String,
+    /// Tag from the innermost `group ... #[tag = "..."]`
+    tag: Option<String>,
+    /// Set when an enclosing group is `#[deprecated]`
+    deprecated: bool,
+    /// `before:` hooks, outermost block first
+    before: Vec<syn::Path>,
+    /// `after:` hooks, innermost block first
+    after: Vec<syn::Path>,
+    /// Set inside `group { ... }` blocks
+    nested: bool,
+}
+
+impl Scope {
+    /// Apply the scope to a route parsed inside it.
+    fn apply(&self, mut route: RouteDef) -> RouteDef {
+        if !self.prefix.is_empty() {
+            route.patterns = route
+                .patterns
+                .iter()
+                .map(|pattern| format!("{}{pattern}", self.prefix))
+                .collect();
+        }
+        if route.tag_override.is_none() {
+            route.tag_override.clone_from(&self.tag);
+        }
+        route.deprecated |= self.deprecated;
+        route.before.clone_from(&self.before);
+        route.after.clone_from(&self.after);
+        route
+    }
+}
+
+/// Parse the contents of the top-level block or a `group { ... }`.
+///
+/// A block starts with optional `before:` / `after:` hook lists, followed by
+/// routes and nested groups. Hooks nest like an onion: the outer `before`
+/// hooks run first, the outer `after` hooks run last. The top-level block may
+/// also declare a `cors: { ... }` policy.
+fn parse_block(
+    input: ParseStream<'_>,
+    inherited: &Scope,
+    routes: &mut Vec<RouteDef>,
+    cors: &mut Option<CorsDef>,
+) -> Result<()> {
+    let mut scope = inherited.clone();
+    let mut own_after = Vec::new();
+    let mut seen_route = false;
+
+    while !input.is_empty() {
+        let keyword = input.fork().parse::<Ident>().ok();
+
+        match keyword {
+            Some(key) if (key == "before" || key == "after") && input.peek2(Token![:]) => {
+                if seen_route {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!(
+                            "`{key}:` hooks must be declared before the routes of their block.\n\
+                             \n\
+                             Example:\n\
+                             routes! {{\n\
+                                 before: [require_auth],\n\
+                                 after: [add_server_header],\n\
+                                 GET \"/users\" => list_users,\n\
+                             }}"
+                        ),
+                    ));
+                }
+                input.parse::<Ident>()?;
+                input.parse::<Token![:]>()?;
+                let paths = parse_hook_list(input)?;
+                if key == "before" {
+                    scope.before.extend(paths);
+                } else {
+                    own_after.extend(paths);
+                    scope.after = own_after.iter().chain(&inherited.after).cloned().collect();
+                }
+            },
+            Some(key) if key == "cors" && input.peek2(Token![:]) => {
+                if scope.nested || seen_route || cors.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        "`cors:` must be declared once, at the top of `routes!`, before any routes.\n\
+                         \n\
+                         Example:\n\
+                         routes! {\n\
+                             cors: { origins: [\"https://app.example.com\"], credentials: true },\n\
+                             GET \"/users\" => list_users,\n\
+                         }",
+                    ));
+                }
+                input.parse::<Ident>()?;
+                input.parse::<Token![:]>()?;
+                *cors = Some(input.parse()?);
+            },
+            Some(key) if key == "group" => {
+                input.parse::<Ident>()?;
+                let group_scope = parse_group_header(input, &scope)?;
+                let content;
+                syn::braced!(content in input);
+                parse_block(&content, &group_scope, routes, cors)?;
+                seen_route = true;
+            },
+            _ => {
+                routes.push(scope.apply(parse_route(input)?));
+                seen_route = true;
+            },
+        }
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse the optional `"/prefix"` and `#[tag = "..."]` / `#[deprecated]`
+/// attributes between `group` and its body.
+fn parse_group_header(input: ParseStream<'_>, scope: &Scope) -> Result<Scope> {
+    let mut group_scope = scope.clone();
+    group_scope.nested = true;
+
+    if input.peek(LitStr) {
+        let prefix: LitStr = input.parse()?;
+        let value = prefix.value();
+        let invalid = |msg: String| Err(syn::Error::new(prefix.span(), msg));
+
+        if !value.starts_with('/') {
+            return invalid(format!(
+                "Group prefix \"{value}\" must start with '/'. Did you mean \"/{value}\"?\n\
+                 \n\
+                 Example: group \"/admin\" {{ GET \"/users\" => list_users }}"
+            ));
+        }
+        match parse_segments(&value) {
+            Err(msg) => return invalid(msg),
+            Ok(segments) if segments.iter().any(|s| matches!(s, Segment::CatchAll(_))) => {
+                return invalid(format!(
+                    "Group prefix \"{value}\" cannot contain a catch-all parameter.\n\
+                     \n\
+                     Catch-all parameters must be the last segment of a route pattern."
+                ));
+            },
+            Ok(_) => {},
+        }
+
+        group_scope.prefix = format!("{}{}", scope.prefix, value.trim_end_matches('/'));
+    }
+
+    for attr in input.call(Attribute::parse_outer)? {
+        if attr.path().is_ident("tag") {
+            group_scope.tag = Some(parse_tag(&attr)?);
+        } else if attr.path().is_ident("deprecated") {
+            group_scope.deprecated = true;
+        } else {
+            let name = attr
+                .path()
+                .get_ident()
+                .map_or_else(String::new, ToString::to_string);
+            return Err(syn::Error::new_spanned(
+                &attr,
+                format!(
+                    "Unknown group attribute '{name}'. Groups support #[tag = \"...\"] and #[deprecated].{}",
+                    did_you_mean(&name, &["tag", "deprecated"])
+                ),
+            ));
+        }
+    }
+
+    Ok(group_scope)
+}
+
+/// Whether the next token is a `#[tag ...]` attribute.
+fn peek_tag_attr(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    let is_tag = || -> Result<bool> {
+        fork.parse::<Token![#]>()?;
+        let content;
+        syn::bracketed!(content in fork);
+        Ok(content.parse::<syn::Path>()?.is_ident("tag"))
+    };
+    is_tag().unwrap_or(false)
+}
+
+/// Parse `#[tag = "..."]` or `#[tag("...")]`.
+fn parse_tag(attr: &Attribute) -> Result<String> {
+    if let syn::Meta::NameValue(meta) = &attr.meta {
+        if let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        }) = &meta.value
+        {
+            return Ok(value.value());
+        }
+        return Err(syn::Error::new_spanned(
+            &meta.value,
+            "tag must be a string literal: #[tag = \"Users\"]",
+        ));
+    }
+    let value: LitStr = attr.parse_args()?;
+    Ok(value.value())
+}
+
+/// Parse `hook` or `[hook_a, hook_b]`.
+fn parse_hook_list(input: ParseStream<'_>) -> Result<Vec<syn::Path>> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let paths = content.parse_terminated(syn::Path::parse, Token![,])?;
+        Ok(paths.into_iter().collect())
+    } else {
+        Ok(vec![input.parse()?])
+    }
+}
+
+/// Map an uppercase standard method name to its [`HttpMethod`].
+fn standard_method(name: &str) -> Option<HttpMethod> {
+    Some(match name {
+        "GET" => HttpMethod::Get,
+        "POST" => HttpMethod::Post,
+        "PUT" => HttpMethod::Put,
+        "PATCH" => HttpMethod::Patch,
+        "DELETE" => HttpMethod::Delete,
+        "HEAD" => HttpMethod::Head,
+        "OPTIONS" => HttpMethod::Options,
+        "TRACE" => HttpMethod::Trace,
+        _ => return None,
+    })
+}
+
+/// Parse an extension method written as a string literal.
+///
+/// The value must be an HTTP token (RFC 9110 `tchar`s). Method names are
+/// case-sensitive, so only exact standard names map to standard methods.
+pub fn parse_custom_method(token: &LitStr) -> Result<HttpMethod> {
+    let name = token.value();
+    let is_tchar = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+    if name.is_empty() || !name.chars().all(is_tchar) {
+        let hint = if name.starts_with('/') {
+            format!(
+                "Expected HTTP method before route path \"{name}\".\n\
+                 \n\
+                 Correct syntax: GET \"{name}\" => handler"
+            )
+        } else {
+            format!(
+                "Invalid HTTP method \"{name}\": method names are tokens without spaces \
+                 or separators.\n\
+                 \n\
+                 Example: \"PROPFIND\" \"/files/{{*path}}\" => propfind"
+            )
+        };
+        return Err(syn::Error::new_spanned(token, hint));
+    }
+    Ok(standard_method(&name).unwrap_or(HttpMethod::Custom(name)))
+}
+
 #[allow(clippy::too_many_lines)] // Complex route parsing with many input variants
 fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
-    // Parse doc comments (/// ...) and attributes (#[tag = "..."], #[deprecated], #[status(code)]) before the route
+    // Parse doc comments (/// ...) and attributes (#[tag = "..."], #[deprecated], #[status(code)], #[etag]) before the route
     let mut summary = None;
     let mut tag_override = None;
     let mut deprecated = false;
     let mut status_code: u16 = 200; // Default status code
+    let mut etag = None;
 
     // Parse outer attributes (doc comments become #[doc = "..."])
     let attrs: Vec<Attribute> = input.call(Attribute::parse_outer)?;
@@ -192,10 +544,11 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
                 }
             }
         } else if attr.path().is_ident("tag") {
-            let value: LitStr = attr.parse_args()?;
-            tag_override = Some(value.value());
+            tag_override = Some(parse_tag(&attr)?);
         } else if attr.path().is_ident("deprecated") {
             deprecated = true;
+        } else if attr.path().is_ident("etag") {
+            etag = Some(parse_etag(&attr)?);
         } else if attr.path().is_ident("status") {
             let code: syn::LitInt = attr.parse_args()?;
             status_code = code.base10_parse().map_err(|_| {
@@ -221,48 +574,50 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
         }
     }
 
-    // Parse method: GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS
-    let method_ident: Ident = input.parse().map_err(|e| {
-        syn::Error::new(
-            e.span(),
-            format!(
-                "Expected HTTP method at start of route definition.\n\
-                 \n\
-                 Valid methods: GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS\n\
-                 \n\
-                 Example:\n\
-                 routes! {{\n\
-                     GET \"/users\" => list_users,\n\
-                     POST \"/users\" => create_user(body: CreateUser) -> User,\n\
-                 }}\n\
-                 \n\
-                 Original error: {e}"
-            ),
+    // Parse method: GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS, TRACE, or a
+    // string literal for extension methods ("PROPFIND")
+    let (method, method_str) = if input.peek(LitStr) {
+        let token: LitStr = input.parse()?;
+        (
+            parse_custom_method(&token)?,
+            format!("\"{}\"", token.value()),
         )
-    })?;
+    } else {
+        let method_ident: Ident = input.parse().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                format!(
+                    "Expected HTTP method at start of route definition.\n\
+                     \n\
+                     Valid methods: GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS, TRACE\n\
+                     \n\
+                     Example:\n\
+                     routes! {{\n\
+                         GET \"/users\" => list_users,\n\
+                         POST \"/users\" => create_user(body: CreateUser) -> User,\n\
+                     }}\n\
+                     \n\
+                     Original error: {e}"
+                ),
+            )
+        })?;
 
-    let method_str = method_ident.to_string().to_uppercase();
-    let method = match method_str.as_str() {
-        "GET" => HttpMethod::Get,
-        "POST" => HttpMethod::Post,
-        "PUT" => HttpMethod::Put,
-        "PATCH" => HttpMethod::Patch,
-        "DELETE" => HttpMethod::Delete,
-        "HEAD" => HttpMethod::Head,
-        "OPTIONS" => HttpMethod::Options,
-        other => {
-            let suggestion = did_you_mean(other, VALID_HTTP_METHODS);
+        let method_str = method_ident.to_string().to_uppercase();
+        let Some(method) = standard_method(&method_str) else {
+            let suggestion = did_you_mean(&method_str, VALID_ROUTE_METHODS);
             return Err(syn::Error::new_spanned(
                 &method_ident,
                 format!(
                     "Invalid HTTP method '{method_ident}'.{suggestion}\n\
                      \n\
-                     Valid methods: GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS\n\
+                     Valid methods: GET, POST, PUT, PATCH, DELETE, HEAD, OPTIONS, TRACE\n\
+                     Other methods are written as string literals: \"PROPFIND\" \"/files\" => handler\n\
                      \n\
                      Example: GET \"/users\" => list_users"
                 ),
             ));
-        },
+        };
+        (method, method_str)
     };
 
     // Parse pattern(s): "/path" or "/path" | "/other"
@@ -283,6 +638,7 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
             ),
         )
     })?;
+    validate_pattern(&first_pattern)?;
     patterns.push(first_pattern.value());
 
     while input.peek(Token![|]) {
@@ -299,6 +655,7 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
                 ),
             )
         })?;
+        validate_pattern(&alt_pattern)?;
         patterns.push(alt_pattern.value());
     }
 
@@ -373,10 +730,10 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
     };
 
     // Parse optional output type: -> User or -> Vec<User>
+    let mut output_list = false;
     let output_type = if input.peek(Token![->]) {
         input.parse::<Token![->]>()?;
-        // For now just parse as Ident, handle Vec<T> later if needed
-        let type_ident: Ident = input.parse().map_err(|e| {
+        let mut type_ident: Ident = input.parse().map_err(|e| {
             syn::Error::new(
                 e.span(),
                 format!(
@@ -403,6 +760,12 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
                 ),
             )
         })?;
+        if type_ident == "Vec" && input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            type_ident = input.parse()?;
+            input.parse::<Token![>]>()?;
+            output_list = true;
+        }
         Some(type_ident)
     } else {
         None
@@ -414,13 +777,51 @@ fn parse_route(input: ParseStream<'_>) -> Result<RouteDef> {
         handler,
         inputs,
         output_type,
+        output_list,
         summary,
         tag_override,
         deprecated,
         status_code,
+        etag,
+        before: Vec::new(),
+        after: Vec::new(),
     })
 }
 
+/// Parse `#[etag]` or `#[etag(weak)]`.
+fn parse_etag(attr: &Attribute) -> Result<EtagMode> {
+    match &attr.meta {
+        syn::Meta::Path(_) => Ok(EtagMode::Strong),
+        syn::Meta::List(list) => {
+            let mode: Ident = list.parse_args()?;
+            if mode == "weak" {
+                Ok(EtagMode::Weak)
+            } else {
+                Err(syn::Error::new_spanned(
+                    &mode,
+                    format!(
+                        "Unknown etag mode '{mode}'.\n\
+                         \n\
+                         Use #[etag] for a strong ETag or #[etag(weak)] for a weak one."
+                    ),
+                ))
+            }
+        },
+        syn::Meta::NameValue(_) => Err(syn::Error::new_spanned(
+            attr,
+            "Expected #[etag] or #[etag(weak)]",
+        )),
+    }
+}
+
+/// Reject malformed `{param}` segments at the pattern's span.
+fn validate_pattern(pattern: &LitStr) -> Result<()> {
+    parse_segments(&pattern.value())
+        .map(|_| ())
+        .map_err(|msg| syn::Error::new(pattern.span(), msg))
+}
+
+#[allow(clippy::too_many_lines)] // One helpful error per malformed input
 fn parse_typed_inputs(
     input: ParseStream<'_>,
     method_str: &str,
@@ -433,7 +834,7 @@ fn parse_typed_inputs(
         .map_or("/path", std::string::String::as_str);
 
     while !input.is_empty() {
-        // Parse source: path, body, or query
+        // Parse source: path, body, form, multipart, query, headers or cookies
         let source_ident: Ident = input.parse().map_err(|e| {
             syn::Error::new(
                 e.span(),
@@ -441,9 +842,13 @@ fn parse_typed_inputs(
                     "Expected input source in handler parameters.\n\
                      \n\
                      Valid sources:\n\
-                     - path: Type   - URL path parameters (e.g., /users/{{id}})\n\
-                     - body: Type   - JSON request body\n\
-                     - query: Type  - Query string parameters\n\
+                     - path: Type      - URL path parameters (e.g., /users/{{id}})\n\
+                     - body: Type      - JSON request body\n\
+                     - form: Type      - Form-urlencoded request body\n\
+                     - multipart: Type - Multipart request body (file uploads)\n\
+                     - query: Type     - Query string parameters\n\
+                     - headers: Type   - Request headers\n\
+                     - cookies: Type   - Request cookies\n\
                      \n\
                      Example:\n\
                      {method_str} \"{path}\" => {handler}(path: UserId, body: CreateUser, query: Pagination) -> User\n\
@@ -456,7 +861,11 @@ fn parse_typed_inputs(
         let source = match source_ident.to_string().as_str() {
             "path" => InputSource::Path,
             "body" => InputSource::Body,
+            "form" => InputSource::Form,
+            "multipart" => InputSource::Multipart,
             "query" => InputSource::Query,
+            "headers" => InputSource::Headers,
+            "cookies" => InputSource::Cookies,
             other => {
                 let suggestion = did_you_mean(other, VALID_INPUT_SOURCES);
                 return Err(syn::Error::new_spanned(
@@ -465,9 +874,13 @@ fn parse_typed_inputs(
                         "Invalid input source '{other}'.{suggestion}\n\
                          \n\
                          Valid sources:\n\
-                         - path  - URL path parameters (e.g., /users/{{id}})\n\
-                         - body  - JSON request body\n\
-                         - query - Query string parameters\n\
+                         - path      - URL path parameters (e.g., /users/{{id}})\n\
+                         - body      - JSON request body\n\
+                         - form      - Form-urlencoded request body\n\
+                         - multipart - Multipart request body (file uploads)\n\
+                         - query     - Query string parameters\n\
+                         - headers   - Request headers\n\
+                         - cookies   - Request cookies\n\
                          \n\
                          Example:\n\
                          {method_str} \"{path}\" => {handler}(path: Id, body: CreateUser) -> User"
@@ -501,9 +914,13 @@ fn parse_typed_inputs(
                     "Expected type name after '{source_ident}: '.\n\
                      \n\
                      The type must be a struct that derives the appropriate trait:\n\
-                     - path: Type   - Type must derive Path\n\
-                     - body: Type   - Type must derive Type (for JSON parsing)\n\
-                     - query: Type  - Type must derive Query\n\
+                     - path: Type      - Type must derive Path\n\
+                     - body: Type      - Type must derive Type (for JSON parsing)\n\
+                     - form: Type      - Type must derive Type\n\
+                     - multipart: Type - Type must derive Type\n\
+                     - query: Type     - Type must derive Query\n\
+                     - headers: Type   - Type must derive Headers\n\
+                     - cookies: Type   - Type must derive Cookies\n\
                      \n\
                      Example:\n\
                      #[derive(Path)]\n\
@@ -516,6 +933,26 @@ fn parse_typed_inputs(
             )
         })?;
 
+        // The request body can only be decoded one way
+        if matches!(
+            source,
+            InputSource::Body | InputSource::Form | InputSource::Multipart
+        ) && inputs.iter().any(|existing: &TypedInput| {
+            matches!(
+                existing.source,
+                InputSource::Body | InputSource::Form | InputSource::Multipart
+            )
+        }) {
+            return Err(syn::Error::new_spanned(
+                &source_ident,
+                format!(
+                    "Handler '{handler}' already reads the request body.\n\
+                     \n\
+                     Use only one of body:, form: or multipart: per route."
+                ),
+            ));
+        }
+
         inputs.push(TypedInput { source, type_name });
 
         // Optional comma
@@ -54,6 +54,12 @@ pub static TYPE_REGISTRY: &[TypeInfo] = &[
         json_getter: "bool",
         display_name: "boolean",
     },
+    // Multipart file parts - decoded by FromForm, never read from JSON
+    TypeInfo {
+        rust_names: &["Upload"],
+        json_getter: "upload",
+        display_name: "file",
+    },
 ];
 
 /// Look up type info by Rust type name.
@@ -0,0 +1,96 @@
+//! Shared helpers for `routes!` runtime tests.
+//!
+//! The WIT bindings normally generated by cargo-component are mocked here so
+//! the generated `Guest::handle` can be called directly on the native target.
+
+#![allow(dead_code)] // Not every test binary uses every helper
+
+pub mod bindings {
+    pub mod exports {
+        pub mod mik {
+            pub mod core {
+                pub mod handler {
+                    #[derive(Debug, Clone)]
+                    pub enum Method {
+                        Get,
+                        Post,
+                        Put,
+                        Patch,
+                        Delete,
+                        Head,
+                        Options,
+                        Trace,
+                        Other(String),
+                    }
+
+                    pub struct RequestData {
+                        pub method: Method,
+                        pub path: String,
+                        pub headers: Vec<(String, String)>,
+                        pub body: Option<Vec<u8>>,
+                    }
+
+                    #[derive(Debug)]
+                    pub struct Response {
+                        pub status: u16,
+                        pub headers: Vec<(String, String)>,
+                        pub body: Option<Vec<u8>>,
+                    }
+
+                    pub trait GuestBodyStream: 'static {
+                        fn next_chunk(&self) -> Option<Vec<u8>>;
+                    }
+
+                    /// Owned handle to an exported `body-stream` resource.
+                    pub struct BodyStream(Box<dyn GuestBodyStream>);
+
+                    impl BodyStream {
+                        pub fn new<T: GuestBodyStream>(val: T) -> Self {
+                            Self(Box::new(val))
+                        }
+                    }
+
+                    pub trait Guest {
+                        type BodyStream: GuestBodyStream;
+
+                        fn handle(req: RequestData) -> Response;
+
+                        fn take_body_stream() -> Option<BodyStream>;
+                    }
+                }
+            }
+        }
+    }
+
+    macro_rules! export {
+        ($handler:ident with_types_in $bindings:ident) => {
+            const _: $handler = $handler;
+        };
+    }
+    pub(crate) use export;
+}
+
+use bindings::exports::mik::core::handler::{self, Response};
+
+/// A 200 response with a plain text body.
+pub fn text(body: &str) -> Response {
+    handler::Response {
+        status: 200,
+        headers: vec![],
+        body: Some(body.as_bytes().to_vec()),
+    }
+}
+
+/// The response body as UTF-8 text.
+pub fn body_of(response: &Response) -> String {
+    String::from_utf8(response.body.clone().unwrap_or_default()).unwrap()
+}
+
+/// First response header with the given name (case-insensitive).
+pub fn header<'a>(response: &'a Response, name: &str) -> Option<&'a str> {
+    response
+        .headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
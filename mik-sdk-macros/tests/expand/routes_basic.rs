@@ -12,8 +12,22 @@ mod bindings {
                         pub body: Option<Vec<u8>>,
                     }
 
+                    pub trait GuestBodyStream: 'static {
+                        fn next_chunk(&self) -> Option<Vec<u8>>;
+                    }
+
+                    pub struct BodyStream(Box<dyn GuestBodyStream>);
+
+                    impl BodyStream {
+                        pub fn new<T: GuestBodyStream>(val: T) -> Self {
+                            Self(Box::new(val))
+                        }
+                    }
+
                     pub trait Guest {
+                        type BodyStream: GuestBodyStream;
                         fn handle(req: super::super::super::super::RequestData) -> Response;
+                        fn take_body_stream() -> Option<BodyStream>;
                     }
                 }
             }
@@ -35,6 +49,8 @@ mod bindings {
         Delete,
         Head,
         Options,
+        Trace,
+        Other(String),
     }
 }
 
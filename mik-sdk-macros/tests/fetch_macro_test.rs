@@ -7,7 +7,7 @@ use mik_sdk::http_client::{ClientRequest, Method};
 #[test]
 fn test_fetch_simple_get() {
     let req: ClientRequest = fetch!(GET "https://api.example.com/users");
-    assert_eq!(req.method(), Method::Get);
+    assert_eq!(req.method(), &Method::Get);
     assert_eq!(req.url(), "https://api.example.com/users");
     assert!(req.headers().is_empty());
     assert!(req.body_bytes().is_none());
@@ -16,19 +16,19 @@ fn test_fetch_simple_get() {
 #[test]
 fn test_fetch_simple_post() {
     let req: ClientRequest = fetch!(POST "https://api.example.com/users");
-    assert_eq!(req.method(), Method::Post);
+    assert_eq!(req.method(), &Method::Post);
     assert_eq!(req.url(), "https://api.example.com/users");
 }
 
 #[test]
 fn test_fetch_all_methods() {
-    assert_eq!(fetch!(GET "http://x").method(), Method::Get);
-    assert_eq!(fetch!(POST "http://x").method(), Method::Post);
-    assert_eq!(fetch!(PUT "http://x").method(), Method::Put);
-    assert_eq!(fetch!(DELETE "http://x").method(), Method::Delete);
-    assert_eq!(fetch!(PATCH "http://x").method(), Method::Patch);
-    assert_eq!(fetch!(HEAD "http://x").method(), Method::Head);
-    assert_eq!(fetch!(OPTIONS "http://x").method(), Method::Options);
+    assert_eq!(fetch!(GET "http://x").method(), &Method::Get);
+    assert_eq!(fetch!(POST "http://x").method(), &Method::Post);
+    assert_eq!(fetch!(PUT "http://x").method(), &Method::Put);
+    assert_eq!(fetch!(DELETE "http://x").method(), &Method::Delete);
+    assert_eq!(fetch!(PATCH "http://x").method(), &Method::Patch);
+    assert_eq!(fetch!(HEAD "http://x").method(), &Method::Head);
+    assert_eq!(fetch!(OPTIONS "http://x").method(), &Method::Options);
 }
 
 #[test]
@@ -40,7 +40,7 @@ fn test_fetch_with_headers() {
         }
     );
 
-    assert_eq!(req.method(), Method::Get);
+    assert_eq!(req.method(), &Method::Get);
     assert_eq!(req.headers().len(), 2);
 
     // Check headers exist (order may vary)
@@ -86,7 +86,7 @@ fn test_fetch_with_raw_body() {
         body: body_bytes
     );
 
-    assert_eq!(req.method(), Method::Post);
+    assert_eq!(req.method(), &Method::Post);
     assert_eq!(req.body_bytes(), Some(b"raw body content".as_slice()));
 }
 
@@ -114,7 +114,7 @@ fn test_fetch_with_all_options() {
         timeout: 10000
     );
 
-    assert_eq!(req.method(), Method::Put);
+    assert_eq!(req.method(), &Method::Put);
     assert_eq!(req.url(), "https://api.example.com/resource");
     assert_eq!(req.headers().len(), 2);
     assert_eq!(req.body_bytes(), Some(b"some data".as_slice()));
@@ -128,9 +128,9 @@ fn test_fetch_case_insensitive_method() {
     let req2: ClientRequest = fetch!(Get "https://example.com");
     let req3: ClientRequest = fetch!(GET "https://example.com");
 
-    assert_eq!(req1.method(), Method::Get);
-    assert_eq!(req2.method(), Method::Get);
-    assert_eq!(req3.method(), Method::Get);
+    assert_eq!(req1.method(), &Method::Get);
+    assert_eq!(req2.method(), &Method::Get);
+    assert_eq!(req3.method(), &Method::Get);
 }
 
 #[test]
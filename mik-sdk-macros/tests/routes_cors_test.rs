@@ -0,0 +1,157 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! `cors:` policies in `routes!`.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, body_of, header, text};
+
+#[derive(Path)]
+struct IdPath {
+    id: u32,
+}
+
+routes! {
+    cors: {
+        origins: ["https://app.example.com", "https://admin.example.com"],
+        headers: ["content-type", "authorization"],
+        credentials: true,
+        max_age: 600,
+    },
+
+    GET "/users" => list_users,
+    POST "/users" => create_user,
+    GET "/users/{id}" => get_user(path: IdPath),
+    OPTIONS "/custom" => custom_options,
+}
+
+fn list_users(_req: &Request) -> Response {
+    text("list_users")
+}
+
+fn create_user(_req: &Request) -> Response {
+    text("create_user")
+}
+
+fn get_user(path: IdPath, _req: &Request) -> Response {
+    text(&format!("user:{}", path.id))
+}
+
+fn custom_options(_req: &Request) -> Response {
+    text("custom_options")
+}
+
+const APP: &str = "https://app.example.com";
+
+fn call(method: handler::Method, path: &str, headers: &[(&str, &str)]) -> Response {
+    Handler::handle(handler::RequestData {
+        method,
+        path: path.to_string(),
+        headers: headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+        body: None,
+    })
+}
+
+fn preflight(path: &str, origin: &str) -> Response {
+    call(
+        handler::Method::Options,
+        path,
+        &[
+            ("Origin", origin),
+            ("Access-Control-Request-Method", "POST"),
+            ("Access-Control-Request-Headers", "x-ignored"),
+        ],
+    )
+}
+
+#[test]
+fn test_simple_request_gets_cors_headers() {
+    let response = call(handler::Method::Get, "/users", &[("origin", APP)]);
+    assert_eq!(body_of(&response), "list_users");
+    assert_eq!(header(&response, "access-control-allow-origin"), Some(APP));
+    assert_eq!(
+        header(&response, "access-control-allow-credentials"),
+        Some("true")
+    );
+    assert_eq!(header(&response, "vary"), Some("Origin"));
+}
+
+#[test]
+fn test_disallowed_or_missing_origin_gets_no_cors_headers() {
+    let response = call(
+        handler::Method::Get,
+        "/users",
+        &[("origin", "https://evil.example")],
+    );
+    assert_eq!(response.status, 200);
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+    // Still keyed on Origin, so a cache can't hand this to an allowed origin
+    assert_eq!(header(&response, "vary"), Some("Origin"));
+
+    let response = call(handler::Method::Get, "/users", &[]);
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+    assert_eq!(header(&response, "vary"), Some("Origin"));
+}
+
+#[test]
+fn test_preflight_is_answered_automatically() {
+    let response = preflight("/users", APP);
+    assert_eq!(response.status, 204);
+    assert_eq!(response.body, None);
+    assert_eq!(header(&response, "access-control-allow-origin"), Some(APP));
+    assert_eq!(
+        header(&response, "access-control-allow-methods"),
+        Some("GET, HEAD, POST, OPTIONS")
+    );
+    assert_eq!(
+        header(&response, "access-control-allow-headers"),
+        Some("content-type, authorization")
+    );
+    assert_eq!(header(&response, "access-control-max-age"), Some("600"));
+}
+
+#[test]
+fn test_preflight_from_disallowed_origin() {
+    let response = preflight("/users", "https://evil.example");
+    assert_eq!(response.status, 204);
+    assert_eq!(header(&response, "access-control-allow-origin"), None);
+    assert_eq!(header(&response, "access-control-allow-methods"), None);
+}
+
+#[test]
+fn test_explicit_options_route_still_wins() {
+    let response = preflight("/custom", APP);
+    assert_eq!(body_of(&response), "custom_options");
+    assert_eq!(header(&response, "access-control-allow-origin"), Some(APP));
+}
+
+#[test]
+fn test_fallback_responses_get_cors_headers() {
+    let origin = [("origin", APP)];
+
+    let not_found = call(handler::Method::Get, "/missing", &origin);
+    assert_eq!(not_found.status, 404);
+    assert_eq!(header(&not_found, "access-control-allow-origin"), Some(APP));
+
+    let bad_input = call(handler::Method::Get, "/users/abc", &origin);
+    assert_eq!(bad_input.status, 400);
+    assert_eq!(header(&bad_input, "access-control-allow-origin"), Some(APP));
+
+    let wrong_method = call(handler::Method::Delete, "/users", &origin);
+    assert_eq!(wrong_method.status, 405);
+    assert_eq!(
+        header(&wrong_method, "access-control-allow-origin"),
+        Some(APP)
+    );
+}
@@ -0,0 +1,282 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! Runtime dispatch tests for the `routes!` macro.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, body_of, header, text};
+
+#[derive(Path)]
+struct UserPath {
+    id: String,
+}
+
+#[derive(Path)]
+struct PostPath {
+    id: String,
+    post_id: String,
+}
+
+routes! {
+    GET "/" | "" => home,
+    GET "/users" => list_users,
+    POST "/users" => create_user,
+    GET "/users/{id}" => get_user(path: UserPath),
+    DELETE "/users/{id}" => delete_user(path: UserPath),
+    GET "/users/me" => get_me,
+    GET "/users/me/settings" => get_settings,
+    GET "/users/{id}/posts/{post_id}" => get_post(path: PostPath),
+    HEAD "/users/me/settings" => head_settings,
+    OPTIONS "/users/me/settings" => options_settings,
+}
+
+fn home(_req: &Request) -> Response {
+    handler::Response {
+        status: 200,
+        headers: vec![("Content-Length".to_string(), "4".to_string())],
+        body: Some(b"home".to_vec()),
+    }
+}
+
+fn list_users(_req: &Request) -> Response {
+    text("list_users")
+}
+
+fn create_user(_req: &Request) -> Response {
+    text("create_user")
+}
+
+fn get_user(path: UserPath, _req: &Request) -> Response {
+    text(&format!("get_user:{}", path.id))
+}
+
+fn delete_user(path: UserPath, _req: &Request) -> Response {
+    text(&format!("delete_user:{}", path.id))
+}
+
+fn get_me(_req: &Request) -> Response {
+    text("get_me")
+}
+
+fn get_settings(_req: &Request) -> Response {
+    text("get_settings")
+}
+
+fn head_settings(_req: &Request) -> Response {
+    handler::Response {
+        status: 200,
+        headers: vec![("x-explicit".to_string(), "head".to_string())],
+        body: Some(b"ignored".to_vec()),
+    }
+}
+
+fn options_settings(_req: &Request) -> Response {
+    text("options_settings")
+}
+
+fn get_post(path: PostPath, req: &Request) -> Response {
+    text(&format!(
+        "get_post:{}:{}:{}",
+        path.id,
+        path.post_id,
+        req.param_or("post_id", "")
+    ))
+}
+
+fn call(method: handler::Method, path: &str) -> Response {
+    Handler::handle(handler::RequestData {
+        method,
+        path: path.to_string(),
+        headers: vec![],
+        body: None,
+    })
+}
+
+#[test]
+fn test_static_routes() {
+    assert_eq!(body_of(&call(handler::Method::Get, "/")), "home");
+    assert_eq!(body_of(&call(handler::Method::Get, "")), "home");
+    assert_eq!(body_of(&call(handler::Method::Get, "/users")), "list_users");
+    assert_eq!(
+        body_of(&call(handler::Method::Post, "/users")),
+        "create_user"
+    );
+}
+
+#[test]
+fn test_param_routes_extract_params() {
+    assert_eq!(
+        body_of(&call(handler::Method::Get, "/users/42")),
+        "get_user:42"
+    );
+    assert_eq!(
+        body_of(&call(handler::Method::Get, "/users/7/posts/99")),
+        "get_post:7:99:99"
+    );
+}
+
+#[test]
+fn test_params_are_url_decoded() {
+    assert_eq!(
+        body_of(&call(handler::Method::Get, "/users/a%20b")),
+        "get_user:a b"
+    );
+}
+
+#[test]
+fn test_query_string_is_ignored_for_matching() {
+    assert_eq!(
+        body_of(&call(handler::Method::Get, "/users/42?expand=posts")),
+        "get_user:42"
+    );
+}
+
+#[test]
+fn test_literal_wins_over_param_regardless_of_order() {
+    assert_eq!(body_of(&call(handler::Method::Get, "/users/me")), "get_me");
+    assert_eq!(
+        body_of(&call(handler::Method::Get, "/users/me/settings")),
+        "get_settings"
+    );
+}
+
+#[test]
+fn test_backtracks_from_literal_to_param() {
+    // "/users/me/posts/1" dead-ends under the "me" literal and must fall back to {id}
+    assert_eq!(
+        body_of(&call(handler::Method::Get, "/users/me/posts/1")),
+        "get_post:me:1:1"
+    );
+    // DELETE is only registered on the {id} branch
+    assert_eq!(
+        body_of(&call(handler::Method::Delete, "/users/me")),
+        "delete_user:me"
+    );
+}
+
+#[test]
+fn test_unmatched_paths_return_404() {
+    for path in ["/nope", "/users/1/posts", "/users/1/posts/2/extra"] {
+        let response = call(handler::Method::Get, path);
+        assert_eq!(response.status, 404, "path {path}");
+    }
+}
+
+#[test]
+fn test_trailing_slash_is_significant() {
+    assert_eq!(
+        call(handler::Method::Get, "/users/me/settings/").status,
+        404
+    );
+    assert_eq!(call(handler::Method::Get, "//").status, 404);
+    // A param segment matches an empty segment, as before
+    assert_eq!(body_of(&call(handler::Method::Get, "/users/")), "get_user:");
+}
+
+#[test]
+fn test_wrong_method_returns_405_with_allow() {
+    let response = call(handler::Method::Put, "/users");
+    assert_eq!(response.status, 405);
+    assert_eq!(header(&response, "allow"), Some("GET, HEAD, POST, OPTIONS"));
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("application/problem+json")
+    );
+
+    let body = json::try_parse(&response.body.unwrap()).unwrap();
+    assert_eq!(body.path_int(&["status"]), Some(405));
+    assert_eq!(
+        body.path_str(&["title"]),
+        Some("Method Not Allowed".to_string())
+    );
+    assert_eq!(
+        body.path_str(&["detail"]),
+        Some("Method PUT not allowed for this route".to_string())
+    );
+}
+
+#[test]
+fn test_allow_header_unions_all_matching_routes() {
+    // "/users/me" matches both the "me" literal (GET) and {id} (GET, DELETE)
+    let response = call(handler::Method::Post, "/users/me");
+    assert_eq!(response.status, 405);
+    assert_eq!(
+        header(&response, "allow"),
+        Some("GET, HEAD, DELETE, OPTIONS")
+    );
+
+    let response = call(handler::Method::Patch, "/");
+    assert_eq!(response.status, 405);
+    assert_eq!(header(&response, "allow"), Some("GET, HEAD, OPTIONS"));
+}
+
+#[test]
+fn test_unknown_path_is_still_404() {
+    let response = call(handler::Method::Put, "/nope");
+    assert_eq!(response.status, 404);
+    assert_eq!(header(&response, "allow"), None);
+}
+
+#[test]
+fn test_head_runs_get_handler_without_body() {
+    let response = call(handler::Method::Head, "/users/42");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, None);
+    assert_eq!(header(&response, "content-length"), Some("11"));
+}
+
+#[test]
+fn test_head_keeps_existing_content_length() {
+    let response = call(handler::Method::Head, "/");
+    assert_eq!(response.body, None);
+    assert_eq!(
+        response
+            .headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+            .count(),
+        1
+    );
+    assert_eq!(header(&response, "content-length"), Some("4"));
+}
+
+#[test]
+fn test_explicit_head_route_wins() {
+    let response = call(handler::Method::Head, "/users/me/settings");
+    assert_eq!(header(&response, "x-explicit"), Some("head"));
+    assert_eq!(response.body, None);
+}
+
+#[test]
+fn test_head_on_unknown_path_has_no_body() {
+    let response = call(handler::Method::Head, "/nope");
+    assert_eq!(response.status, 404);
+    assert_eq!(response.body, None);
+}
+
+#[test]
+fn test_options_lists_allowed_methods() {
+    let response = call(handler::Method::Options, "/users/42");
+    assert_eq!(response.status, 204);
+    assert_eq!(response.body, None);
+    assert_eq!(
+        header(&response, "allow"),
+        Some("GET, HEAD, DELETE, OPTIONS")
+    );
+
+    assert_eq!(call(handler::Method::Options, "/nope").status, 404);
+}
+
+#[test]
+fn test_explicit_options_route_wins() {
+    let response = call(handler::Method::Options, "/users/me/settings");
+    assert_eq!(body_of(&response), "options_settings");
+}
@@ -0,0 +1,152 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! `#[etag]` routes and conditional request helpers.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::conditional::{ETag, HEADER_LAST_MODIFIED};
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest};
+use common::bindings;
+
+#[derive(Type)]
+struct User {
+    id: i64,
+    name: String,
+    version: i64,
+}
+
+#[derive(Type)]
+struct Rename {
+    name: String,
+}
+
+routes! {
+    #[etag]
+    GET "/users/{id}" => get_user(path: Id) -> User,
+    #[etag(weak)]
+    GET "/feed" => feed,
+    PUT "/users/{id}" => rename_user(path: Id, body: Rename) -> User,
+}
+
+fn alice() -> User {
+    User {
+        id: 1,
+        name: "alice".to_string(),
+        version: 7,
+    }
+}
+
+fn get_user(path: Id, _req: &Request) -> Result<User, ApiError> {
+    if path.as_str() == "1" {
+        Ok(alice())
+    } else {
+        Err(ApiError::not_found("User not found"))
+    }
+}
+
+fn feed(_req: &Request) -> mik_sdk::response::ResponseParts {
+    json::obj()
+        .set("items", json::arr())
+        .into_response()
+        .with_header(HEADER_LAST_MODIFIED, time::to_http_date(1_737_024_600))
+}
+
+fn rename_user(_path: Id, body: Rename, req: &Request) -> Result<User, ApiError> {
+    let current = alice();
+    req.check_preconditions(Some(&ETag::strong(current.version.to_string())), None)?;
+    Ok(User {
+        name: body.name,
+        version: current.version + 1,
+        ..current
+    })
+}
+
+fn etag_of(path: &str) -> String {
+    __mik_test(TestRequest::get(path))
+        .header("etag")
+        .expect("ETag header")
+        .to_string()
+}
+
+#[test]
+fn test_etag_route_sets_strong_etag() {
+    let etag = etag_of("/users/1");
+    assert!(etag.starts_with('"'), "{etag}");
+    assert_eq!(etag, etag_of("/users/1"), "ETag is stable");
+}
+
+#[test]
+fn test_matching_if_none_match_is_304() {
+    let etag = etag_of("/users/1");
+    let response = __mik_test(TestRequest::get("/users/1").header("If-None-Match", &etag));
+    response
+        .assert_status(304)
+        .assert_header("etag", &etag)
+        .assert_no_header("content-type")
+        .assert_text("");
+
+    __mik_test(TestRequest::get("/users/1").header("If-None-Match", "\"stale\""))
+        .assert_status(200)
+        .assert_json_path("name", "alice");
+}
+
+#[test]
+fn test_errors_are_not_tagged() {
+    __mik_test(TestRequest::get("/users/2"))
+        .assert_status(404)
+        .assert_no_header("etag");
+}
+
+#[test]
+fn test_weak_etag_and_last_modified() {
+    let etag = etag_of("/feed");
+    assert!(etag.starts_with("W/\""), "{etag}");
+
+    __mik_test(
+        TestRequest::get("/feed").header("If-Modified-Since", "Thu, 16 Jan 2025 10:50:00 GMT"),
+    )
+    .assert_status(304)
+    .assert_header("last-modified", "Thu, 16 Jan 2025 10:50:00 GMT");
+}
+
+#[test]
+fn test_if_match_guards_updates() {
+    let rename = || json::obj().set("name", json::str("bob"));
+    __mik_test(
+        TestRequest::put("/users/1")
+            .header("If-Match", "\"6\"")
+            .json(rename()),
+    )
+    .assert_status(412)
+    .assert_json_path("title", "Precondition Failed");
+
+    __mik_test(
+        TestRequest::put("/users/1")
+            .header("If-Match", "\"7\"")
+            .json(rename()),
+    )
+    .assert_status(200)
+    .assert_json_path("version", 8);
+}
+
+#[test]
+fn test_openapi_documents_304() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let responses = |path: &str, method: &str| {
+        spec.get("paths")
+            .get(path)
+            .get(method)
+            .get("responses")
+            .keys()
+    };
+    assert!(responses("/users/{id}", "get").contains(&"304".to_string()));
+    assert!(!responses("/users/{id}", "put").contains(&"304".to_string()));
+}
@@ -0,0 +1,177 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! `form:` and `multipart:` input sources and `Upload` fields.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, text};
+
+#[derive(Type)]
+struct Signup {
+    #[field(min = 2)]
+    name: String,
+    age: Option<u32>,
+    newsletter: bool,
+    #[field(rename = "tag")]
+    tags: Vec<String>,
+}
+
+#[derive(Type)]
+struct Album {
+    title: String,
+    cover: Upload,
+    photos: Vec<Upload>,
+    notes: Option<Upload>,
+}
+
+routes! {
+    POST "/signup" => signup(form: Signup),
+    POST "/albums" => create_album(multipart: Album),
+}
+
+fn signup(form: Signup, _req: &Request) -> Response {
+    text(&format!(
+        "{}:{}:{}:{}",
+        form.name,
+        form.age
+            .map_or_else(|| "-".to_string(), |age| age.to_string()),
+        form.newsletter,
+        form.tags.join(",")
+    ))
+}
+
+fn create_album(album: Album, _req: &Request) -> Response {
+    text(&format!(
+        "{}:{}:{}:{}:{}",
+        album.title,
+        album.cover.filename().unwrap_or("-"),
+        album.cover.len(),
+        album.photos.len(),
+        album.notes.is_some()
+    ))
+}
+
+fn form(body: &str) -> TestRequest {
+    TestRequest::post("/signup")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .text(body)
+}
+
+fn multipart(parts: &[(&str, Option<&str>, &str)]) -> TestRequest {
+    let mut body = String::new();
+    for (name, filename, data) in parts {
+        body.push_str("--XyZ\r\nContent-Disposition: form-data; name=\"");
+        body.push_str(name);
+        body.push('"');
+        if let Some(filename) = filename {
+            body.push_str("; filename=\"");
+            body.push_str(filename);
+            body.push('"');
+        }
+        body.push_str("\r\n\r\n");
+        body.push_str(data);
+        body.push_str("\r\n");
+    }
+    body.push_str("--XyZ--\r\n");
+    TestRequest::post("/albums")
+        .header("content-type", "multipart/form-data; boundary=XyZ")
+        .text(&body)
+}
+
+#[test]
+fn test_form_fields_are_decoded() {
+    __mik_test(form("name=Ada+L&age=36&newsletter=on&tag=a&tag=b%2Fc"))
+        .assert_status(200)
+        .assert_text("Ada L:36:true:a,b/c");
+}
+
+#[test]
+fn test_form_optional_and_checkbox_defaults() {
+    __mik_test(form("name=Ada&age=")).assert_text("Ada:-:false:");
+}
+
+#[test]
+fn test_form_errors_are_400() {
+    __mik_test(form("age=3"))
+        .assert_status(400)
+        .assert_header("content-type", "application/problem+json")
+        .assert_json_path("detail", "missing required field `name`");
+    __mik_test(form("name=Ada&age=old"))
+        .assert_status(400)
+        .assert_json_path("detail", "expected integer for field `age`");
+}
+
+#[test]
+fn test_form_is_validated() {
+    __mik_test(form("name=A"))
+        .assert_status(422)
+        .assert_json_path("status", 422)
+        .assert_json_path("errors[0].field", "name")
+        .assert_json_path("errors[0].constraint", "min");
+}
+
+#[test]
+fn test_multipart_uploads_are_decoded() {
+    __mik_test(multipart(&[
+        ("title", None, "Summer"),
+        ("cover", Some("cover.jpg"), "JPEGDATA"),
+        ("photos", Some("1.jpg"), "one"),
+        ("photos", Some("2.jpg"), "two"),
+    ]))
+    .assert_status(200)
+    .assert_text("Summer:cover.jpg:8:2:false");
+}
+
+#[test]
+fn test_multipart_errors_are_400() {
+    __mik_test(multipart(&[("title", None, "Summer")]))
+        .assert_status(400)
+        .assert_json_path("detail", "missing required field `cover`");
+    __mik_test(TestRequest::post("/albums").text("title=Summer"))
+        .assert_status(400)
+        .assert_json_path("detail", "Content-Type is not multipart/form-data");
+}
+
+#[test]
+fn test_openapi_form_request_bodies() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let paths = spec.get("paths");
+
+    let signup = paths.get("/signup").get("post").get("requestBody");
+    assert_eq!(
+        signup
+            .get("content")
+            .get("application/x-www-form-urlencoded")
+            .get("schema")
+            .get("$ref")
+            .str(),
+        Some("#/components/schemas/Signup".to_string())
+    );
+
+    let album = paths.get("/albums").get("post").get("requestBody");
+    assert!(
+        !album
+            .get("content")
+            .get("multipart/form-data")
+            .get("schema")
+            .is_null()
+    );
+
+    let cover = spec
+        .get("components")
+        .get("schemas")
+        .get("Album")
+        .get("properties")
+        .get("cover");
+    assert_eq!(cover.get("type").str(), Some("string".to_string()));
+    assert_eq!(cover.get("format").str(), Some("binary".to_string()));
+}
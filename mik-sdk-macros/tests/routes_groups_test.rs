@@ -0,0 +1,152 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value,
+    clippy::unnecessary_wraps,
+    clippy::literal_string_with_formatting_args // Route patterns like "/orgs/{org}"
+)]
+//! Route groups with shared prefix, tag and guards in `routes!`.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, body_of, text};
+
+#[derive(Path)]
+struct OrgPath {
+    org: u32,
+    user: String,
+}
+
+routes! {
+    #[tag = "Public"]
+
+    GET "/health" => health,
+
+    group "/admin" #[tag = "Admin"] {
+        before: require_admin,
+
+        GET "" => admin_home,
+        GET "/users" => admin_users,
+        #[tag = "Audit"]
+        GET "/audit" => admin_audit,
+
+        group "/reports" #[deprecated] {
+            GET "/daily" => daily_report,
+        }
+    }
+
+    group "/orgs/{org:int}/" {
+        GET "/users/{user}" => org_user(path: OrgPath),
+    }
+}
+
+fn require_admin(req: &Request) -> Option<Response> {
+    if req.header_or("x-role", "") == "admin" {
+        None
+    } else {
+        Some(handler::Response {
+            status: 403,
+            headers: vec![],
+            body: None,
+        })
+    }
+}
+
+fn health(_req: &Request) -> Response {
+    text("ok")
+}
+
+fn admin_home(_req: &Request) -> Response {
+    text("admin_home")
+}
+
+fn admin_users(_req: &Request) -> Response {
+    text("admin_users")
+}
+
+fn admin_audit(_req: &Request) -> Response {
+    text("admin_audit")
+}
+
+fn daily_report(_req: &Request) -> Response {
+    text("daily_report")
+}
+
+fn org_user(path: OrgPath, _req: &Request) -> Response {
+    text(&format!("org:{}:{}", path.org, path.user))
+}
+
+fn call(path: &str, admin: bool) -> Response {
+    let headers = if admin {
+        vec![("x-role".to_string(), "admin".to_string())]
+    } else {
+        vec![]
+    };
+    Handler::handle(handler::RequestData {
+        method: handler::Method::Get,
+        path: path.to_string(),
+        headers,
+        body: None,
+    })
+}
+
+#[test]
+fn test_group_prefix_is_applied() {
+    assert_eq!(body_of(&call("/admin", true)), "admin_home");
+    assert_eq!(body_of(&call("/admin/users", true)), "admin_users");
+    assert_eq!(body_of(&call("/admin/reports/daily", true)), "daily_report");
+    assert_eq!(call("/users", true).status, 404);
+    assert_eq!(call("/reports/daily", true).status, 404);
+}
+
+#[test]
+fn test_group_guard_applies_to_nested_routes_only() {
+    assert_eq!(call("/admin/users", false).status, 403);
+    assert_eq!(call("/admin/reports/daily", false).status, 403);
+    assert_eq!(body_of(&call("/health", false)), "ok");
+}
+
+#[test]
+fn test_prefix_params_and_constraints() {
+    assert_eq!(body_of(&call("/orgs/7/users/bob", false)), "org:7:bob");
+    assert_eq!(call("/orgs/acme/users/bob", false).status, 404);
+}
+
+#[test]
+fn test_openapi_reflects_groups() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let paths = spec.get("paths");
+    let tag = |path: &str| paths.get(path).get("get").get("tags").at(0).str();
+
+    assert_eq!(tag("/health"), Some("Public".to_string()));
+    assert_eq!(tag("/admin"), Some("Admin".to_string()));
+    assert_eq!(tag("/admin/users"), Some("Admin".to_string()));
+    assert_eq!(tag("/admin/audit"), Some("Audit".to_string()));
+    assert_eq!(tag("/admin/reports/daily"), Some("Admin".to_string()));
+    assert_eq!(
+        paths
+            .get("/admin/reports/daily")
+            .get("get")
+            .get("deprecated")
+            .bool(),
+        Some(true)
+    );
+    assert!(
+        paths
+            .get("/admin/users")
+            .get("get")
+            .get("deprecated")
+            .is_null()
+    );
+
+    let params = paths
+        .get("/orgs/{org}/users/{user}")
+        .get("get")
+        .get("parameters");
+    assert_eq!(params.len(), Some(2));
+}
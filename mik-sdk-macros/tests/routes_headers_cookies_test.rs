@@ -0,0 +1,161 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! `headers:` and `cookies:` input sources and their derives.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, text};
+
+#[derive(Headers)]
+struct AuthHeaders {
+    #[field(rename = "X-Api-Key")]
+    api_key: String,
+    x_tenant_id: u64,
+    x_request_source: Option<String>,
+}
+
+#[derive(Cookies)]
+struct Session {
+    session_id: String,
+    lang: Option<String>,
+}
+
+#[derive(Query)]
+struct Page {
+    page: Option<u32>,
+}
+
+routes! {
+    GET "/me" => me(headers: AuthHeaders),
+    GET "/prefs" => prefs(cookies: Session),
+    GET "/search" => search(query: Page, headers: AuthHeaders, cookies: Session),
+}
+
+fn me(headers: AuthHeaders, _req: &Request) -> Response {
+    text(&format!(
+        "{}:{}:{}",
+        headers.api_key,
+        headers.x_tenant_id,
+        headers.x_request_source.as_deref().unwrap_or("-")
+    ))
+}
+
+fn prefs(cookies: Session, _req: &Request) -> Response {
+    text(&format!(
+        "{}:{}",
+        cookies.session_id,
+        cookies.lang.as_deref().unwrap_or("en")
+    ))
+}
+
+fn search(query: Page, headers: AuthHeaders, cookies: Session, _req: &Request) -> Response {
+    text(&format!(
+        "{}:{}:{}",
+        query.page.unwrap_or(1),
+        headers.x_tenant_id,
+        cookies.session_id
+    ))
+}
+
+#[test]
+fn test_headers_are_parsed_case_insensitively() {
+    __mik_test(
+        TestRequest::get("/me")
+            .header("x-api-key", "secret")
+            .header("X-Tenant-Id", "42"),
+    )
+    .assert_status(200)
+    .assert_text("secret:42:-");
+}
+
+#[test]
+fn test_first_header_value_wins() {
+    __mik_test(
+        TestRequest::get("/me")
+            .header("x-api-key", "first")
+            .header("x-api-key", "second")
+            .header("x-tenant-id", "1")
+            .header("x-request-source", "cli"),
+    )
+    .assert_text("first:1:cli");
+}
+
+#[test]
+fn test_missing_or_invalid_headers_are_400() {
+    __mik_test(TestRequest::get("/me").header("x-tenant-id", "1"))
+        .assert_status(400)
+        .assert_header("content-type", "application/problem+json")
+        .assert_json_path("detail", "missing required field `X-Api-Key`");
+
+    __mik_test(
+        TestRequest::get("/me")
+            .header("x-api-key", "k")
+            .header("x-tenant-id", "acme"),
+    )
+    .assert_status(400)
+    .assert_json_path("detail", "expected integer for field `x-tenant-id`");
+}
+
+#[test]
+fn test_cookies_are_parsed_with_defaults() {
+    __mik_test(TestRequest::get("/prefs").header("cookie", "session_id=abc; lang=de"))
+        .assert_text("abc:de");
+    __mik_test(TestRequest::get("/prefs").header("cookie", "session_id=abc")).assert_text("abc:en");
+    __mik_test(TestRequest::get("/prefs").header("cookie", "lang=de"))
+        .assert_status(400)
+        .assert_json_path("detail", "missing required field `session_id`");
+}
+
+#[test]
+fn test_sources_combine() {
+    __mik_test(
+        TestRequest::get("/search?page=3")
+            .header("x-api-key", "k")
+            .header("x-tenant-id", "9")
+            .header("cookie", "session_id=s1"),
+    )
+    .assert_text("3:9:s1");
+}
+
+#[test]
+fn test_openapi_header_and_cookie_parameters() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let params = spec
+        .get("paths")
+        .get("/search")
+        .get("get")
+        .get("parameters");
+    assert_eq!(params.len(), Some(6));
+
+    let find = |name: &str| {
+        (0..params.len().unwrap())
+            .map(|i| params.at(i))
+            .find(|param| param.get("name").str().as_deref() == Some(name))
+            .unwrap()
+    };
+
+    let api_key = find("X-Api-Key");
+    assert_eq!(api_key.get("in").str(), Some("header".to_string()));
+    assert_eq!(api_key.get("required").bool(), Some(true));
+
+    let tenant = find("x-tenant-id");
+    assert_eq!(
+        tenant.get("schema").get("type").str(),
+        Some("integer".to_string())
+    );
+    assert_eq!(find("x-request-source").get("required").bool(), Some(false));
+
+    let session = find("session_id");
+    assert_eq!(session.get("in").str(), Some("cookie".to_string()));
+    assert_eq!(find("lang").get("required").bool(), Some(false));
+    assert_eq!(find("page").get("in").str(), Some("query".to_string()));
+}
@@ -0,0 +1,161 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value,
+    clippy::unnecessary_wraps
+)]
+//! `before:` / `after:` hooks and their scoping in `routes!` groups.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, body_of, header, text};
+
+#[derive(Path)]
+struct IdPath {
+    id: u32,
+}
+
+routes! {
+    before: [reject_blocked],
+    after: [stamp_outer],
+
+    GET "/health" => health,
+    GET "/items/{id}" => get_item(path: IdPath),
+
+    group {
+        before: require_token,
+        after: [stamp_inner],
+
+        GET "/admin" => admin,
+
+        group {
+            after: stamp_innermost,
+            GET "/admin/deep" => admin,
+        }
+    }
+}
+
+fn reject_blocked(req: &Request) -> Option<Response> {
+    req.header_all("x-blocked")
+        .first()
+        .map(|_| handler::Response {
+            status: 403,
+            headers: vec![],
+            body: None,
+        })
+}
+
+fn require_token(req: &Request) -> Option<Response> {
+    if req.header_all("authorization").is_empty() {
+        Some(handler::Response {
+            status: 401,
+            headers: vec![],
+            body: None,
+        })
+    } else {
+        None
+    }
+}
+
+/// Append `name` to the `x-trail` header.
+fn stamp(name: &str, mut response: Response) -> Response {
+    let trail = header(&response, "x-trail")
+        .map_or_else(|| name.to_string(), |existing| format!("{existing},{name}"));
+    response.headers.retain(|(key, _)| key != "x-trail");
+    response.headers.push(("x-trail".to_string(), trail));
+    response
+}
+
+fn stamp_outer(_req: &Request, response: Response) -> Response {
+    stamp("outer", response)
+}
+
+fn stamp_inner(_req: &Request, response: Response) -> Response {
+    stamp("inner", response)
+}
+
+fn stamp_innermost(_req: &Request, response: Response) -> Response {
+    stamp("innermost", response)
+}
+
+fn health(_req: &Request) -> Response {
+    text("ok")
+}
+
+fn get_item(path: IdPath, _req: &Request) -> Response {
+    text(&format!("item:{}", path.id))
+}
+
+fn admin(_req: &Request) -> Response {
+    text("admin")
+}
+
+fn call(path: &str, headers: &[(&str, &str)]) -> Response {
+    Handler::handle(handler::RequestData {
+        method: handler::Method::Get,
+        path: path.to_string(),
+        headers: headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+        body: None,
+    })
+}
+
+#[test]
+fn test_top_level_hooks_apply_to_every_route() {
+    let response = call("/health", &[]);
+    assert_eq!(body_of(&response), "ok");
+    assert_eq!(header(&response, "x-trail"), Some("outer"));
+}
+
+#[test]
+fn test_before_hook_short_circuits() {
+    let response = call("/health", &[("x-blocked", "1")]);
+    assert_eq!(response.status, 403);
+    // after hooks still see the short-circuited response
+    assert_eq!(header(&response, "x-trail"), Some("outer"));
+}
+
+#[test]
+fn test_after_hooks_see_input_errors() {
+    let response = call("/items/abc", &[]);
+    assert_eq!(response.status, 400);
+    assert_eq!(header(&response, "x-trail"), Some("outer"));
+}
+
+#[test]
+fn test_group_hooks_are_scoped() {
+    let response = call("/admin", &[]);
+    assert_eq!(response.status, 401);
+    assert_eq!(header(&response, "x-trail"), Some("inner,outer"));
+
+    let response = call("/admin", &[("authorization", "Bearer t")]);
+    assert_eq!(body_of(&response), "admin");
+
+    // Routes outside the group are not guarded
+    assert_eq!(call("/items/7", &[]).status, 200);
+}
+
+#[test]
+fn test_nested_groups_run_like_an_onion() {
+    let response = call("/admin/deep", &[("authorization", "Bearer t")]);
+    assert_eq!(body_of(&response), "admin");
+    assert_eq!(header(&response, "x-trail"), Some("innermost,inner,outer"));
+
+    // Outer before hooks run before inner ones
+    let response = call("/admin/deep", &[("x-blocked", "1")]);
+    assert_eq!(response.status, 403);
+}
+
+#[test]
+fn test_unmatched_requests_skip_hooks() {
+    let response = call("/missing", &[]);
+    assert_eq!(response.status, 404);
+    assert_eq!(header(&response, "x-trail"), None);
+}
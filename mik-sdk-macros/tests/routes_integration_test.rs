@@ -149,6 +149,41 @@ mod mik_sdk {
             fn from_json(value: &crate::mik_sdk::json::JsonValue) -> Result<Self, ParseError>;
         }
 
+        pub struct FormData {
+            pub values: Vec<(String, String)>,
+        }
+
+        impl FormData {
+            pub fn value(&self, name: &str) -> Option<&str> {
+                self.values
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.as_str())
+            }
+
+            pub fn values(&self, name: &str) -> Vec<&str> {
+                self.values
+                    .iter()
+                    .filter(|(key, _)| key == name)
+                    .map(|(_, value)| value.as_str())
+                    .collect()
+            }
+
+            pub fn flag(&self, name: &str) -> Result<Option<bool>, ParseError> {
+                self.value(name)
+                    .map(|value| {
+                        value
+                            .parse()
+                            .map_err(|_| ParseError::type_mismatch(name, "boolean"))
+                    })
+                    .transpose()
+            }
+        }
+
+        pub trait FromForm: Sized {
+            fn from_form(form: &FormData) -> Result<Self, ParseError>;
+        }
+
         pub trait FromQuery: Sized {
             fn from_query(params: &[(String, String)]) -> Result<Self, ParseError>;
         }
@@ -157,8 +192,45 @@ mod mik_sdk {
             fn from_params(params: &HashMap<String, String>) -> Result<Self, ParseError>;
         }
 
+        #[derive(Debug, Clone, Default)]
+        pub struct ValidationErrors {
+            pub errors: Vec<ValidationError>,
+        }
+
+        impl ValidationErrors {
+            pub const fn new() -> Self {
+                Self { errors: Vec::new() }
+            }
+
+            pub fn push(&mut self, error: ValidationError) {
+                self.errors.push(error);
+            }
+
+            pub fn nest(&mut self, path: &str, errors: Self) {
+                self.errors
+                    .extend(errors.errors.into_iter().map(|mut error| {
+                        error.field = format!("{path}.{}", error.field);
+                        error
+                    }));
+            }
+
+            pub fn into_result(self) -> Result<(), Self> {
+                if self.errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+        }
+
         pub trait Validate {
             fn validate(&self) -> Result<(), ValidationError>;
+
+            fn validate_all(&self) -> Result<(), ValidationErrors> {
+                self.validate().map_err(|error| ValidationErrors {
+                    errors: vec![error],
+                })
+            }
         }
 
         pub trait OpenApiSchema {
@@ -246,6 +318,11 @@ mod mik_sdk {
     pub mod json {
         use std::collections::HashMap;
 
+        /// Only used for nested form fields; the mock never parses.
+        pub const fn try_parse(_bytes: &[u8]) -> Option<JsonValue> {
+            None
+        }
+
         #[derive(Clone)]
         pub struct JsonValue {
             data: JsonData,
@@ -0,0 +1,87 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+//! TRACE and extension methods (e.g., `PROPFIND`) in `routes!`.
+
+#![allow(dead_code, missing_docs)] // Mock bindings
+
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest};
+use common::bindings;
+
+routes! {
+    GET "/files/{path}" => read_file,
+    "PROPFIND" "/files/{path}" => propfind,
+    "PURGE" "/cache" => purge,
+    TRACE "/debug" => trace,
+}
+
+fn read_file(_req: &Request) -> handler::Response {
+    ok!({ "op": "read" })
+}
+
+fn propfind(req: &Request) -> handler::Response {
+    ok!({ "op": req.method().as_str(), "path": req.param_or("path", "") })
+}
+
+fn purge(req: &Request) -> handler::Response {
+    ok!({ "purged": req.path() })
+}
+
+fn trace(req: &Request) -> handler::Response {
+    ok!({ "method": req.method().as_str() })
+}
+
+fn request(method: &str, path: &str) -> mik_sdk::testing::TestResponse {
+    __mik_test(TestRequest::new(Method::Other(method.to_string()), path))
+}
+
+#[test]
+fn test_custom_method_dispatches() {
+    request("PROPFIND", "/files/a.txt")
+        .assert_status(200)
+        .assert_json_path("op", "PROPFIND")
+        .assert_json_path("path", "a.txt");
+    request("PURGE", "/cache")
+        .assert_status(200)
+        .assert_json_path("purged", "/cache");
+}
+
+#[test]
+fn test_trace_dispatches() {
+    __mik_test(TestRequest::new(Method::Trace, "/debug"))
+        .assert_status(200)
+        .assert_json_path("method", "TRACE");
+}
+
+#[test]
+fn test_custom_methods_are_case_sensitive() {
+    // Method tokens are case-sensitive (RFC 9110 §9.1)
+    request("propfind", "/files/a.txt")
+        .assert_status(405)
+        .assert_header("allow", "GET, HEAD, OPTIONS, PROPFIND");
+}
+
+#[test]
+fn test_allow_lists_custom_methods() {
+    request("MKCOL", "/files/a.txt")
+        .assert_status(405)
+        .assert_header("allow", "GET, HEAD, OPTIONS, PROPFIND");
+    __mik_test(TestRequest::get("/cache"))
+        .assert_status(405)
+        .assert_header("allow", "OPTIONS, PURGE");
+    __mik_test(TestRequest::options("/debug"))
+        .assert_status(204)
+        .assert_header("allow", "OPTIONS, TRACE");
+}
+
+#[test]
+fn test_custom_methods_stay_out_of_openapi() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let files = spec.get("paths").get("/files/{path}");
+    assert_eq!(files.keys(), vec!["get".to_string()]);
+    assert!(!spec.get("paths").get("/debug").get("trace").is_null());
+    assert!(spec.get("paths").get("/cache").is_null());
+}
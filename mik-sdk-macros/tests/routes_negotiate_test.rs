@@ -0,0 +1,88 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! One route serving JSON, CSV and NDJSON via `Request::negotiate`.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::constants::{MIME_CSV, MIME_JSON, MIME_NDJSON};
+use mik_sdk::prelude::*;
+use mik_sdk::response::{self, ResponseParts};
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest};
+use common::bindings;
+
+#[derive(Type)]
+struct Row {
+    id: i64,
+    name: String,
+}
+
+routes! {
+    GET "/export" => export,
+}
+
+const FORMATS: &[&str] = &[MIME_JSON, MIME_CSV, MIME_NDJSON];
+
+fn rows() -> Vec<Row> {
+    vec![
+        Row {
+            id: 1,
+            name: "Alice".to_string(),
+        },
+        Row {
+            id: 2,
+            name: "Smith, Bob".to_string(),
+        },
+    ]
+}
+
+fn export(req: &Request) -> Result<ResponseParts, ApiError> {
+    let rows = rows();
+    let response = match req.negotiate(FORMATS) {
+        Some(MIME_CSV) => response::csv(&rows),
+        Some(MIME_NDJSON) => response::ndjson(&rows),
+        Some(_) => rows.to_json().into_response(),
+        None => return Err(ApiError::not_acceptable(FORMATS)),
+    };
+    Ok(response.with_header("Vary", "Accept"))
+}
+
+#[test]
+fn test_json_by_default() {
+    __mik_test(TestRequest::get("/export"))
+        .assert_status(200)
+        .assert_header("content-type", "application/json")
+        .assert_header("vary", "Accept")
+        .assert_json_path("[1].name", "Smith, Bob");
+}
+
+#[test]
+fn test_csv_when_preferred() {
+    __mik_test(TestRequest::get("/export").header("Accept", "application/json;q=0.5, text/csv"))
+        .assert_status(200)
+        .assert_header("content-type", "text/csv; charset=utf-8")
+        .assert_text("id,name\r\n1,Alice\r\n2,\"Smith, Bob\"\r\n");
+}
+
+#[test]
+fn test_ndjson_beats_type_wildcard() {
+    __mik_test(
+        TestRequest::get("/export").header("Accept", "application/*;q=0.5, application/x-ndjson"),
+    )
+    .assert_status(200)
+    .assert_text("{\"id\":1,\"name\":\"Alice\"}\n{\"id\":2,\"name\":\"Smith, Bob\"}\n");
+}
+
+#[test]
+fn test_not_acceptable() {
+    __mik_test(TestRequest::get("/export").header("Accept", "application/xml"))
+        .assert_status(406)
+        .assert_header("content-type", "application/problem+json")
+        .assert_json_path("available[1]", "text/csv");
+}
@@ -0,0 +1,125 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! Declared output types (`-> T`) are serialized by the dispatcher.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest};
+use common::bindings;
+
+#[derive(Type)]
+struct User {
+    id: i64,
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(Type)]
+struct NewUser {
+    name: String,
+}
+
+fn alice() -> User {
+    User {
+        id: 1,
+        name: "alice".to_string(),
+        email: None,
+    }
+}
+
+routes! {
+    GET "/users" => list_users -> Vec<User>,
+    GET "/users/{id}" => get_user(path: Id) -> User,
+    #[status(201)]
+    POST "/users" => create_user(body: NewUser) -> User,
+}
+
+fn list_users(_req: &Request) -> Vec<User> {
+    vec![alice()]
+}
+
+fn get_user(path: Id, _req: &Request) -> Result<User, ApiError> {
+    if path.as_str() == "1" {
+        Ok(alice())
+    } else {
+        Err(ApiError::not_found("User not found"))
+    }
+}
+
+fn create_user(body: NewUser, _req: &Request) -> User {
+    User {
+        id: 2,
+        name: body.name,
+        email: Some("new@example.com".to_string()),
+    }
+}
+
+#[test]
+fn test_declared_type_is_serialized() {
+    __mik_test(TestRequest::get("/users/1"))
+        .assert_status(200)
+        .assert_header("content-type", "application/json")
+        .assert_json_path("id", 1)
+        .assert_json_path("name", "alice");
+}
+
+#[test]
+fn test_declared_list_is_serialized() {
+    __mik_test(TestRequest::get("/users"))
+        .assert_status(200)
+        .assert_json_path("[0].name", "alice");
+}
+
+#[test]
+fn test_declared_status_is_used() {
+    __mik_test(TestRequest::post("/users").json(json::obj().set("name", json::str("bob"))))
+        .assert_status(201)
+        .assert_json_path("id", 2)
+        .assert_json_path("email", "new@example.com");
+}
+
+#[test]
+fn test_err_variant_keeps_its_own_status() {
+    __mik_test(TestRequest::get("/users/9"))
+        .assert_status(404)
+        .assert_header("content-type", "application/problem+json");
+}
+
+#[test]
+fn test_openapi_list_response_is_array() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let users = spec.get("paths").get("/users");
+
+    let list = users
+        .get("get")
+        .get("responses")
+        .get("200")
+        .get("content")
+        .get("application/json")
+        .get("schema");
+    assert_eq!(list.get("type").str(), Some("array".to_string()));
+    assert_eq!(
+        list.get("items").get("$ref").str(),
+        Some("#/components/schemas/User".to_string())
+    );
+
+    let created = users
+        .get("post")
+        .get("responses")
+        .get("201")
+        .get("content")
+        .get("application/json")
+        .get("schema");
+    assert_eq!(
+        created.get("$ref").str(),
+        Some("#/components/schemas/User".to_string())
+    );
+}
@@ -0,0 +1,186 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value,
+    clippy::literal_string_with_formatting_args // Route patterns like "/users/{id}"
+)]
+//! Catch-all and constrained path segments in `routes!` patterns.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, body_of, text};
+
+#[derive(Path)]
+struct IdPath {
+    id: i64,
+}
+
+routes! {
+    GET "/users/{id:int}" => get_user_by_id(path: IdPath),
+    GET "/users/me" => get_me,
+    GET "/users/{name}" => get_user_by_name,
+    GET "/orders/{id:uuid}" => get_order,
+    GET "/tags/{slug:[a-z0-9-]+}" => get_tag,
+    GET "/files/{*key}" => get_file,
+    GET "/files/{id:int}/meta" => get_file_meta,
+}
+
+fn get_user_by_id(path: IdPath, _req: &Request) -> Response {
+    text(&format!("id:{}", path.id))
+}
+
+fn get_me(_req: &Request) -> Response {
+    text("me")
+}
+
+fn get_user_by_name(req: &Request) -> Response {
+    text(&format!("name:{}", req.param_or("name", "")))
+}
+
+fn get_order(req: &Request) -> Response {
+    text(&format!("order:{}", req.param_or("id", "")))
+}
+
+fn get_tag(req: &Request) -> Response {
+    text(&format!("tag:{}", req.param_or("slug", "")))
+}
+
+fn get_file(req: &Request) -> Response {
+    text(&format!("file:{}", req.param_or("key", "")))
+}
+
+fn get_file_meta(req: &Request) -> Response {
+    text(&format!("meta:{}", req.param_or("id", "")))
+}
+
+fn get(path: &str) -> Response {
+    Handler::handle(handler::RequestData {
+        method: handler::Method::Get,
+        path: path.to_string(),
+        headers: vec![],
+        body: None,
+    })
+}
+
+#[test]
+fn test_int_constraint_wins_over_plain_param() {
+    assert_eq!(body_of(&get("/users/42")), "id:42");
+    assert_eq!(body_of(&get("/users/-7")), "id:-7");
+    assert_eq!(body_of(&get("/users/me")), "me");
+    assert_eq!(body_of(&get("/users/alice")), "name:alice");
+    assert_eq!(body_of(&get("/users/4x2")), "name:4x2");
+    assert_eq!(body_of(&get("/users/-")), "name:-");
+}
+
+#[test]
+fn test_int_constraint_rejects_values_outside_i64() {
+    assert_eq!(
+        body_of(&get("/users/9223372036854775807")),
+        "id:9223372036854775807"
+    );
+    assert_eq!(
+        body_of(&get("/users/-9223372036854775808")),
+        "id:-9223372036854775808"
+    );
+    // Overflowing segments fall through to the next route
+    assert_eq!(
+        body_of(&get("/users/9223372036854775808")),
+        "name:9223372036854775808"
+    );
+    assert_eq!(
+        body_of(&get("/users/99999999999999999999999")),
+        "name:99999999999999999999999"
+    );
+    assert_eq!(body_of(&get("/users/%2B5")), "name:+5");
+    assert_eq!(
+        body_of(&get("/files/99999999999999999999999/meta")),
+        "file:99999999999999999999999/meta"
+    );
+}
+
+#[test]
+fn test_uuid_constraint() {
+    assert_eq!(
+        body_of(&get("/orders/550e8400-e29b-41d4-a716-446655440000")),
+        "order:550e8400-e29b-41d4-a716-446655440000"
+    );
+    assert_eq!(get("/orders/550e8400e29b41d4a716446655440000").status, 404);
+    assert_eq!(get("/orders/not-a-uuid").status, 404);
+}
+
+#[test]
+fn test_class_constraint() {
+    assert_eq!(body_of(&get("/tags/rust-lang")), "tag:rust-lang");
+    assert_eq!(get("/tags/Rust").status, 404);
+    assert_eq!(get("/tags/").status, 404);
+}
+
+#[test]
+fn test_catch_all_captures_rest_of_path() {
+    assert_eq!(body_of(&get("/files/a.txt")), "file:a.txt");
+    assert_eq!(
+        body_of(&get("/files/docs/2024/a b.pdf")),
+        "file:docs/2024/a b.pdf"
+    );
+    assert_eq!(body_of(&get("/files/docs%2Fa.txt")), "file:docs/a.txt");
+    assert_eq!(body_of(&get("/files/")), "file:");
+    assert_eq!(get("/files").status, 404);
+}
+
+#[test]
+fn test_catch_all_is_tried_last() {
+    assert_eq!(body_of(&get("/files/12/meta")), "meta:12");
+    assert_eq!(body_of(&get("/files/ab/meta")), "file:ab/meta");
+    assert_eq!(body_of(&get("/files/12/meta/x")), "file:12/meta/x");
+}
+
+#[test]
+fn test_openapi_uses_plain_templates_and_constraint_schemas() {
+    let spec = json::try_parse(__mik_schema::json().as_bytes()).unwrap();
+    let paths = spec.get("paths");
+
+    assert!(!paths.get("/users/{id}").is_null());
+    assert!(paths.get("/users/{id:int}").is_null());
+    assert!(!paths.get("/files/{key}").is_null());
+
+    // The pattern constraint overrides the Path type's schema
+    let param = paths.get("/users/{id}").get("get").get("parameters").at(0);
+    assert_eq!(param.get("name").str(), Some("id".to_string()));
+    assert_eq!(param.get("in").str(), Some("path".to_string()));
+    assert_eq!(
+        param.get("schema").get("type").str(),
+        Some("integer".to_string())
+    );
+
+    // Without a Path type, parameters are described from the pattern
+    let schema = paths
+        .get("/orders/{id}")
+        .get("get")
+        .get("parameters")
+        .at(0)
+        .get("schema");
+    assert_eq!(schema.get("format").str(), Some("uuid".to_string()));
+
+    let schema = paths
+        .get("/tags/{slug}")
+        .get("get")
+        .get("parameters")
+        .at(0)
+        .get("schema");
+    assert_eq!(
+        schema.get("pattern").str(),
+        Some("^[a-z0-9-]+$".to_string())
+    );
+
+    let param = paths.get("/files/{key}").get("get").get("parameters").at(0);
+    assert_eq!(param.get("name").str(), Some("key".to_string()));
+    assert_eq!(
+        param.get("schema").get("type").str(),
+        Some("string".to_string())
+    );
+}
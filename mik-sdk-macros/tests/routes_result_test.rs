@@ -0,0 +1,171 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! Handlers returning `Result<T, E>` and other `IntoResponse` types.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, text};
+
+#[derive(Type)]
+struct NewUser {
+    #[field(min = 2)]
+    name: String,
+}
+
+#[derive(Type)]
+struct NewTeam {
+    #[field(min = 3)]
+    name: String,
+    members: Vec<NewUser>,
+}
+
+routes! {
+    GET "/users/{id}" => get_user,
+    POST "/users" => create_user(body: NewUser),
+    POST "/teams" => create_team(body: NewTeam),
+    GET "/status" => status,
+    GET "/upstream" => upstream,
+    GET "/teapot" => teapot,
+}
+
+fn get_user(req: &Request) -> Result<Response, ApiError> {
+    let id: u32 = req
+        .param_or("id", "")
+        .parse()
+        .map_err(|_| ApiError::bad_request("User id must be a number"))?;
+    if id != 1 {
+        return Err(ApiError::not_found("User not found").with_instance(req.path()));
+    }
+    Ok(text("alice"))
+}
+
+fn create_user(user: NewUser, _req: &Request) -> Result<Response, ApiError> {
+    // Field constraints were checked before the handler ran
+    if user.name == "root" {
+        Err(ValidationError::custom(
+            "name",
+            "reserved",
+            "`name` is reserved",
+        ))?;
+    }
+    Ok(text(&user.name))
+}
+
+fn create_team(team: NewTeam, _req: &Request) -> Response {
+    text(&team.name)
+}
+
+fn status(_req: &Request) -> json::JsonValue {
+    json::obj().set("up", json::bool(true))
+}
+
+const fn call_upstream() -> Result<String, http_client::Error> {
+    Err(http_client::Error::Timeout {
+        timeout_ms: Some(50),
+    })
+}
+
+fn upstream(_req: &Request) -> Result<Response, ApiError> {
+    let body = call_upstream()?;
+    Ok(text(&body))
+}
+
+fn teapot(_req: &Request) -> Result<json::JsonValue, ApiError> {
+    Err(ApiError::new(418)
+        .with_type("urn:problem:teapot")
+        .with_meta("brew", "coffee")
+        .with_header("Retry-After", "60"))
+}
+
+#[test]
+fn test_ok_variant_is_the_response() {
+    __mik_test(TestRequest::get("/users/1"))
+        .assert_status(200)
+        .assert_text("alice");
+}
+
+#[test]
+fn test_err_variant_is_problem_json() {
+    __mik_test(TestRequest::get("/users/2"))
+        .assert_status(404)
+        .assert_header("content-type", "application/problem+json")
+        .assert_json_path("title", "Not Found")
+        .assert_json_path("detail", "User not found")
+        .assert_json_path("instance", "/users/2");
+    __mik_test(TestRequest::get("/users/x"))
+        .assert_status(400)
+        .assert_json_path("type", "about:blank");
+}
+
+#[test]
+fn test_question_mark_converts_validation_errors() {
+    __mik_test(TestRequest::post("/users").json(json::obj().set("name", json::str("root"))))
+        .assert_status(422)
+        .assert_json_path("field", "name")
+        .assert_json_path("constraint", "reserved");
+    __mik_test(TestRequest::post("/users").json(json::obj().set("name", json::str("Ada"))))
+        .assert_status(200)
+        .assert_text("Ada");
+}
+
+#[test]
+fn test_body_constraints_are_checked_before_the_handler() {
+    __mik_test(TestRequest::post("/users").json(json::obj().set("name", json::str("A"))))
+        .assert_status(422)
+        .assert_header("content-type", "application/problem+json")
+        .assert_json_path("detail", "`name` must be at least 2")
+        .assert_json_path("errors[0].field", "name")
+        .assert_json_path("errors[0].constraint", "min");
+}
+
+#[test]
+fn test_every_violation_is_listed() {
+    let members = json::arr()
+        .push(json::obj().set("name", json::str("Ada")))
+        .push(json::obj().set("name", json::str("B")));
+    __mik_test(
+        TestRequest::post("/teams").json(
+            json::obj()
+                .set("name", json::str("QA"))
+                .set("members", members),
+        ),
+    )
+    .assert_status(422)
+    .assert_json_path("detail", "2 validation errors")
+    .assert_json_path("errors[0].field", "name")
+    .assert_json_path("errors[1].field", "members[1].name")
+    .assert_json_path("errors[1].message", "`members[1].name` must be at least 2");
+}
+
+#[test]
+fn test_question_mark_converts_http_client_errors() {
+    __mik_test(TestRequest::get("/upstream"))
+        .assert_status(504)
+        .assert_json_path("detail", "Upstream request timed out");
+}
+
+#[test]
+fn test_json_value_is_200() {
+    __mik_test(TestRequest::get("/status"))
+        .assert_status(200)
+        .assert_header("content-type", "application/json")
+        .assert_json_path("up", true);
+}
+
+#[test]
+fn test_api_error_headers_and_meta() {
+    __mik_test(TestRequest::get("/teapot"))
+        .assert_status(418)
+        .assert_header("retry-after", "60")
+        .assert_json_path("type", "urn:problem:teapot")
+        .assert_json_path("brew", "coffee");
+}
@@ -0,0 +1,156 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! `Streaming` and Server-Sent Events responses through `routes!`.
+
+#![allow(dead_code, missing_docs)] // Mock bindings
+
+use mik_sdk::prelude::*;
+use mik_sdk::sse::Event;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest};
+use common::bindings;
+
+routes! {
+    GET "/export" => export,
+    GET "/rows" => rows,
+    GET "/events" => events,
+    GET "/ticks" => ticks,
+
+    group {
+        after: hide_admin,
+        GET "/admin/export" => export,
+    }
+
+    group {
+        after: accept_later,
+        GET "/queued/export" => export,
+    }
+
+    group {
+        after: tag_export,
+        GET "/tagged/export" => export,
+    }
+}
+
+fn export(_req: &Request) -> Streaming {
+    Streaming::new((1..=3).map(|n| format!("{n},row {n}\n"))).content_type("text/csv")
+}
+
+fn rows(_req: &Request) -> Streaming {
+    Streaming::ndjson((1..=2).map(|id| json::obj().set("id", json::int(id))))
+}
+
+fn events(req: &Request) -> handler::Response {
+    sse!(
+        [
+            Event::new().event("status").data("ready\nset"),
+            Event::new().id("2").json(&json::obj().set("progress", json::int(50))),
+        ],
+        headers: { "X-Request-Id": req.trace_id_or("none") }
+    )
+}
+
+fn ticks(_req: &Request) -> Streaming {
+    mik_sdk::sse::stream((1..=2).map(|n| Event::new().id(n.to_string()).data("tick")))
+}
+
+fn hide_admin(_req: &Request, _response: handler::Response) -> handler::Response {
+    not_found!("Nothing here")
+}
+
+fn accept_later(_req: &Request, _response: handler::Response) -> handler::Response {
+    handler::Response {
+        status: 202,
+        headers: vec![],
+        body: None,
+    }
+}
+
+fn tag_export(_req: &Request, mut response: handler::Response) -> handler::Response {
+    response
+        .headers
+        .push(("x-export".to_string(), "v2".to_string()));
+    response
+}
+
+#[test]
+fn test_streamed_chunks_follow_the_head() {
+    let response = Handler::handle(handler::RequestData {
+        method: handler::Method::Get,
+        path: "/export".to_string(),
+        headers: vec![],
+        body: None,
+    });
+    assert_eq!(response.status, 200);
+    assert!(response.body.is_none(), "chunks are not buffered");
+
+    assert!(Handler::take_body_stream().is_some());
+    assert!(Handler::take_body_stream().is_none(), "taken once");
+}
+
+#[test]
+fn test_test_client_joins_chunks() {
+    __mik_test(TestRequest::get("/export"))
+        .assert_status(200)
+        .assert_header("content-type", "text/csv")
+        .assert_text("1,row 1\n2,row 2\n3,row 3\n");
+    __mik_test(TestRequest::get("/rows"))
+        .assert_header("content-type", "application/x-ndjson")
+        .assert_text("{\"id\":1}\n{\"id\":2}\n");
+}
+
+#[test]
+fn test_head_and_replaced_responses_drop_the_stream() {
+    __mik_test(TestRequest::head("/export"))
+        .assert_status(200)
+        .assert_header("content-type", "text/csv")
+        .assert_text("");
+
+    __mik_test(TestRequest::get("/admin/export"))
+        .assert_status(404)
+        .assert_json_path("detail", "Nothing here");
+    assert!(Handler::take_body_stream().is_none());
+}
+
+#[test]
+fn test_after_hook_replacing_the_response_drops_the_stream() {
+    let response = Handler::handle(handler::RequestData {
+        method: handler::Method::Get,
+        path: "/queued/export".to_string(),
+        headers: vec![],
+        body: None,
+    });
+    assert_eq!(response.status, 202);
+    assert!(Handler::take_body_stream().is_none());
+
+    // Editing the streamed response keeps its body, without the internal marker
+    let response = __mik_test(TestRequest::get("/tagged/export"));
+    response
+        .assert_header("x-export", "v2")
+        .assert_text("1,row 1\n2,row 2\n3,row 3\n");
+    assert!(response.header(mik_sdk::stream::__STREAM_MARKER).is_none());
+}
+
+#[test]
+fn test_sse_macro() {
+    __mik_test(TestRequest::get("/events"))
+        .assert_status(200)
+        .assert_header("content-type", "text/event-stream")
+        .assert_header("cache-control", "no-cache")
+        .assert_header("x-request-id", "none")
+        .assert_text("event: status\ndata: ready\ndata: set\n\nid: 2\ndata: {\"progress\":50}\n\n");
+}
+
+#[test]
+fn test_sse_stream() {
+    __mik_test(TestRequest::get("/ticks"))
+        .assert_status(200)
+        .assert_header("content-type", "text/event-stream")
+        .assert_text("id: 1\ndata: tick\n\nid: 2\ndata: tick\n\n");
+}
@@ -0,0 +1,133 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::needless_pass_by_value
+)]
+//! The native `__mik_test` harness generated by `routes!`.
+
+#![allow(dead_code, missing_docs)] // Mock bindings and route input types
+
+use std::collections::HashMap;
+
+use mik_sdk::prelude::*;
+use mik_sdk::testing::TestRequest;
+
+mod common;
+
+use bindings::exports::mik::core::handler::{self, Guest, Response};
+use common::{bindings, text};
+
+#[derive(Path)]
+struct IdPath {
+    id: u32,
+}
+
+#[derive(Query)]
+struct SearchQuery {
+    #[field(default = 1)]
+    page: u32,
+}
+
+#[derive(Type)]
+struct CreateUser {
+    name: String,
+}
+
+routes! {
+    cors: { origins: ["https://app.example.com"] },
+
+    GET "/users" => list_users(query: SearchQuery),
+    POST "/users" => create_user(body: CreateUser),
+    GET "/users/{id}" => get_user(path: IdPath),
+}
+
+fn list_users(query: SearchQuery, _req: &Request) -> Response {
+    handler::Response {
+        status: 200,
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: Some(
+            json::obj()
+                .set("page", json::int(i64::from(query.page)))
+                .set("users", json::arr().push(json::str("alice")))
+                .to_bytes(),
+        ),
+    }
+}
+
+fn create_user(body: CreateUser, req: &Request) -> Response {
+    handler::Response {
+        status: 201,
+        headers: vec![(
+            "x-auth".to_string(),
+            req.header_or("authorization", "").to_string(),
+        )],
+        body: Some(json::obj().set("name", json::str(&body.name)).to_bytes()),
+    }
+}
+
+fn get_user(path: IdPath, _req: &Request) -> Response {
+    text(&format!("user:{}", path.id))
+}
+
+#[test]
+fn test_routes_typed_query() {
+    __mik_test(TestRequest::get("/users?page=3"))
+        .assert_status(200)
+        .assert_header("content-type", "application/json")
+        .assert_json_path("page", 3)
+        .assert_json_path("users[0]", "alice");
+}
+
+#[test]
+fn test_json_body_and_headers() {
+    __mik_test(
+        TestRequest::post("/users")
+            .header("authorization", "Bearer t")
+            .json(json::obj().set("name", json::str("Bob"))),
+    )
+    .assert_status(201)
+    .assert_header("x-auth", "Bearer t")
+    .assert_json_path("name", "Bob");
+}
+
+#[test]
+fn test_input_errors_are_problem_responses() {
+    __mik_test(TestRequest::post("/users").json(json::obj()))
+        .assert_status(400)
+        .assert_header("content-type", "application/problem+json")
+        .assert_json_path("status", 400);
+
+    __mik_test(TestRequest::get("/users/abc")).assert_status(400);
+}
+
+#[test]
+fn test_fallbacks_head_and_cors() {
+    __mik_test(TestRequest::get("/missing"))
+        .assert_status(404)
+        .assert_json_path("detail", "Route not found");
+    __mik_test(TestRequest::delete("/users"))
+        .assert_status(405)
+        .assert_header("allow", "GET, HEAD, POST, OPTIONS");
+
+    let response = __mik_test(TestRequest::head("/users/7"));
+    response
+        .assert_status(200)
+        .assert_header("content-length", "6");
+    assert_eq!(response.body, None);
+
+    __mik_test(TestRequest::get("/users/7").header("origin", "https://app.example.com"))
+        .assert_text("user:7")
+        .assert_header("access-control-allow-origin", "https://app.example.com");
+}
+
+#[test]
+fn test_accepts_sdk_request() {
+    let request = Request::new(
+        Method::Get,
+        "/users/9".to_string(),
+        vec![],
+        None,
+        HashMap::new(),
+    );
+    __mik_test(request).assert_text("user:9");
+}
@@ -16,7 +16,7 @@
 //! Tests for the derive macros (Type, Query, Path) and their generated code.
 //!
 //! These tests verify:
-//! 1. Type derive generates `FromJson`, Validate, and `OpenApiSchema`
+//! 1. Type derive generates `FromJson`, `FromForm`, Validate, and `OpenApiSchema`
 //! 2. Query derive generates `FromQuery`
 //! 3. Path derive generates `FromPath`
 //! 4. Field attributes work correctly
@@ -91,12 +91,74 @@ mod mik_sdk {
                     message: format!("'{field}' must be at most {max}"),
                 }
             }
+
+            pub fn format(field: &str, expected: &str) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: "format".to_string(),
+                    message: format!("'{field}' must be a valid {expected}"),
+                }
+            }
+
+            pub fn pattern(field: &str, pattern: &str) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: "pattern".to_string(),
+                    message: format!("'{field}' must match pattern: {pattern}"),
+                }
+            }
+
+            pub fn custom(field: &str, constraint: &str, message: impl Into<String>) -> Self {
+                Self {
+                    field: field.to_string(),
+                    constraint: constraint.to_string(),
+                    message: message.into(),
+                }
+            }
         }
 
+        // Format and pattern checks are plain functions; use the real ones
+        pub use ::mik_sdk::typed::{formats, pattern};
+
         pub trait FromJson: Sized {
             fn from_json(value: &crate::mik_sdk::json::JsonValue) -> Result<Self, ParseError>;
         }
 
+        #[derive(Default)]
+        pub struct FormData {
+            pub values: Vec<(String, String)>,
+        }
+
+        impl FormData {
+            pub fn value(&self, name: &str) -> Option<&str> {
+                self.values
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.as_str())
+            }
+
+            pub fn values(&self, name: &str) -> Vec<&str> {
+                self.values
+                    .iter()
+                    .filter(|(key, _)| key == name)
+                    .map(|(_, value)| value.as_str())
+                    .collect()
+            }
+
+            pub fn flag(&self, name: &str) -> Result<Option<bool>, ParseError> {
+                match self.value(name) {
+                    None => Ok(None),
+                    Some("on" | "true" | "1") => Ok(Some(true)),
+                    Some("off" | "false" | "0") => Ok(Some(false)),
+                    Some(_) => Err(ParseError::type_mismatch(name, "boolean")),
+                }
+            }
+        }
+
+        pub trait FromForm: Sized {
+            fn from_form(form: &FormData) -> Result<Self, ParseError>;
+        }
+
         pub trait FromQuery: Sized {
             fn from_query(params: &[(String, String)]) -> Result<Self, ParseError>;
         }
@@ -105,8 +167,45 @@ mod mik_sdk {
             fn from_params(params: &HashMap<String, String>) -> Result<Self, ParseError>;
         }
 
+        #[derive(Debug, Clone, Default)]
+        pub struct ValidationErrors {
+            pub errors: Vec<ValidationError>,
+        }
+
+        impl ValidationErrors {
+            pub const fn new() -> Self {
+                Self { errors: Vec::new() }
+            }
+
+            pub fn push(&mut self, error: ValidationError) {
+                self.errors.push(error);
+            }
+
+            pub fn nest(&mut self, path: &str, errors: Self) {
+                self.errors
+                    .extend(errors.errors.into_iter().map(|mut error| {
+                        error.field = format!("{path}.{}", error.field);
+                        error
+                    }));
+            }
+
+            pub fn into_result(self) -> Result<(), Self> {
+                if self.errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+        }
+
         pub trait Validate {
             fn validate(&self) -> Result<(), ValidationError>;
+
+            fn validate_all(&self) -> Result<(), ValidationErrors> {
+                self.validate().map_err(|error| ValidationErrors {
+                    errors: vec![error],
+                })
+            }
         }
 
         pub trait OpenApiSchema {
@@ -211,6 +310,11 @@ mod mik_sdk {
     pub mod json {
         use std::collections::HashMap;
 
+        /// Only used for nested form fields; the mock never parses.
+        pub const fn try_parse(_bytes: &[u8]) -> Option<JsonValue> {
+            None
+        }
+
         #[derive(Clone)]
         pub struct JsonValue {
             data: JsonData,
@@ -647,6 +751,52 @@ fn test_type_derive_vec_field() {
     assert!(json_str.contains("\"items\":[\"go\",\"python\",\"java\"]"));
 }
 
+#[test]
+fn test_type_derive_from_form() {
+    #[derive(Type)]
+    struct Signup {
+        #[field(rename = "user")]
+        name: String,
+        age: Option<u32>,
+        agree: bool,
+        tags: Vec<String>,
+    }
+
+    let form = |pairs: &[(&str, &str)]| mik_sdk::typed::FormData {
+        values: pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+    };
+
+    let signup = <Signup as mik_sdk::typed::FromForm>::from_form(&form(&[
+        ("user", "ada"),
+        ("age", "36"),
+        ("agree", "on"),
+        ("tags", "a"),
+        ("tags", "b"),
+    ]))
+    .unwrap();
+    assert_eq!(signup.name, "ada");
+    assert_eq!(signup.age, Some(36));
+    assert!(signup.agree);
+    assert_eq!(signup.tags, vec!["a", "b"]);
+
+    // Empty optional values are None, missing checkboxes are false
+    let signup =
+        <Signup as mik_sdk::typed::FromForm>::from_form(&form(&[("user", "ada"), ("age", "")]))
+            .unwrap();
+    assert_eq!(signup.age, None);
+    assert!(!signup.agree);
+    assert!(signup.tags.is_empty());
+
+    let err = <Signup as mik_sdk::typed::FromForm>::from_form(&form(&[("age", "1")]));
+    assert_eq!(err.err().unwrap().field, "user");
+    let err =
+        <Signup as mik_sdk::typed::FromForm>::from_form(&form(&[("user", "a"), ("age", "x")]));
+    assert_eq!(err.err().unwrap().field, "age");
+}
+
 /// Test ToJson with nested structs
 #[test]
 fn test_type_derive_nested_to_json() {
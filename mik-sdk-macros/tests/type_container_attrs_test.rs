@@ -0,0 +1,221 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing // serde_json::Value indexing returns Null instead of panicking
+)]
+//! `#[mik(...)]` container attributes: `rename_all`, `deny_unknown_fields`
+//! and `default`, and how they show up in the `OpenAPI` schema.
+
+#![allow(dead_code, missing_docs)] // Test input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::typed::{FormData, FromForm, FromJson, OpenApiSchema, ParseError};
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(rename_all = "camelCase")]
+struct Profile {
+    user_id: String,
+    display_name: Option<String>,
+    #[field(rename = "e-mail", format = "email")]
+    email_address: String,
+    created_at_ms: i64,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(rename_all = "SCREAMING-KEBAB-CASE")]
+enum Level {
+    Info,
+    HighAlert,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(rename_all = "camelCase", tag = "type")]
+enum Notice {
+    PasswordReset { reset_token: String },
+    Welcome,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(deny_unknown_fields)]
+struct Login {
+    username: String,
+    password: String,
+    remember: Option<bool>,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(default, rename_all = "camelCase")]
+struct Settings {
+    theme: String,
+    #[field(min = 10, max = 100)]
+    page_size: i32,
+    beta: Option<bool>,
+    tags: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: "light".to_string(),
+            page_size: 25,
+            beta: Some(false),
+            tags: vec!["new".to_string()],
+        }
+    }
+}
+
+// `#[schema(...)]` belongs to utoipa and is left alone
+#[derive(Type, utoipa::ToSchema, Debug, PartialEq)]
+#[mik(rename_all = "camelCase")]
+#[schema(title = "Contact card")]
+struct Contact {
+    #[schema(example = "Ann")]
+    first_name: String,
+}
+
+#[derive(Type)]
+struct Account {
+    login: Login,
+}
+
+fn parse<T: FromJson>(json: &str) -> Result<T, ParseError> {
+    T::from_json(&json::try_parse(json.as_bytes()).unwrap())
+}
+
+fn schema<T: OpenApiSchema>() -> serde_json::Value {
+    serde_json::from_str(T::openapi_schema()).unwrap()
+}
+
+#[test]
+fn test_rename_all_fields() {
+    let profile: Profile =
+        parse(r#"{"userId":"u1","displayName":"Ann","e-mail":"ann@example.com","createdAtMs":5}"#)
+            .unwrap();
+    assert_eq!(profile.user_id, "u1");
+    assert_eq!(profile.display_name.as_deref(), Some("Ann"));
+    assert_eq!(
+        profile.to_json().to_string(),
+        r#"{"createdAtMs":5,"displayName":"Ann","e-mail":"ann@example.com","userId":"u1"}"#
+    );
+
+    // The Rust names are no longer accepted
+    assert_eq!(
+        parse::<Profile>(r#"{"user_id":"u1","e-mail":"a@b.co","createdAtMs":1}"#)
+            .unwrap_err()
+            .field(),
+        "userId"
+    );
+
+    let schema = schema::<Profile>();
+    assert_eq!(
+        schema["required"],
+        serde_json::json!(["userId", "e-mail", "createdAtMs"])
+    );
+    assert_eq!(schema["properties"]["e-mail"]["format"], "email");
+}
+
+#[test]
+fn test_rename_all_variants() {
+    assert_eq!(Level::HighAlert.to_json().to_string(), r#""HIGH-ALERT""#);
+    assert_eq!(parse::<Level>(r#""INFO""#).unwrap(), Level::Info);
+    assert_eq!(
+        schema::<Level>()["enum"],
+        serde_json::json!(["INFO", "HIGH-ALERT"])
+    );
+
+    // On enums only variant names change, as with serde
+    let notice = Notice::PasswordReset {
+        reset_token: "t".to_string(),
+    };
+    assert_eq!(
+        notice.to_json().to_string(),
+        r#"{"reset_token":"t","type":"passwordReset"}"#
+    );
+    assert_eq!(
+        parse::<Notice>(r#"{"type":"welcome"}"#).unwrap(),
+        Notice::Welcome
+    );
+}
+
+#[test]
+fn test_derives_next_to_utoipa() {
+    let contact: Contact = parse(r#"{"firstName":"Ann"}"#).unwrap();
+    assert_eq!(contact.to_json().to_string(), r#"{"firstName":"Ann"}"#);
+    assert_eq!(
+        schema::<Contact>()["required"],
+        serde_json::json!(["firstName"])
+    );
+
+    let utoipa_schema = serde_json::to_value(<Contact as utoipa::PartialSchema>::schema()).unwrap();
+    assert_eq!(utoipa_schema["title"], "Contact card");
+}
+
+#[test]
+fn test_deny_unknown_fields() {
+    assert!(parse::<Login>(r#"{"username":"ann","password":"pw","remember":true}"#).is_ok());
+
+    let err = parse::<Login>(r#"{"username":"ann","password":"pw","admin":true,"role":"x"}"#)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::unknown_fields(vec!["admin".to_string(), "role".to_string()])
+    );
+    assert_eq!(err.to_string(), "unknown fields `admin`, `role`");
+
+    // Nested types check their own keys
+    assert!(parse::<Account>(r#"{"login":{"username":"a","password":"b","otp":"1"}}"#).is_err());
+
+    let schema = schema::<Login>();
+    assert_eq!(schema["additionalProperties"], false);
+    let parsed: openapiv3::Schema = serde_json::from_value(schema).unwrap();
+    let openapiv3::SchemaKind::Type(openapiv3::Type::Object(object)) = parsed.schema_kind else {
+        panic!("expected an object schema");
+    };
+    assert_eq!(
+        object.additional_properties,
+        Some(openapiv3::AdditionalProperties::Any(false))
+    );
+}
+
+#[test]
+fn test_default_fills_missing_fields() {
+    assert_eq!(parse::<Settings>("{}").unwrap(), Settings::default());
+
+    let settings: Settings = parse(r#"{"theme":"dark","beta":null}"#).unwrap();
+    assert_eq!(
+        settings,
+        Settings {
+            theme: "dark".to_string(),
+            beta: None,
+            ..Settings::default()
+        }
+    );
+
+    // Only objects get defaults
+    for json in ["42", "[1,2]", "\"dark\"", "null"] {
+        assert_eq!(
+            parse::<Settings>(json).unwrap_err(),
+            ParseError::type_mismatch("value", "object")
+        );
+    }
+
+    // Present values are still type checked and validated
+    assert_eq!(
+        parse::<Settings>(r#"{"pageSize":"big"}"#)
+            .unwrap_err()
+            .field(),
+        "pageSize"
+    );
+    let small: Settings = parse(r#"{"pageSize":5}"#).unwrap();
+    assert_eq!(small.validate().unwrap_err().constraint(), "min");
+
+    let form = FormData::default().with_value("theme", "dark");
+    let settings = Settings::from_form(&form).unwrap();
+    assert_eq!(settings.theme, "dark");
+    assert_eq!(settings.page_size, 25);
+
+    // Nothing is required
+    let schema = schema::<Settings>();
+    assert!(schema.get("required").is_none());
+    assert_eq!(schema["properties"]["pageSize"]["minimum"], 10);
+}
@@ -0,0 +1,317 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing // serde_json::Value indexing returns Null instead of panicking
+)]
+//! Data-carrying enums in `#[derive(Type)]`: the four tagging representations,
+//! validation of variant fields and the `OpenAPI` `oneOf` schemas.
+
+#![allow(dead_code, missing_docs)] // Test input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::typed::{FromJson, OpenApiSchema};
+
+#[derive(Type, Debug, PartialEq)]
+struct Iban {
+    #[field(min = 15, max = 34)]
+    iban: String,
+}
+
+// Externally tagged (default)
+#[derive(Type, Debug, PartialEq)]
+enum Payment {
+    Card {
+        #[field(min = 4, max = 4)]
+        last4: String,
+        expires: Option<String>,
+    },
+    Transfer(Iban),
+    Cash,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(tag = "kind")]
+enum Event {
+    #[field(rename = "signed_up")]
+    SignUp {
+        #[field(format = "email")]
+        email: String,
+    },
+    Refund(Iban),
+    Ping,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(tag = "t", content = "c")]
+enum Message {
+    Text(String),
+    Move { x: i32, y: i32 },
+    Quit,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(untagged)]
+enum Contact {
+    Email {
+        #[field(format = "email")]
+        email: String,
+    },
+    Phone {
+        phone: String,
+    },
+    Nobody,
+}
+
+#[derive(Type)]
+struct Checkout {
+    payments: Vec<Payment>,
+}
+
+fn parse<T: FromJson>(json: &str) -> Result<T, mik_sdk::typed::ParseError> {
+    T::from_json(&json::try_parse(json.as_bytes()).unwrap())
+}
+
+fn card(last4: &str) -> Payment {
+    Payment::Card {
+        last4: last4.to_string(),
+        expires: None,
+    }
+}
+
+#[test]
+fn test_externally_tagged_round_trip() {
+    let cases = [
+        (card("4242"), r#"{"card":{"expires":null,"last4":"4242"}}"#),
+        (
+            Payment::Transfer(Iban {
+                iban: "DE89370400440532013000".to_string(),
+            }),
+            r#"{"transfer":{"iban":"DE89370400440532013000"}}"#,
+        ),
+        (Payment::Cash, r#""cash""#),
+    ];
+    for (value, json) in cases {
+        assert_eq!(value.to_json().to_string(), json);
+        assert_eq!(parse::<Payment>(json).unwrap(), value);
+    }
+
+    let err = parse::<Payment>(r#"{"cheque":{}}"#).unwrap_err();
+    assert!(err.to_string().contains("unknown enum variant \"cheque\""));
+    assert!(parse::<Payment>(r#"{"card":{"last4":"1"},"cash":null}"#).is_err());
+    assert!(parse::<Payment>(r#""card""#).is_err());
+}
+
+#[test]
+fn test_internally_tagged_round_trip() {
+    let cases = [
+        (
+            Event::SignUp {
+                email: "a@example.com".to_string(),
+            },
+            r#"{"email":"a@example.com","kind":"signed_up"}"#,
+        ),
+        (
+            Event::Refund(Iban {
+                iban: "DE89370400440532013000".to_string(),
+            }),
+            r#"{"iban":"DE89370400440532013000","kind":"refund"}"#,
+        ),
+        (Event::Ping, r#"{"kind":"ping"}"#),
+    ];
+    for (value, json) in cases {
+        assert_eq!(value.to_json().to_string(), json);
+        assert_eq!(parse::<Event>(json).unwrap(), value);
+    }
+
+    assert_eq!(
+        parse::<Event>(r#"{"email":"a@example.com"}"#)
+            .unwrap_err()
+            .field(),
+        "kind"
+    );
+    assert!(parse::<Event>(r#"{"kind":1}"#).is_err());
+    assert_eq!(
+        parse::<Event>(r#"{"kind":"sign_up"}"#).unwrap_err().field(),
+        "kind"
+    );
+}
+
+#[test]
+fn test_adjacently_tagged_round_trip() {
+    let cases = [
+        (Message::Text("hi".to_string()), r#"{"c":"hi","t":"text"}"#),
+        (
+            Message::Move { x: 1, y: -2 },
+            r#"{"c":{"x":1,"y":-2},"t":"move"}"#,
+        ),
+        (Message::Quit, r#"{"t":"quit"}"#),
+    ];
+    for (value, json) in cases {
+        assert_eq!(value.to_json().to_string(), json);
+        assert_eq!(parse::<Message>(json).unwrap(), value);
+    }
+    assert!(parse::<Message>(r#"{"t":"move","c":{"x":1}}"#).is_err());
+}
+
+#[test]
+fn test_untagged_picks_first_matching_variant() {
+    assert_eq!(
+        parse::<Contact>(r#"{"email":"a@example.com"}"#).unwrap(),
+        Contact::Email {
+            email: "a@example.com".to_string()
+        }
+    );
+    assert_eq!(
+        parse::<Contact>(r#"{"phone":"555"}"#).unwrap(),
+        Contact::Phone {
+            phone: "555".to_string()
+        }
+    );
+    assert_eq!(parse::<Contact>("null").unwrap(), Contact::Nobody);
+    assert_eq!(
+        Contact::Phone {
+            phone: "555".to_string()
+        }
+        .to_json()
+        .to_string(),
+        r#"{"phone":"555"}"#
+    );
+    // As with serde, untagged unit variants are `null`
+    assert_eq!(Contact::Nobody.to_json().to_string(), "null");
+    assert!(parse::<Contact>(r#""nobody""#).is_err());
+
+    let err = parse::<Contact>(r#"{"fax":"555"}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "value does not match any variant of Contact"
+    );
+}
+
+#[test]
+fn test_variant_fields_are_validated() {
+    assert!(card("4242").validate().is_ok());
+    assert!(Payment::Cash.validate().is_ok());
+
+    // Externally tagged errors are reported under the variant key
+    let err = card("42").validate().unwrap_err();
+    assert_eq!(err.field(), "card.last4");
+    assert_eq!(err.constraint(), "min");
+
+    let transfer = Payment::Transfer(Iban {
+        iban: "DE89".to_string(),
+    });
+    assert_eq!(transfer.validate().unwrap_err().field(), "transfer.iban");
+
+    // Internally tagged fields sit next to the tag
+    let event = Event::SignUp {
+        email: "nope".to_string(),
+    };
+    assert_eq!(event.validate().unwrap_err().field(), "email");
+    let refund = Event::Refund(Iban {
+        iban: "DE89".to_string(),
+    });
+    assert_eq!(refund.validate_all().unwrap_err().field("iban").len(), 1);
+}
+
+#[test]
+fn test_validate_all_walks_enums_in_collections() {
+    let checkout = Checkout {
+        payments: vec![
+            card("4242"),
+            card("1"),
+            Payment::Transfer(Iban {
+                iban: "DE89".to_string(),
+            }),
+        ],
+    };
+    let errors = checkout.validate_all().unwrap_err();
+    let fields: Vec<&str> = errors
+        .iter()
+        .map(mik_sdk::typed::ValidationError::field)
+        .collect();
+    assert_eq!(
+        fields,
+        ["payments[1].card.last4", "payments[2].transfer.iban"]
+    );
+}
+
+#[test]
+fn test_openapi_one_of_with_discriminator() {
+    let schema: serde_json::Value = serde_json::from_str(Event::openapi_schema()).unwrap();
+    assert_eq!(
+        schema["oneOf"][0]["$ref"],
+        "#/components/schemas/EventSignUp"
+    );
+    assert_eq!(schema["discriminator"]["propertyName"], "kind");
+    assert_eq!(
+        schema["discriminator"]["mapping"]["refund"],
+        "#/components/schemas/EventRefund"
+    );
+
+    // Variant components, and the types they use, come with the enum
+    let components: serde_json::Value =
+        serde_json::from_str(&format!("{{{}}}", Event::nested_schemas())).unwrap();
+    assert_eq!(
+        components["EventSignUp"]["properties"]["kind"]["enum"][0],
+        "signed_up"
+    );
+    assert_eq!(components["EventSignUp"]["required"][0], "kind");
+    assert_eq!(
+        components["EventRefund"]["allOf"][0]["$ref"],
+        "#/components/schemas/Iban"
+    );
+    assert_eq!(components["Iban"]["properties"]["iban"]["minLength"], 15);
+
+    for (name, component) in components.as_object().unwrap() {
+        let parsed: Result<openapiv3::ReferenceOr<openapiv3::Schema>, _> =
+            serde_json::from_value(component.clone());
+        assert!(parsed.is_ok(), "{name} is not a valid schema");
+    }
+    let parsed: openapiv3::Schema = serde_json::from_value(schema).unwrap();
+    assert!(matches!(
+        parsed.schema_kind,
+        openapiv3::SchemaKind::OneOf { .. }
+    ));
+}
+
+#[test]
+fn test_openapi_representations() {
+    let components: serde_json::Value =
+        serde_json::from_str(&format!("{{{}}}", Payment::nested_schemas())).unwrap();
+    assert_eq!(components["PaymentCash"]["enum"][0], "cash");
+    assert_eq!(components["PaymentCard"]["required"][0], "card");
+    assert_eq!(
+        components["PaymentCard"]["properties"]["card"]["properties"]["last4"]["maxLength"],
+        4
+    );
+    assert!(!Payment::openapi_schema().contains("discriminator"));
+
+    let components: serde_json::Value =
+        serde_json::from_str(&format!("{{{}}}", Message::nested_schemas())).unwrap();
+    assert_eq!(
+        components["MessageMove"]["required"],
+        serde_json::json!(["t", "c"])
+    );
+    assert_eq!(
+        components["MessageText"]["properties"]["c"]["type"],
+        "string"
+    );
+
+    let components: serde_json::Value =
+        serde_json::from_str(&format!("{{{}}}", Contact::nested_schemas())).unwrap();
+    assert_eq!(components["ContactPhone"]["required"][0], "phone");
+    assert_eq!(
+        components["ContactNobody"]["enum"],
+        serde_json::json!([null])
+    );
+    let nobody: openapiv3::Schema =
+        serde_json::from_value(components["ContactNobody"].clone()).unwrap();
+    assert!(nobody.schema_data.nullable);
+
+    // Plain unit enums stay string enums
+    let checkout: serde_json::Value = serde_json::from_str(Checkout::openapi_schema()).unwrap();
+    assert_eq!(
+        checkout["properties"]["payments"]["items"]["$ref"],
+        "#/components/schemas/Payment"
+    );
+}
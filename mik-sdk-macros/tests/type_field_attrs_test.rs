@@ -0,0 +1,277 @@
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing // serde_json::Value indexing returns Null instead of panicking
+)]
+//! `#[field(flatten)]`, `#[field(skip)]` and `#[field(skip_if)]`, and the
+//! `OpenAPI` schemas of types that use them.
+
+#![allow(dead_code, missing_docs)] // Test input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::typed::{FormData, FromForm, FromJson, OpenApiSchema, ParseError};
+
+#[derive(Type, Debug, PartialEq)]
+struct Audit {
+    created_by: String,
+    #[field(format = "date-time")]
+    created_at: String,
+}
+
+#[derive(Type, Debug, PartialEq)]
+struct Page {
+    #[field(min = 1)]
+    page: i32,
+    total: i32,
+}
+
+#[derive(Type, Debug, PartialEq)]
+struct Document {
+    #[field(min = 1)]
+    title: String,
+    #[field(flatten)]
+    audit: Audit,
+    #[field(skip)]
+    cache_key: String,
+    #[field(skip_if = "Option::is_none")]
+    summary: Option<String>,
+    #[field(skip_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[derive(Type, Debug, PartialEq)]
+struct Listing {
+    items: Vec<Document>,
+    #[field(flatten)]
+    page: Page,
+}
+
+#[derive(Type, Debug, PartialEq)]
+#[mik(tag = "kind")]
+enum Change {
+    Edited {
+        #[field(flatten)]
+        audit: Audit,
+        #[field(skip_if = "Option::is_none")]
+        note: Option<String>,
+    },
+    Deleted,
+}
+
+#[derive(Type, Debug, PartialEq)]
+struct Event {
+    id: String,
+    #[field(flatten)]
+    change: Change,
+}
+
+fn parse<T: FromJson>(json: &str) -> Result<T, ParseError> {
+    T::from_json(&json::try_parse(json.as_bytes()).unwrap())
+}
+
+fn audit() -> Audit {
+    Audit {
+        created_by: "ann".to_string(),
+        created_at: "2024-05-01T09:30:00Z".to_string(),
+    }
+}
+
+fn document(title: &str) -> Document {
+    Document {
+        title: title.to_string(),
+        audit: audit(),
+        cache_key: String::new(),
+        summary: None,
+        tags: Vec::new(),
+    }
+}
+
+#[test]
+fn test_flatten_round_trip() {
+    assert_eq!(
+        document("Q2").to_json().to_string(),
+        r#"{"created_at":"2024-05-01T09:30:00Z","created_by":"ann","title":"Q2"}"#
+    );
+    let json = r#"{"created_at":"2024-05-01T09:30:00Z","created_by":"ann","tags":[],"title":"Q2"}"#;
+    assert_eq!(parse::<Document>(json).unwrap(), document("Q2"));
+
+    // Missing flattened keys are reported like the parent's own
+    assert_eq!(
+        parse::<Document>(r#"{"title":"Q2","tags":[],"created_by":"ann"}"#)
+            .unwrap_err()
+            .field(),
+        "created_at"
+    );
+
+    let form = FormData::default()
+        .with_value("title", "Q2")
+        .with_value("created_by", "ann")
+        .with_value("created_at", "2024-05-01T09:30:00Z");
+    assert_eq!(Document::from_form(&form).unwrap(), document("Q2"));
+}
+
+#[test]
+fn test_skip_and_skip_if() {
+    // Skipped fields are neither read nor written
+    let doc: Document = parse(
+        r#"{"title":"Q2","tags":[],"created_by":"ann","created_at":"2024-05-01T09:30:00Z","cache_key":"x"}"#,
+    )
+    .unwrap();
+    assert_eq!(doc.cache_key, "");
+
+    let doc = Document {
+        cache_key: "internal".to_string(),
+        summary: Some("numbers".to_string()),
+        tags: vec!["finance".to_string()],
+        ..document("Q2")
+    };
+    let json = doc.to_json();
+    assert!(json.get("cache_key").is_null());
+    assert_eq!(json.get("summary").str().as_deref(), Some("numbers"));
+    assert_eq!(json.get("tags").len(), Some(1));
+
+    // `skip_if` only affects output: `tags` is still required when parsing
+    let err = parse::<Document>(
+        r#"{"title":"Q2","created_by":"ann","created_at":"2024-05-01T09:30:00Z"}"#,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ParseError::TypeMismatch { .. }));
+}
+
+#[test]
+fn test_required_matches_parsing() {
+    let schema: serde_json::Value = serde_json::from_str(Document::openapi_schema()).unwrap();
+    let own = &schema["allOf"][0];
+    let required = own["required"].as_array().unwrap();
+    let full = serde_json::json!({
+        "title": "Q2",
+        "summary": "numbers",
+        "tags": ["finance"],
+        "created_by": "ann",
+        "created_at": "2024-05-01T09:30:00Z",
+    });
+    assert!(parse::<Document>(&full.to_string()).is_ok());
+
+    for key in own["properties"].as_object().unwrap().keys() {
+        let mut input = full.clone();
+        input.as_object_mut().unwrap().remove(key);
+        assert_eq!(
+            parse::<Document>(&input.to_string()).is_err(),
+            required.contains(&serde_json::json!(key)),
+            "{key}"
+        );
+    }
+}
+
+#[test]
+fn test_flattened_fields_are_validated() {
+    let doc = Document {
+        audit: Audit {
+            created_at: "yesterday".to_string(),
+            ..audit()
+        },
+        ..document("")
+    };
+    assert_eq!(doc.validate().unwrap_err().field(), "title");
+    let errors = doc.validate_all().unwrap_err();
+    let fields: Vec<&str> = errors
+        .iter()
+        .map(mik_sdk::typed::ValidationError::field)
+        .collect();
+    assert_eq!(fields, ["title", "created_at"]);
+
+    let listing = Listing {
+        items: vec![document("ok"), document("")],
+        page: Page { page: 0, total: 2 },
+    };
+    let errors = listing.validate_all().unwrap_err();
+    let fields: Vec<&str> = errors
+        .iter()
+        .map(mik_sdk::typed::ValidationError::field)
+        .collect();
+    assert_eq!(fields, ["items[1].title", "page"]);
+}
+
+#[test]
+fn test_flatten_in_enum_variant() {
+    let change = Change::Edited {
+        audit: audit(),
+        note: None,
+    };
+    let json = r#"{"created_at":"2024-05-01T09:30:00Z","created_by":"ann","kind":"edited"}"#;
+    assert_eq!(change.to_json().to_string(), json);
+    assert_eq!(parse::<Change>(json).unwrap(), change);
+}
+
+#[test]
+fn test_flatten_tagged_enum() {
+    let event = Event {
+        id: "e1".to_string(),
+        change: Change::Edited {
+            audit: audit(),
+            note: Some("typo".to_string()),
+        },
+    };
+    let json = r#"{"created_at":"2024-05-01T09:30:00Z","created_by":"ann","id":"e1","kind":"edited","note":"typo"}"#;
+    assert_eq!(event.to_json().to_string(), json);
+    assert_eq!(parse::<Event>(json).unwrap(), event);
+
+    let form = FormData::default()
+        .with_value("id", "e1")
+        .with_value("kind", "edited")
+        .with_value("note", "typo")
+        .with_value("created_by", "ann")
+        .with_value("created_at", "2024-05-01T09:30:00Z");
+    assert_eq!(Event::from_form(&form).unwrap(), event);
+
+    let form = FormData::default()
+        .with_value("id", "e2")
+        .with_value("kind", "deleted");
+    assert_eq!(Event::from_form(&form).unwrap().change, Change::Deleted);
+
+    // The tag is required and checked like in JSON
+    let form = FormData::default().with_value("id", "e3");
+    assert_eq!(Event::from_form(&form).unwrap_err().field(), "kind");
+    let form = form.with_value("kind", "moved");
+    assert_eq!(Event::from_form(&form).unwrap_err().field(), "kind");
+
+    let schema: serde_json::Value = serde_json::from_str(Event::openapi_schema()).unwrap();
+    assert_eq!(schema["allOf"][1]["$ref"], "#/components/schemas/Change");
+}
+
+#[test]
+fn test_openapi_schema() {
+    let schema: serde_json::Value = serde_json::from_str(Document::openapi_schema()).unwrap();
+    let own = &schema["allOf"][0];
+    // `tags` has to be sent even though `skip_if` may leave it out of output
+    assert_eq!(own["required"], serde_json::json!(["title", "tags"]));
+    assert!(own["properties"]["cache_key"].is_null());
+    assert!(own["properties"]["tags"].is_object());
+    assert_eq!(schema["allOf"][1]["$ref"], "#/components/schemas/Audit");
+
+    let nested: serde_json::Value =
+        serde_json::from_str(&format!("{{{}}}", Document::nested_schemas())).unwrap();
+    assert_eq!(
+        nested["Audit"]["properties"]["created_at"]["format"],
+        "date-time"
+    );
+
+    let parsed: openapiv3::Schema = serde_json::from_value(schema).unwrap();
+    assert!(matches!(
+        parsed.schema_kind,
+        openapiv3::SchemaKind::AllOf { .. }
+    ));
+
+    // Types without flattened fields stay plain objects
+    let schema: serde_json::Value = serde_json::from_str(Page::openapi_schema()).unwrap();
+    assert_eq!(schema["type"], "object");
+
+    let components: serde_json::Value =
+        serde_json::from_str(&format!("{{{}}}", Change::nested_schemas())).unwrap();
+    let edited = &components["ChangeEdited"];
+    assert_eq!(
+        edited["allOf"][0]["properties"]["kind"]["enum"][0],
+        "edited"
+    );
+    assert_eq!(edited["allOf"][1]["$ref"], "#/components/schemas/Audit");
+}
@@ -0,0 +1,310 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+//! `#[field(format)]` and `#[field(pattern)]` checks generated by `#[derive(Type)]`,
+//! and `validate_all` collecting every violation.
+
+#![allow(dead_code, missing_docs)] // Test input types
+
+use mik_sdk::prelude::*;
+use mik_sdk::typed::{FromJson, ValidationError, ValidationErrors};
+
+#[derive(Type)]
+struct Signup {
+    #[field(format = "email")]
+    email: String,
+    #[field(format = "uuid")]
+    invite: Option<String>,
+    #[field(format = "date")]
+    birthday: Option<String>,
+    #[field(format = "date-time")]
+    created_at: Option<String>,
+    #[field(format = "uri")]
+    website: Option<String>,
+    #[field(format = "hostname")]
+    host: Option<String>,
+    #[field(format = "ipv4")]
+    ipv4: Option<String>,
+    #[field(format = "ipv6")]
+    ipv6: Option<String>,
+    // Not checked at runtime, only documented
+    #[field(format = "password")]
+    password: Option<String>,
+}
+
+#[derive(Type)]
+struct Slugged {
+    #[field(pattern = r"^[a-z0-9]+(-[a-z0-9]+)*$", min = 3)]
+    slug: String,
+    #[field(pattern = r"^\+?\d{1,3}([ .-]?\d{2,4}){2,4}$")]
+    phone: Option<String>,
+    // Unanchored: only has to contain a digit
+    #[field(pattern = r"\d")]
+    code: Option<String>,
+    #[field(pattern = "^(draft|published)$")]
+    tags: Vec<String>,
+}
+
+#[derive(Type)]
+struct Tagged {
+    #[field(pattern = r"^([a-z]+,)*[a-z]+$")]
+    tags: String,
+    #[field(pattern = r"^(\w+\s?)+$")]
+    title: String,
+    #[field(pattern = r"^(a*)*b$")]
+    code: Option<String>,
+}
+
+fn signup(email: &str) -> Signup {
+    Signup {
+        email: email.to_string(),
+        invite: None,
+        birthday: None,
+        created_at: None,
+        website: None,
+        host: None,
+        ipv4: None,
+        ipv6: None,
+        password: None,
+    }
+}
+
+fn slugged(slug: &str) -> Slugged {
+    Slugged {
+        slug: slug.to_string(),
+        phone: None,
+        code: None,
+        tags: vec!["anything".to_string()],
+    }
+}
+
+#[test]
+fn test_valid_formats_pass() {
+    let input = Signup {
+        invite: Some("550e8400-e29b-41d4-a716-446655440000".to_string()),
+        birthday: Some("1990-02-28".to_string()),
+        created_at: Some("2024-05-01T09:30:00Z".to_string()),
+        website: Some("https://example.com/me".to_string()),
+        host: Some("api.example.com".to_string()),
+        ipv4: Some("10.0.0.1".to_string()),
+        ipv6: Some("fe80::1".to_string()),
+        password: Some("not checked".to_string()),
+        ..signup("alice@example.com")
+    };
+    assert!(input.validate().is_ok());
+}
+
+#[test]
+fn test_invalid_format_is_rejected() {
+    assert_eq!(
+        signup("not-an-email").validate().unwrap_err(),
+        ValidationError::format("email", "email")
+    );
+
+    let base = || signup("alice@example.com");
+    let cases = [
+        (
+            "invite",
+            Signup {
+                invite: Some("1234".to_string()),
+                ..base()
+            },
+        ),
+        (
+            "birthday",
+            Signup {
+                birthday: Some("1990-02-30".to_string()),
+                ..base()
+            },
+        ),
+        (
+            "created_at",
+            Signup {
+                created_at: Some("2024-05-01".to_string()),
+                ..base()
+            },
+        ),
+        (
+            "website",
+            Signup {
+                website: Some("example.com".to_string()),
+                ..base()
+            },
+        ),
+        (
+            "host",
+            Signup {
+                host: Some("-bad-".to_string()),
+                ..base()
+            },
+        ),
+        (
+            "ipv4",
+            Signup {
+                ipv4: Some("10.0.0.256".to_string()),
+                ..base()
+            },
+        ),
+        (
+            "ipv6",
+            Signup {
+                ipv6: Some("10.0.0.1".to_string()),
+                ..base()
+            },
+        ),
+    ];
+    for (field, input) in cases {
+        let err = input.validate().unwrap_err();
+        assert_eq!(err.field(), field);
+        assert_eq!(err.constraint(), "format");
+    }
+}
+
+#[test]
+fn test_pattern_is_enforced() {
+    assert!(slugged("hello-world-42").validate().is_ok());
+    assert_eq!(
+        slugged("Hello World").validate().unwrap_err(),
+        ValidationError::pattern("slug", r"^[a-z0-9]+(-[a-z0-9]+)*$")
+    );
+    assert!(slugged("trailing-").validate().is_err());
+    // Other constraints still run first
+    assert_eq!(slugged("ab").validate().unwrap_err().constraint(), "min");
+}
+
+#[test]
+fn test_optional_and_unanchored_patterns() {
+    let mut input = slugged("ok-slug");
+    input.phone = Some("+1 555 123 4567".to_string());
+    input.code = Some("abc7".to_string());
+    assert!(input.validate().is_ok());
+
+    input.phone = Some("555-CALL-NOW".to_string());
+    assert_eq!(input.validate().unwrap_err().field(), "phone");
+
+    input.phone = None;
+    input.code = Some("none".to_string());
+    assert_eq!(input.validate().unwrap_err().field(), "code");
+}
+
+#[test]
+fn test_long_values_match_repeated_groups() {
+    let mut input = Tagged {
+        tags: vec!["tag"; 600].join(","),
+        title: vec!["word"; 600].join(" "),
+        code: None,
+    };
+    assert!(input.validate().is_ok());
+
+    input.tags.push(',');
+    assert_eq!(input.validate().unwrap_err().constraint(), "pattern");
+
+    // Giving up is reported apart from a mismatch
+    input.tags.pop();
+    input.code = Some(format!("{}c", "a".repeat(40)));
+    let err = input.validate().unwrap_err();
+    assert_eq!(err.field(), "code");
+    assert_eq!(err.constraint(), "pattern_limit");
+}
+
+#[test]
+fn test_parsed_json_is_validated() {
+    let value = json::try_parse(br#"{"email":"bob@example"}"#).unwrap();
+    let input = Signup::from_json(&value).unwrap();
+    assert_eq!(input.validate().unwrap_err().constraint(), "format");
+}
+
+#[derive(Type)]
+struct LineItem {
+    #[field(min = 1)]
+    name: String,
+    #[field(min = 1, max = 99)]
+    quantity: i32,
+}
+
+#[derive(Type)]
+struct Shipping {
+    #[field(min = 2)]
+    city: String,
+}
+
+#[derive(Type)]
+struct Order {
+    #[field(format = "email")]
+    email: String,
+    items: Vec<LineItem>,
+    shipping: Option<Shipping>,
+    gifts: Option<Vec<LineItem>>,
+    batches: Vec<Vec<LineItem>>,
+}
+
+fn item(name: &str, quantity: i32) -> LineItem {
+    LineItem {
+        name: name.to_string(),
+        quantity,
+    }
+}
+
+fn order(items: Vec<LineItem>) -> Order {
+    Order {
+        email: "alice@example.com".to_string(),
+        items,
+        shipping: None,
+        gifts: None,
+        batches: Vec::new(),
+    }
+}
+
+fn paths(errors: &ValidationErrors) -> Vec<(&str, &str)> {
+    errors
+        .iter()
+        .map(|error| (error.field(), error.constraint()))
+        .collect()
+}
+
+#[test]
+fn test_validate_all_passes_valid_values() {
+    assert_eq!(order(vec![item("pen", 2)]).validate_all(), Ok(()));
+}
+
+#[test]
+fn test_validate_all_collects_every_violation() {
+    let input = Order {
+        email: "nope".to_string(),
+        ..order(vec![item("pen", 2), item("", 100)])
+    };
+    let errors = input.validate_all().unwrap_err();
+    assert_eq!(
+        paths(&errors),
+        [
+            ("email", "format"),
+            ("items[1].name", "min"),
+            ("items[1].quantity", "max"),
+        ]
+    );
+    // `validate` still stops at the first violation
+    assert_eq!(input.validate().unwrap_err().field(), "email");
+}
+
+#[test]
+fn test_validate_all_walks_optional_and_nested_collections() {
+    let input = Order {
+        shipping: Some(Shipping {
+            city: "X".to_string(),
+        }),
+        gifts: Some(vec![item("card", 1), item("", 1)]),
+        batches: vec![vec![], vec![item("ok", 1), item("ok", 0)]],
+        ..order(vec![item("pen", 1)])
+    };
+    let errors = input.validate_all().unwrap_err();
+    assert_eq!(
+        paths(&errors),
+        [
+            ("shipping.city", "min"),
+            ("gifts[1].name", "min"),
+            ("batches[1][1].quantity", "min"),
+        ]
+    );
+    assert_eq!(
+        errors.field("shipping.city").first().unwrap().to_string(),
+        "`shipping.city` must be at least 2"
+    );
+}
@@ -0,0 +1,11 @@
+use mik_sdk_macros::Type;
+
+// Error: deny_unknown_fields and default only apply to structs
+#[derive(Type)]
+#[mik(deny_unknown_fields)]
+enum Status {
+    Active,
+    Inactive,
+}
+
+fn main() {}
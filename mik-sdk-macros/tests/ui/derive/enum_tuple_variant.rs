@@ -0,0 +1,9 @@
+use mik_sdk_macros::Type;
+
+// Error: tuple variants must hold exactly one value
+#[derive(Type)]
+enum Shape {
+    Point(i32, i32),
+}
+
+fn main() {}
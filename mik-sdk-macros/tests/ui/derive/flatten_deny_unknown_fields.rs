@@ -0,0 +1,17 @@
+use mik_sdk_macros::Type;
+
+#[derive(Type)]
+struct Audit {
+    created_by: String,
+}
+
+// Error: the flattened keys aren't known, so unknown ones can't be rejected
+#[derive(Type)]
+#[mik(deny_unknown_fields)]
+struct Document {
+    title: String,
+    #[field(flatten)]
+    audit: Audit,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use mik_sdk_macros::Type;
+
+#[derive(Type)]
+struct Card {
+    last4: String,
+}
+
+#[derive(Type)]
+enum Payment {
+    Card(Card),
+    Cash,
+}
+
+// Error: only internally tagged enums can be read from a form
+#[derive(Type)]
+struct Order {
+    id: String,
+    #[field(flatten)]
+    payment: Payment,
+}
+
+fn main() {}
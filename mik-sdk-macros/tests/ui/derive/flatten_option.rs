@@ -0,0 +1,16 @@
+use mik_sdk_macros::Type;
+
+#[derive(Type)]
+struct Audit {
+    created_by: String,
+}
+
+// Error: flatten needs a Type, not an Option of one
+#[derive(Type)]
+struct Document {
+    title: String,
+    #[field(flatten)]
+    audit: Option<Audit>,
+}
+
+fn main() {}
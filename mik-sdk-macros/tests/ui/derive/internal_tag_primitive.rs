@@ -0,0 +1,10 @@
+use mik_sdk_macros::Type;
+
+// Error: the tag has nowhere to go in a string
+#[derive(Type)]
+#[mik(tag = "kind")]
+enum Message {
+    Text(String),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::Type;
+
+// Error: tagging attributes only apply to enums
+#[derive(Type)]
+#[mik(tag = "kind")]
+struct Event {
+    id: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::Type;
+
+// Error: typo in a container attribute
+#[derive(Type)]
+#[mik(tagg = "kind")]
+enum Message {
+    Quit,
+}
+
+fn main() {}
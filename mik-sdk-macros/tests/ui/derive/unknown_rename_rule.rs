@@ -0,0 +1,10 @@
+use mik_sdk_macros::Type;
+
+// Error: rename_all rules are spelled as in serde
+#[derive(Type)]
+#[mik(rename_all = "camelcase")]
+struct Profile {
+    user_id: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::Type;
+
+// Error: Backreferences can't be matched without a regex engine
+#[derive(Type)]
+pub struct Tag {
+    #[field(pattern = r"^(a+)\1$")]
+    pub name: String,
+}
+
+fn main() {}
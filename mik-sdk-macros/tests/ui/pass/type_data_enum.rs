@@ -0,0 +1,43 @@
+use mik_sdk_macros::Type;
+
+#[derive(Type)]
+struct CardDetails {
+    #[field(min = 4, max = 4)]
+    last4: String,
+}
+
+// Externally tagged: struct, newtype and unit variants
+#[derive(Type)]
+enum Payment {
+    Card(CardDetails),
+    Iban { iban: String },
+    Cash,
+}
+
+// Internally tagged
+#[derive(Type)]
+#[mik(tag = "kind")]
+enum Event {
+    Created { id: String },
+    Paid(CardDetails),
+    Closed,
+}
+
+// Adjacently tagged, with primitive newtypes
+#[derive(Type)]
+#[mik(tag = "t", content = "c")]
+enum Message {
+    Text(String),
+    Count(i64),
+    Quit,
+}
+
+// Untagged
+#[derive(Type)]
+#[mik(untagged)]
+enum Id {
+    Number(i64),
+    Name(String),
+}
+
+fn main() {}
@@ -7,6 +7,10 @@ pub struct Constrained {
     pub value: i32,
     #[field(min = 3, max = 50)]
     pub name: String,
+    #[field(format = "email")]
+    pub email: String,
+    #[field(pattern = r"^[a-z0-9-]+$")]
+    pub slug: Option<String>,
 }
 
 fn main() {}
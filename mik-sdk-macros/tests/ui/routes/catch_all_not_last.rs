@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: Catch-all parameter must be the last segment
+routes! {
+    GET "/files/{*key}/meta" => handler,
+}
+
+fn main() {}
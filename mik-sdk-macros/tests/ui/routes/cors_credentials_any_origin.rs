@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: credentials for any origin let every website act as the user
+routes! {
+    cors: { origins: ["*"], credentials: true },
+    GET "/users" => handler,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: cors: is only allowed at the top of routes!
+routes! {
+    group "/admin" {
+        cors: { origins: ["*"] },
+        GET "/users" => handler,
+    }
+}
+
+fn main() {}
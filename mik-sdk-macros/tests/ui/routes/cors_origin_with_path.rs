@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: CORS origins are compared against the Origin header and have no path
+routes! {
+    cors: { origins: ["https://app.example.com/"] },
+    GET "/users" => handler,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: Group prefix must start with '/'
+routes! {
+    group "admin" {
+        GET "/users" => handler,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+fn auth() -> String { String::new() }
+
+// Error: hooks must be declared before the routes of their block
+routes! {
+    GET "/users" => handler,
+    before: [auth],
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: Method tokens cannot contain spaces
+routes! {
+    "MY METHOD" "/users" => home,
+}
+
+fn main() {}
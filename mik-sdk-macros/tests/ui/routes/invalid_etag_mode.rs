@@ -0,0 +1,11 @@
+use mik_sdk_macros::routes;
+
+fn home() -> String { String::new() }
+
+// Error: #[etag] only accepts `weak`
+routes! {
+    #[etag(strong)]
+    GET "/users" => home,
+}
+
+fn main() {}
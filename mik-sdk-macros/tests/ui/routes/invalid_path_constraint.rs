@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: Unknown path parameter constraint (should be int, uuid, or [class]+)
+routes! {
+    GET "/users/{id:itn}" => handler,
+}
+
+fn main() {}
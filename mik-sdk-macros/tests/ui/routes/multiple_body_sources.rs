@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: The body can only be read as JSON, form or multipart - not twice
+routes! {
+    POST "/upload" => handler(body: Meta, multipart: Files),
+}
+
+fn main() {}
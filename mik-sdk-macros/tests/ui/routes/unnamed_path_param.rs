@@ -0,0 +1,10 @@
+use mik_sdk_macros::routes;
+
+fn handler() -> String { String::new() }
+
+// Error: Path parameter needs a name
+routes! {
+    GET "/users/{}" => handler,
+}
+
+fn main() {}
@@ -1,5 +1,6 @@
 #![allow(clippy::too_many_lines)]
-//! Benchmarks for Request operations: query parsing, header lookup, path parameters.
+//! Benchmarks for Request operations: query parsing, header lookup, path parameters,
+//! and `routes!` dispatch.
 //!
 //! Run with: cargo bench -p mik-sdk -- request
 
@@ -665,6 +666,289 @@ fn bench_body_access(c: &mut Criterion) {
     group.finish();
 }
 
+// =============================================================================
+// Route Dispatch Benchmarks (routes! trie vs. linear matching)
+// =============================================================================
+
+// Minimal stand-in for the cargo-component bindings so `routes!` can expand
+// on the native target.
+#[allow(dead_code)]
+mod bindings {
+    pub mod exports {
+        pub mod mik {
+            pub mod core {
+                pub mod handler {
+                    #[derive(Clone)]
+                    pub enum Method {
+                        Get,
+                        Post,
+                        Put,
+                        Patch,
+                        Delete,
+                        Head,
+                        Options,
+                        Trace,
+                        Other(String),
+                    }
+
+                    pub struct RequestData {
+                        pub method: Method,
+                        pub path: String,
+                        pub headers: Vec<(String, String)>,
+                        pub body: Option<Vec<u8>>,
+                    }
+
+                    pub struct Response {
+                        pub status: u16,
+                        pub headers: Vec<(String, String)>,
+                        pub body: Option<Vec<u8>>,
+                    }
+
+                    pub trait GuestBodyStream: 'static {
+                        fn next_chunk(&self) -> Option<Vec<u8>>;
+                    }
+
+                    /// Owned handle to an exported `body-stream` resource.
+                    pub struct BodyStream(Box<dyn GuestBodyStream>);
+
+                    impl BodyStream {
+                        pub fn new<T: GuestBodyStream>(val: T) -> Self {
+                            Self(Box::new(val))
+                        }
+                    }
+
+                    pub trait Guest {
+                        type BodyStream: GuestBodyStream;
+
+                        fn handle(req: RequestData) -> Response;
+
+                        fn take_body_stream() -> Option<BodyStream>;
+                    }
+                }
+            }
+        }
+    }
+
+    macro_rules! export {
+        ($handler:ident with_types_in $bindings:ident) => {
+            const _: $handler = $handler;
+        };
+    }
+    pub(crate) use export;
+}
+
+use bindings::exports::mik::core::handler::{self, Guest};
+
+/// 16 resources x 4 routes = 64 routes, a typical mid-sized service.
+const RESOURCES: [&str; 16] = [
+    "accounts",
+    "invoices",
+    "orders",
+    "products",
+    "customers",
+    "payments",
+    "shipments",
+    "refunds",
+    "coupons",
+    "reviews",
+    "tickets",
+    "webhooks",
+    "reports",
+    "teams",
+    "projects",
+    "users",
+];
+
+mik_sdk::routes! {
+    GET "/api/accounts" => endpoint,
+    POST "/api/accounts" => endpoint,
+    GET "/api/accounts/{id}" => endpoint,
+    PUT "/api/accounts/{id}" => endpoint,
+    GET "/api/invoices" => endpoint,
+    POST "/api/invoices" => endpoint,
+    GET "/api/invoices/{id}" => endpoint,
+    PUT "/api/invoices/{id}" => endpoint,
+    GET "/api/orders" => endpoint,
+    POST "/api/orders" => endpoint,
+    GET "/api/orders/{id}" => endpoint,
+    PUT "/api/orders/{id}" => endpoint,
+    GET "/api/products" => endpoint,
+    POST "/api/products" => endpoint,
+    GET "/api/products/{id}" => endpoint,
+    PUT "/api/products/{id}" => endpoint,
+    GET "/api/customers" => endpoint,
+    POST "/api/customers" => endpoint,
+    GET "/api/customers/{id}" => endpoint,
+    PUT "/api/customers/{id}" => endpoint,
+    GET "/api/payments" => endpoint,
+    POST "/api/payments" => endpoint,
+    GET "/api/payments/{id}" => endpoint,
+    PUT "/api/payments/{id}" => endpoint,
+    GET "/api/shipments" => endpoint,
+    POST "/api/shipments" => endpoint,
+    GET "/api/shipments/{id}" => endpoint,
+    PUT "/api/shipments/{id}" => endpoint,
+    GET "/api/refunds" => endpoint,
+    POST "/api/refunds" => endpoint,
+    GET "/api/refunds/{id}" => endpoint,
+    PUT "/api/refunds/{id}" => endpoint,
+    GET "/api/coupons" => endpoint,
+    POST "/api/coupons" => endpoint,
+    GET "/api/coupons/{id}" => endpoint,
+    PUT "/api/coupons/{id}" => endpoint,
+    GET "/api/reviews" => endpoint,
+    POST "/api/reviews" => endpoint,
+    GET "/api/reviews/{id}" => endpoint,
+    PUT "/api/reviews/{id}" => endpoint,
+    GET "/api/tickets" => endpoint,
+    POST "/api/tickets" => endpoint,
+    GET "/api/tickets/{id}" => endpoint,
+    PUT "/api/tickets/{id}" => endpoint,
+    GET "/api/webhooks" => endpoint,
+    POST "/api/webhooks" => endpoint,
+    GET "/api/webhooks/{id}" => endpoint,
+    PUT "/api/webhooks/{id}" => endpoint,
+    GET "/api/reports" => endpoint,
+    POST "/api/reports" => endpoint,
+    GET "/api/reports/{id}" => endpoint,
+    PUT "/api/reports/{id}" => endpoint,
+    GET "/api/teams" => endpoint,
+    POST "/api/teams" => endpoint,
+    GET "/api/teams/{id}" => endpoint,
+    PUT "/api/teams/{id}" => endpoint,
+    GET "/api/projects" => endpoint,
+    POST "/api/projects" => endpoint,
+    GET "/api/projects/{id}" => endpoint,
+    PUT "/api/projects/{id}" => endpoint,
+    GET "/api/users" => endpoint,
+    POST "/api/users" => endpoint,
+    GET "/api/users/{id}" => endpoint,
+    PUT "/api/users/{id}" => endpoint,
+}
+
+fn endpoint(req: &Request) -> handler::Response {
+    handler::Response {
+        status: 200,
+        headers: vec![],
+        body: Some(req.param_or("id", "").as_bytes().to_vec()),
+    }
+}
+
+/// Route table in the same order as the `routes!` block above.
+fn linear_route_table() -> Vec<(Method, Vec<String>)> {
+    RESOURCES
+        .iter()
+        .flat_map(|r| {
+            [
+                (Method::Get, format!("/api/{r}")),
+                (Method::Post, format!("/api/{r}")),
+                (Method::Get, format!("/api/{r}/{{id}}")),
+                (Method::Put, format!("/api/{r}/{{id}}")),
+            ]
+        })
+        .map(|(method, pattern)| (method, pattern.split('/').map(String::from).collect()))
+        .collect()
+}
+
+/// Linear baseline: routes are tried in declaration order and each candidate
+/// allocates a `HashMap` of params. Patterns are split once up front so only
+/// the matching itself is measured.
+fn linear_dispatch(
+    table: &[(Method, Vec<String>)],
+    method: &Method,
+    raw_path: &str,
+) -> handler::Response {
+    let path = raw_path.split('?').next().unwrap_or(raw_path);
+    let segments: Vec<&str> = path.split('/').collect();
+
+    for (route_method, pattern_segments) in table {
+        if route_method != method {
+            continue;
+        }
+        let matched = (|| -> Option<HashMap<String, String>> {
+            if segments.len() != pattern_segments.len() {
+                return None;
+            }
+            let mut params = HashMap::new();
+            for (seg, pat) in segments.iter().zip(pattern_segments) {
+                if pat.starts_with('{') && pat.ends_with('}') {
+                    let value = mik_sdk::url_decode(seg).unwrap_or_else(|_| (*seg).to_string());
+                    params.insert(pat[1..pat.len() - 1].to_string(), value);
+                } else if seg != pat {
+                    return None;
+                }
+            }
+            Some(params)
+        })();
+
+        if let Some(params) = matched {
+            let req = Request::new(method.clone(), raw_path.to_string(), vec![], None, params);
+            return endpoint(&req);
+        }
+    }
+
+    handler::Response {
+        status: 404,
+        headers: vec![],
+        body: None,
+    }
+}
+
+fn bench_route_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("route_dispatch");
+    let table = linear_route_table();
+
+    let cases = [
+        (
+            "first_route",
+            handler::Method::Get,
+            Method::Get,
+            "/api/accounts",
+        ),
+        (
+            "middle_param",
+            handler::Method::Get,
+            Method::Get,
+            "/api/refunds/42",
+        ),
+        (
+            "last_route",
+            handler::Method::Put,
+            Method::Put,
+            "/api/users/42",
+        ),
+        (
+            "not_found",
+            handler::Method::Get,
+            Method::Get,
+            "/api/unknown/42",
+        ),
+    ];
+
+    for (name, wit_method, method, path) in cases {
+        group.bench_function(format!("trie_{name}"), |b| {
+            b.iter(|| {
+                let response = Handler::handle(handler::RequestData {
+                    method: wit_method.clone(),
+                    path: black_box(path).to_string(),
+                    headers: vec![],
+                    body: None,
+                });
+                black_box(response.status)
+            });
+        });
+
+        group.bench_function(format!("linear_{name}"), |b| {
+            b.iter(|| {
+                let response = linear_dispatch(&table, &method, black_box(path));
+                black_box(response.status)
+            });
+        });
+    }
+
+    group.finish();
+}
+
 // =============================================================================
 // Main
 // =============================================================================
@@ -679,6 +963,7 @@ criterion_group!(
     bench_request_creation,
     bench_content_type_checks,
     bench_body_access,
+    bench_route_dispatch,
 );
 
 criterion_main!(benches);
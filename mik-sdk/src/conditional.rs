@@ -0,0 +1,391 @@
+//! Conditional requests (RFC 9110 §13): entity tags, `304 Not Modified`
+//! and `412 Precondition Failed`.
+//!
+//! Mark a route `#[etag]` in [`routes!`](crate::routes) to tag its responses
+//! and answer matching `If-None-Match` requests with `304`. Handlers can also
+//! check validators themselves:
+//!
+//! ```ignore
+//! fn update_user(body: UpdateUser, req: &Request) -> Result<User, ApiError> {
+//!     let user = load_user(req.param_or("id", ""))?;
+//!     // 412 unless If-Match names the current version
+//!     req.check_preconditions(Some(&ETag::strong(user.version.to_string())), None)?;
+//!     Ok(save_user(user, body))
+//! }
+//! ```
+
+use crate::Request;
+use crate::constants::{HEADER_CONTENT_LENGTH, HEADER_CONTENT_TYPE};
+use crate::response::{IntoResponse, ResponseParts};
+use crate::time;
+
+/// `ETag` response header.
+pub const HEADER_ETAG: &str = "ETag";
+
+/// `Last-Modified` response header.
+pub const HEADER_LAST_MODIFIED: &str = "Last-Modified";
+
+/// `If-None-Match` request header (lowercase for lookups).
+pub const HEADER_IF_NONE_MATCH: &str = "if-none-match";
+
+/// `If-Modified-Since` request header (lowercase for lookups).
+pub const HEADER_IF_MODIFIED_SINCE: &str = "if-modified-since";
+
+/// `If-Match` request header (lowercase for lookups).
+pub const HEADER_IF_MATCH: &str = "if-match";
+
+/// `If-Unmodified-Since` request header (lowercase for lookups).
+pub const HEADER_IF_UNMODIFIED_SINCE: &str = "if-unmodified-since";
+
+/// An entity tag identifying one version of a representation.
+///
+/// Strong tags promise byte-for-byte identical bodies; weak tags (`W/"..."`)
+/// only semantic equivalence, so they never satisfy `If-Match`.
+///
+/// ```
+/// # use mik_sdk::conditional::ETag;
+/// let etag = ETag::from_body(br#"{"id":1}"#);
+/// assert!(!etag.is_weak());
+/// assert_eq!(etag, ETag::from_body(br#"{"id":1}"#));
+///
+/// assert_eq!(ETag::weak("v7").to_string(), r#"W/"v7""#);
+/// assert_eq!(ETag::parse(r#"W/"v7""#), Some(ETag::weak("v7")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    /// A strong tag with the given opaque value (e.g. a version number).
+    ///
+    /// Characters not allowed in an entity tag (`"`, spaces, controls) are dropped.
+    #[must_use]
+    pub fn strong(tag: impl Into<String>) -> Self {
+        let mut tag = tag.into();
+        tag.retain(is_etag_char);
+        Self { tag, weak: false }
+    }
+
+    /// A weak tag with the given opaque value.
+    #[must_use]
+    pub fn weak(tag: impl Into<String>) -> Self {
+        Self {
+            weak: true,
+            ..Self::strong(tag)
+        }
+    }
+
+    /// A strong tag hashed from a response body.
+    ///
+    /// Uses the body length and its 64-bit FNV-1a hash - cheap and stable
+    /// across builds, but not collision resistant against crafted input.
+    #[must_use]
+    pub fn from_body(body: &[u8]) -> Self {
+        Self {
+            tag: format!("{:x}-{:016x}", body.len(), fnv1a(body)),
+            weak: false,
+        }
+    }
+
+    /// The same tag, marked weak.
+    #[must_use]
+    pub fn into_weak(self) -> Self {
+        Self { weak: true, ..self }
+    }
+
+    /// The opaque tag, without quotes or `W/` prefix.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Whether this is a weak tag.
+    #[must_use]
+    pub const fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Parse a single entity tag such as `"abc"` or `W/"abc"`.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match parse_list(value.trim()).as_slice() {
+            [etag] => Some(etag.clone()),
+            _ => None,
+        }
+    }
+
+    /// Strong comparison: both strong with the same tag.
+    fn strong_eq(&self, other: &Self) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Weak comparison: same tag, either may be weak.
+    fn weak_eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl std::fmt::Display for ETag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+/// `etagc` from RFC 9110: visible ASCII except `"`, plus obs-text.
+const fn is_etag_char(c: char) -> bool {
+    c == '!' || (c >= '#' && c <= '~') || c >= '\u{80}'
+}
+
+/// 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Parse a comma-separated list of entity tags, stopping at the first malformed one.
+///
+/// Tags may contain commas, so the list is scanned rather than split.
+fn parse_list(mut rest: &str) -> Vec<ETag> {
+    let mut tags = Vec::new();
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ',']);
+        if rest.is_empty() {
+            return tags;
+        }
+        let (weak, quoted) = rest
+            .strip_prefix("W/")
+            .map_or((false, rest), |quoted| (true, quoted));
+        let Some((tag, tail)) = quoted
+            .strip_prefix('"')
+            .and_then(|open| open.split_once('"'))
+        else {
+            return tags;
+        };
+        if !tag.chars().all(is_etag_char) {
+            return tags;
+        }
+        tags.push(ETag {
+            tag: tag.to_string(),
+            weak,
+        });
+        rest = tail;
+    }
+}
+
+/// Whether an `If-Match` / `If-None-Match` value matches the current tag.
+///
+/// `*` matches any existing representation (`etag` is `Some`).
+pub(crate) fn list_matches(header: &str, etag: Option<&ETag>, weak: bool) -> bool {
+    let Some(etag) = etag else {
+        return false;
+    };
+    if header.trim() == "*" {
+        return true;
+    }
+    parse_list(header).iter().any(|candidate| {
+        if weak {
+            candidate.weak_eq(etag)
+        } else {
+            candidate.strong_eq(etag)
+        }
+    })
+}
+
+/// Whether `last_modified` is no later than an HTTP-date header value.
+///
+/// Unparseable dates are ignored, as RFC 9110 requires.
+pub(crate) fn unmodified_since(header: &str, last_modified: u64) -> Option<bool> {
+    time::parse_http_date(header).map(|since| last_modified <= since)
+}
+
+/// A `304 Not Modified` response carrying `etag`.
+///
+/// ```
+/// # use mik_sdk::conditional::{self, ETag};
+/// let response = conditional::not_modified(&ETag::strong("v7"));
+/// assert_eq!(response.status, 304);
+/// assert!(response.body.is_none());
+/// ```
+#[must_use]
+pub fn not_modified(etag: &ETag) -> ResponseParts {
+    ResponseParts::new(304, vec![(HEADER_ETAG.to_string(), etag.to_string())], None)
+}
+
+/// Tag a response with a strong `ETag` and answer fresh requests with `304`.
+///
+/// What `#[etag]` routes do. Only successful (2xx) responses are tagged;
+/// an `ETag` the handler already set is kept. `GET` and `HEAD` requests whose
+/// `If-None-Match` (or `If-Modified-Since`, against a `Last-Modified` header)
+/// matches get a bodyless `304` with the response's other headers.
+#[must_use]
+pub fn with_etag(req: &Request, response: impl IntoResponse) -> ResponseParts {
+    tag_response(req, response.into_response(), false)
+}
+
+/// Like [`with_etag`], with a weak `ETag` (what `#[etag(weak)]` routes do).
+#[must_use]
+pub fn with_weak_etag(req: &Request, response: impl IntoResponse) -> ResponseParts {
+    tag_response(req, response.into_response(), true)
+}
+
+fn tag_response(req: &Request, mut response: ResponseParts, weak: bool) -> ResponseParts {
+    if !(200..300).contains(&response.status) {
+        return response;
+    }
+
+    let existing = response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(HEADER_ETAG))
+        .and_then(|(_, value)| ETag::parse(value));
+    let etag = match (existing, &response.body) {
+        (Some(etag), _) => etag,
+        (None, Some(body)) => {
+            let etag = ETag::from_body(body);
+            let etag = if weak { etag.into_weak() } else { etag };
+            response
+                .headers
+                .push((HEADER_ETAG.to_string(), etag.to_string()));
+            etag
+        },
+        (None, None) => return response,
+    };
+    let last_modified = response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(HEADER_LAST_MODIFIED))
+        .and_then(|(_, value)| time::parse_http_date(value));
+
+    if response.status == 200 && req.is_fresh(Some(&etag), last_modified) {
+        response.status = 304;
+        response.body = None;
+        response.headers.retain(|(name, _)| {
+            !name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE)
+                && !name.eq_ignore_ascii_case(HEADER_CONTENT_LENGTH)
+        });
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use std::collections::HashMap;
+
+    fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+        Request::new(
+            method,
+            "/".to_string(),
+            headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+            None,
+            HashMap::new(),
+        )
+    }
+
+    fn ok(body: &str) -> ResponseParts {
+        ResponseParts::new(
+            200,
+            vec![("content-type".to_string(), "application/json".to_string())],
+            Some(body.as_bytes().to_vec()),
+        )
+    }
+
+    #[test]
+    fn test_from_body_is_stable_and_distinct() {
+        assert_eq!(ETag::from_body(b"").tag(), "0-cbf29ce484222325");
+        assert_eq!(ETag::from_body(b"a").tag(), "1-af63dc4c8601ec8c");
+        assert_ne!(ETag::from_body(b"ab"), ETag::from_body(b"ba"));
+    }
+
+    #[test]
+    fn test_strong_drops_invalid_characters() {
+        assert_eq!(ETag::strong("a \"b\"\nc").to_string(), "\"abc\"");
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let tags = parse_list(r#""a,b", W/"c" ,"d""#);
+        assert_eq!(
+            tags,
+            vec![ETag::strong("a,b"), ETag::weak("c"), ETag::strong("d")]
+        );
+        assert_eq!(parse_list(r#""a", bogus, "b""#), vec![ETag::strong("a")]);
+        assert_eq!(ETag::parse(r#""a", "b""#), None);
+        assert_eq!(ETag::parse("unquoted"), None);
+    }
+
+    #[test]
+    fn test_list_matches_weak_and_strong() {
+        let strong = ETag::strong("v1");
+        let weak = ETag::weak("v1");
+        assert!(list_matches(r#"W/"v1""#, Some(&strong), true));
+        assert!(!list_matches(r#"W/"v1""#, Some(&strong), false));
+        assert!(!list_matches(r#""v1""#, Some(&weak), false));
+        assert!(list_matches(r#""v0", "v1""#, Some(&strong), false));
+        assert!(list_matches("*", Some(&weak), false));
+        assert!(!list_matches("*", None, false));
+    }
+
+    #[test]
+    fn test_with_etag_adds_header() {
+        let response = with_etag(&request(Method::Get, &[]), ok("{}"));
+        assert_eq!(response.status, 200);
+        let etag = ETag::from_body(b"{}").to_string();
+        assert!(response.headers.contains(&(HEADER_ETAG.to_string(), etag)));
+    }
+
+    #[test]
+    fn test_with_etag_answers_304() {
+        let etag = ETag::from_body(b"{}").into_weak().to_string();
+        let req = request(Method::Get, &[("If-None-Match", &etag)]);
+        let response = with_weak_etag(&req, ok("{}"));
+        assert_eq!(response.status, 304);
+        assert_eq!(response.body, None);
+        assert_eq!(
+            response.headers,
+            vec![(HEADER_ETAG.to_string(), etag.clone())]
+        );
+
+        // Changed body, or unsafe method: full response
+        assert_eq!(with_weak_etag(&req, ok("[]")).status, 200);
+        let req = request(Method::Put, &[("If-None-Match", &etag)]);
+        assert_eq!(with_weak_etag(&req, ok("{}")).status, 200);
+    }
+
+    #[test]
+    fn test_with_etag_keeps_handler_tag_and_skips_errors() {
+        let req = request(Method::Get, &[("If-None-Match", "\"v2\"")]);
+        let mut response = ok("{}");
+        response
+            .headers
+            .push(("etag".to_string(), "\"v2\"".to_string()));
+        assert_eq!(with_etag(&req, response).status, 304);
+
+        let error = ResponseParts::new(404, Vec::new(), Some(b"{}".to_vec()));
+        assert!(with_etag(&req, error).headers.is_empty());
+    }
+
+    #[test]
+    fn test_with_etag_uses_last_modified() {
+        let req = request(
+            Method::Get,
+            &[("If-Modified-Since", "Thu, 16 Jan 2025 10:50:00 GMT")],
+        );
+        let response = ok("{}").with_header(HEADER_LAST_MODIFIED, time::to_http_date(1737024600));
+        assert_eq!(with_etag(&req, response).status, 304);
+        let response = ok("{}").with_header(HEADER_LAST_MODIFIED, time::to_http_date(1737024601));
+        assert_eq!(with_etag(&req, response).status, 200);
+    }
+}
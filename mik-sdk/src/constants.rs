@@ -122,6 +122,15 @@ pub const HEADER_CONTENT_TYPE_TITLE: &str = "Content-Type";
 /// Authorization header name (lowercase for lookups).
 pub const HEADER_AUTHORIZATION: &str = "authorization";
 
+/// Content-Length header name (lowercase for lookups).
+pub const HEADER_CONTENT_LENGTH: &str = "content-length";
+
+/// Allow header name (title-case for setting headers).
+pub const HEADER_ALLOW: &str = "Allow";
+
+/// Cache-Control header name (lowercase for lookups).
+pub const HEADER_CACHE_CONTROL: &str = "cache-control";
+
 /// W3C Trace Context header name (always lowercase per spec).
 pub const HEADER_TRACE_ID: &str = "traceparent";
 
@@ -147,6 +156,15 @@ pub const MIME_FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
 /// Multipart form-data MIME type.
 pub const MIME_MULTIPART: &str = "multipart/form-data";
 
+/// CSV MIME type.
+pub const MIME_CSV: &str = "text/csv";
+
+/// Newline-delimited JSON MIME type.
+pub const MIME_NDJSON: &str = "application/x-ndjson";
+
+/// Server-Sent Events MIME type.
+pub const MIME_EVENT_STREAM: &str = "text/event-stream";
+
 // ============================================================================
 // COOKIE CONSTANTS
 // ============================================================================
@@ -207,6 +225,7 @@ pub const fn status_title(code: u16) -> &'static str {
         406 => "Not Acceptable",
         409 => "Conflict",
         410 => "Gone",
+        412 => "Precondition Failed",
         413 => "Payload Too Large",
         422 => "Unprocessable Entity",
         429 => "Too Many Requests",
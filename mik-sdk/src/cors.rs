@@ -0,0 +1,391 @@
+//! Cross-Origin Resource Sharing (CORS) policy.
+//!
+//! Usually declared through the `cors:` section of [`routes!`](crate::routes),
+//! which answers preflight requests and stamps `Access-Control-*` headers on
+//! every response. The policy can also be applied by hand:
+//!
+//! ```
+//! # use mik_sdk::cors::Cors;
+//! const CORS: Cors = Cors::new()
+//!     .origins(&["https://app.example.com"])
+//!     .credentials(true);
+//!
+//! let mut headers = vec![("content-type".to_string(), "application/json".to_string())];
+//! CORS.apply(Some("https://app.example.com"), &mut headers);
+//!
+//! assert!(headers.contains(&(
+//!     "Access-Control-Allow-Origin".to_string(),
+//!     "https://app.example.com".to_string()
+//! )));
+//! ```
+
+/// `Origin` request header (lowercase for lookups).
+pub const HEADER_ORIGIN: &str = "origin";
+
+/// `Access-Control-Request-Method` preflight header (lowercase for lookups).
+pub const HEADER_REQUEST_METHOD: &str = "access-control-request-method";
+
+/// `Access-Control-Request-Headers` preflight header (lowercase for lookups).
+pub const HEADER_REQUEST_HEADERS: &str = "access-control-request-headers";
+
+/// `Access-Control-Allow-Origin` response header.
+pub const HEADER_ALLOW_ORIGIN: &str = "Access-Control-Allow-Origin";
+
+/// `Access-Control-Allow-Credentials` response header.
+pub const HEADER_ALLOW_CREDENTIALS: &str = "Access-Control-Allow-Credentials";
+
+/// `Access-Control-Allow-Methods` preflight response header.
+pub const HEADER_ALLOW_METHODS: &str = "Access-Control-Allow-Methods";
+
+/// `Access-Control-Allow-Headers` preflight response header.
+pub const HEADER_ALLOW_HEADERS: &str = "Access-Control-Allow-Headers";
+
+/// `Access-Control-Max-Age` preflight response header.
+pub const HEADER_MAX_AGE: &str = "Access-Control-Max-Age";
+
+/// `Vary` response header.
+pub const HEADER_VARY: &str = "Vary";
+
+/// A CORS policy.
+///
+/// Built with `const fn` setters so it can live in a `const`:
+///
+/// ```
+/// # use mik_sdk::cors::Cors;
+/// const CORS: Cors = Cors::new()
+///     .origins(&["https://app.example.com", "https://admin.example.com"])
+///     .methods(&["GET", "POST"])
+///     .headers(&["content-type", "authorization"])
+///     .max_age(600);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cors {
+    origins: &'static [&'static str],
+    methods: &'static [&'static str],
+    headers: &'static [&'static str],
+    credentials: bool,
+    max_age: Option<u32>,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cors {
+    /// A policy allowing any origin, without credentials.
+    ///
+    /// Preflight requests are answered with the methods routed for the path
+    /// and the headers the browser asked for.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            origins: &["*"],
+            methods: &[],
+            headers: &[],
+            credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Restrict the allowed origins (`"*"` allows any origin).
+    #[must_use]
+    pub const fn origins(mut self, origins: &'static [&'static str]) -> Self {
+        self.origins = origins;
+        self
+    }
+
+    /// Methods advertised to preflight requests (default: the routed methods).
+    #[must_use]
+    pub const fn methods(mut self, methods: &'static [&'static str]) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    /// Request headers allowed in preflight responses (default: echo the request).
+    #[must_use]
+    pub const fn headers(mut self, headers: &'static [&'static str]) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Allow cookies and `Authorization` headers on cross-origin requests.
+    ///
+    /// Only sent for explicitly listed origins: an origin allowed through
+    /// `"*"` is answered with a literal `*`, which browsers refuse for
+    /// credentialed requests.
+    #[must_use]
+    pub const fn credentials(mut self, credentials: bool) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// How long (in seconds) browsers may cache a preflight response.
+    #[must_use]
+    pub const fn max_age(mut self, seconds: u32) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// The `Access-Control-Allow-Origin` value for `origin`, or `None` if the
+    /// origin is not allowed.
+    ///
+    /// ```
+    /// # use mik_sdk::cors::Cors;
+    /// let cors = Cors::new().origins(&["https://app.example.com"]);
+    /// assert_eq!(
+    ///     cors.allow_origin("https://app.example.com"),
+    ///     Some("https://app.example.com")
+    /// );
+    /// assert_eq!(cors.allow_origin("https://evil.example"), None);
+    /// assert_eq!(Cors::new().allow_origin("https://any.example"), Some("*"));
+    /// ```
+    #[must_use]
+    pub fn allow_origin<'a>(&self, origin: &'a str) -> Option<&'a str> {
+        if self.origins.contains(&"*") {
+            return Some("*");
+        }
+        self.origins
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+            .then_some(origin)
+    }
+
+    /// Append the CORS headers for a request from `origin` to a response.
+    ///
+    /// Adds no `Access-Control-*` headers when the request has no `Origin`,
+    /// the origin is not allowed, or the response already carries
+    /// `Access-Control-Allow-Origin`. Unless every origin is answered with
+    /// `*`, the response still gets `Vary: Origin` so caches never serve one
+    /// origin's answer to another.
+    pub fn apply(&self, origin: Option<&str>, headers: &mut Vec<(String, String)>) {
+        if let Some(allowed) = origin.and_then(|origin| self.allow_origin(origin))
+            && find(headers, HEADER_ALLOW_ORIGIN).is_none()
+        {
+            headers.push((HEADER_ALLOW_ORIGIN.to_string(), allowed.to_string()));
+            if self.credentials && allowed != "*" {
+                headers.push((HEADER_ALLOW_CREDENTIALS.to_string(), "true".to_string()));
+            }
+        }
+
+        if !self.origins.contains(&"*") && !varies_on(headers, "Origin") {
+            headers.push((HEADER_VARY.to_string(), "Origin".to_string()));
+        }
+    }
+
+    /// The preflight-specific headers for an `OPTIONS` request, or `None` if
+    /// the request is not a preflight from an allowed origin.
+    ///
+    /// `allow` lists the methods routed for the path; it is advertised when
+    /// no explicit [`methods`](Self::methods) are configured. The origin
+    /// headers themselves are added by [`apply`](Self::apply).
+    ///
+    /// ```
+    /// # use mik_sdk::cors::Cors;
+    /// let request = vec![
+    ///     ("origin".to_string(), "https://app.example.com".to_string()),
+    ///     ("access-control-request-method".to_string(), "PUT".to_string()),
+    /// ];
+    /// let headers = Cors::new().preflight(&request, "GET, PUT").unwrap();
+    /// assert!(headers.contains(&(
+    ///     "Access-Control-Allow-Methods".to_string(),
+    ///     "GET, PUT".to_string()
+    /// )));
+    ///
+    /// // A plain OPTIONS request is not a preflight
+    /// assert!(Cors::new().preflight(&request[..1], "GET").is_none());
+    /// ```
+    #[must_use]
+    pub fn preflight(
+        &self,
+        request_headers: &[(String, String)],
+        allow: &str,
+    ) -> Option<Vec<(String, String)>> {
+        let origin = find(request_headers, HEADER_ORIGIN)?;
+        find(request_headers, HEADER_REQUEST_METHOD)?;
+        self.allow_origin(origin)?;
+
+        let methods = if self.methods.is_empty() {
+            allow.to_string()
+        } else {
+            self.methods.join(", ")
+        };
+        let mut headers = vec![(HEADER_ALLOW_METHODS.to_string(), methods)];
+
+        if self.headers.is_empty() {
+            if let Some(requested) = find(request_headers, HEADER_REQUEST_HEADERS) {
+                headers.push((HEADER_ALLOW_HEADERS.to_string(), requested.to_string()));
+                headers.push((
+                    HEADER_VARY.to_string(),
+                    "Access-Control-Request-Headers".to_string(),
+                ));
+            }
+        } else {
+            headers.push((HEADER_ALLOW_HEADERS.to_string(), self.headers.join(", ")));
+        }
+
+        if let Some(seconds) = self.max_age {
+            headers.push((HEADER_MAX_AGE.to_string(), seconds.to_string()));
+        }
+
+        Some(headers)
+    }
+}
+
+/// Case-insensitive header lookup.
+fn find<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Whether a `Vary` header already lists `name`.
+fn varies_on(headers: &[(String, String)], name: &str) -> bool {
+    headers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(HEADER_VARY))
+        .flat_map(|(_, value)| value.split(','))
+        .any(|item| item.trim().eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_allow_origin_exact_match_is_case_insensitive() {
+        let cors = Cors::new().origins(&["https://app.example.com"]);
+        assert_eq!(
+            cors.allow_origin("HTTPS://APP.example.com"),
+            Some("HTTPS://APP.example.com")
+        );
+        assert_eq!(cors.allow_origin("https://app.example.com.evil"), None);
+    }
+
+    #[test]
+    fn test_wildcard_with_credentials_never_echoes_origin() {
+        let cors = Cors::new().credentials(true);
+        assert_eq!(cors.allow_origin("https://a.dev"), Some("*"));
+
+        let mut response = Vec::new();
+        cors.apply(Some("https://a.dev"), &mut response);
+        assert_eq!(response, headers(&[(HEADER_ALLOW_ORIGIN, "*")]));
+    }
+
+    #[test]
+    fn test_apply_adds_vary_for_specific_origins() {
+        let mut response = Vec::new();
+        Cors::new()
+            .origins(&["https://a.dev"])
+            .credentials(true)
+            .apply(Some("https://a.dev"), &mut response);
+        assert_eq!(
+            response,
+            headers(&[
+                (HEADER_ALLOW_ORIGIN, "https://a.dev"),
+                (HEADER_ALLOW_CREDENTIALS, "true"),
+                (HEADER_VARY, "Origin"),
+            ])
+        );
+
+        let mut response = Vec::new();
+        Cors::new().apply(Some("https://a.dev"), &mut response);
+        assert_eq!(response, headers(&[(HEADER_ALLOW_ORIGIN, "*")]));
+    }
+
+    #[test]
+    fn test_apply_skips_missing_or_disallowed_origin() {
+        let cors = Cors::new().origins(&["https://a.dev"]);
+        for origin in [None, Some("https://b.dev")] {
+            let mut response = Vec::new();
+            cors.apply(origin, &mut response);
+            assert_eq!(response, headers(&[(HEADER_VARY, "Origin")]));
+        }
+
+        let mut response = Vec::new();
+        Cors::new().apply(None, &mut response);
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn test_apply_keeps_handler_set_origin() {
+        let mut response = headers(&[("access-control-allow-origin", "https://mine.dev")]);
+        Cors::new().apply(Some("https://a.dev"), &mut response);
+        assert_eq!(response.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_does_not_repeat_vary_origin() {
+        let mut response = headers(&[("vary", "Accept, origin")]);
+        Cors::new()
+            .origins(&["https://a.dev"])
+            .apply(Some("https://a.dev"), &mut response);
+        assert_eq!(
+            response,
+            headers(&[
+                ("vary", "Accept, origin"),
+                (HEADER_ALLOW_ORIGIN, "https://a.dev"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_preflight_uses_configured_values() {
+        let request = headers(&[
+            ("Origin", "https://a.dev"),
+            ("Access-Control-Request-Method", "POST"),
+            ("Access-Control-Request-Headers", "x-custom"),
+        ]);
+        let response = Cors::new()
+            .methods(&["GET", "POST"])
+            .headers(&["content-type", "authorization"])
+            .max_age(600)
+            .preflight(&request, "GET")
+            .unwrap();
+        assert_eq!(
+            response,
+            headers(&[
+                (HEADER_ALLOW_METHODS, "GET, POST"),
+                (HEADER_ALLOW_HEADERS, "content-type, authorization"),
+                (HEADER_MAX_AGE, "600"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_preflight_echoes_requested_headers_by_default() {
+        let request = headers(&[
+            ("origin", "https://a.dev"),
+            ("access-control-request-method", "PUT"),
+            ("access-control-request-headers", "x-custom, content-type"),
+        ]);
+        let response = Cors::new().preflight(&request, "GET, PUT").unwrap();
+        assert_eq!(
+            response,
+            headers(&[
+                (HEADER_ALLOW_METHODS, "GET, PUT"),
+                (HEADER_ALLOW_HEADERS, "x-custom, content-type"),
+                (HEADER_VARY, "Access-Control-Request-Headers"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_preflight_rejects_disallowed_origin() {
+        let request = headers(&[
+            ("origin", "https://b.dev"),
+            ("access-control-request-method", "GET"),
+        ]);
+        let cors = Cors::new().origins(&["https://a.dev"]);
+        assert!(cors.preflight(&request, "GET").is_none());
+    }
+}
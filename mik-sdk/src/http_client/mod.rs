@@ -245,7 +245,7 @@ mod tests {
             .header("Authorization", "Bearer token")
             .header("Accept", "application/json");
 
-        assert_eq!(req.method(), Method::Get);
+        assert_eq!(req.method(), &Method::Get);
         assert_eq!(req.url(), "https://api.example.com/users");
         assert_eq!(req.headers().len(), 2);
     }
@@ -254,7 +254,7 @@ mod tests {
     fn test_request_with_json_body() {
         let req = post("https://api.example.com/users").json(b"{\"name\":\"Alice\"}");
 
-        assert_eq!(req.method(), Method::Post);
+        assert_eq!(req.method(), &Method::Post);
         assert!(
             req.headers()
                 .iter()
@@ -338,13 +338,13 @@ mod tests {
 
     #[test]
     fn test_all_http_methods_request() {
-        assert_eq!(get("http://x.com").method(), Method::Get);
-        assert_eq!(post("http://x.com").method(), Method::Post);
-        assert_eq!(put("http://x.com").method(), Method::Put);
-        assert_eq!(delete("http://x.com").method(), Method::Delete);
-        assert_eq!(patch("http://x.com").method(), Method::Patch);
-        assert_eq!(head("http://x.com").method(), Method::Head);
-        assert_eq!(options("http://x.com").method(), Method::Options);
+        assert_eq!(get("http://x.com").method(), &Method::Get);
+        assert_eq!(post("http://x.com").method(), &Method::Post);
+        assert_eq!(put("http://x.com").method(), &Method::Put);
+        assert_eq!(delete("http://x.com").method(), &Method::Delete);
+        assert_eq!(patch("http://x.com").method(), &Method::Patch);
+        assert_eq!(head("http://x.com").method(), &Method::Head);
+        assert_eq!(options("http://x.com").method(), &Method::Options);
     }
 
     #[test]
@@ -547,35 +547,35 @@ mod tests {
     fn test_put_method_specific() {
         // Ensure PUT is correctly mapped (not defaulting to GET)
         let req = put("https://example.com/resource").body(b"data");
-        assert_eq!(req.method(), Method::Put);
+        assert_eq!(req.method(), &Method::Put);
     }
 
     #[test]
     fn test_delete_method_specific() {
         // Ensure DELETE is correctly mapped (not defaulting to GET)
         let req = delete("https://example.com/resource/123");
-        assert_eq!(req.method(), Method::Delete);
+        assert_eq!(req.method(), &Method::Delete);
     }
 
     #[test]
     fn test_patch_method_specific() {
         // Ensure PATCH is correctly mapped (not defaulting to GET)
         let req = patch("https://example.com/resource").body(b"partial");
-        assert_eq!(req.method(), Method::Patch);
+        assert_eq!(req.method(), &Method::Patch);
     }
 
     #[test]
     fn test_head_method_specific() {
         // Ensure HEAD is correctly mapped (not defaulting to GET)
         let req = head("https://example.com/resource");
-        assert_eq!(req.method(), Method::Head);
+        assert_eq!(req.method(), &Method::Head);
     }
 
     #[test]
     fn test_options_method_specific() {
         // Ensure OPTIONS is correctly mapped (not defaulting to GET)
         let req = options("https://example.com/resource");
-        assert_eq!(req.method(), Method::Options);
+        assert_eq!(req.method(), &Method::Options);
     }
 
     // === WASI ERROR MAPPING TESTS ===
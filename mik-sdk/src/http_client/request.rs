@@ -186,8 +186,8 @@ impl ClientRequest {
 
     /// Get the HTTP method.
     #[must_use]
-    pub const fn method(&self) -> Method {
-        self.method
+    pub const fn method(&self) -> &Method {
+        &self.method
     }
 
     /// Get the URL.
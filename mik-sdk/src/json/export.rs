@@ -0,0 +1,118 @@
+//! Tabular and streaming text formats for lists of `ToJson` values.
+
+use super::ToJson;
+use miniserde::json::Value;
+
+/// Serialize items as newline-delimited JSON (`application/x-ndjson`).
+///
+/// Each item is written as compact JSON on its own line, including the last.
+///
+/// ```
+/// # use mik_sdk::json;
+/// let lines = json::to_ndjson(&[1, 2]);
+/// assert_eq!(lines, "1\n2\n");
+/// ```
+pub fn to_ndjson<T: ToJson>(items: &[T]) -> String {
+    let mut out = String::new();
+    for item in items {
+        // Re-serialize so lazily parsed input never carries raw newlines
+        out.push_str(&miniserde::json::to_string(
+            &item.to_json().get_value_for_tree(),
+        ));
+        out.push('\n');
+    }
+    out
+}
+
+/// Serialize items as CSV (`text/csv`, RFC 4180).
+///
+/// Columns are the object keys of all rows. Object keys are stored sorted, so
+/// the first row's keys come in alphabetical order, followed by keys that
+/// only later rows have; a row missing a key gets an empty cell. Strings are written as-is, `null` as an
+/// empty cell, and nested arrays or objects as JSON text. Non-object items
+/// are written as a single `value` column. No items produce an empty string.
+///
+/// String cells starting with `=`, `+`, `-`, `@`, tab or carriage return are
+/// prefixed with `'` so spreadsheets do not evaluate them as formulas.
+///
+/// ```
+/// # use mik_sdk::json;
+/// let rows = vec![
+///     json::obj().set("id", json::int(1)).set("name", json::str("Alice, Jr.")),
+///     json::obj().set("id", json::int(2)),
+/// ];
+/// assert_eq!(json::to_csv(&rows), "id,name\r\n1,\"Alice, Jr.\"\r\n2,\r\n");
+/// ```
+pub fn to_csv<T: ToJson>(items: &[T]) -> String {
+    let rows: Vec<Value> = items
+        .iter()
+        .map(|item| item.to_json().get_value_for_tree())
+        .collect();
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut columns: Vec<&str> = Vec::new();
+    for row in &rows {
+        let keys: Vec<&str> = match row {
+            Value::Object(object) => object.keys().map(String::as_str).collect(),
+            _ => vec!["value"],
+        };
+        for key in keys {
+            if !columns.contains(&key) {
+                columns.push(key);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write_record(&mut out, columns.iter().map(|column| (*column).to_string()));
+    for row in &rows {
+        write_record(
+            &mut out,
+            columns.iter().map(|column| {
+                let cell = match row {
+                    Value::Object(object) => object.get(*column),
+                    other => (*column == "value").then_some(other),
+                };
+                cell.map_or_else(String::new, cell_text)
+            }),
+        );
+    }
+    out
+}
+
+/// Text of one CSV cell, before quoting.
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => {
+            if s.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+                format!("'{s}")
+            } else {
+                s.clone()
+            }
+        },
+        other => miniserde::json::to_string(other),
+    }
+}
+
+/// Quote a field if it contains a delimiter, quote or line break.
+fn quote_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Append one CRLF-terminated record.
+fn write_record(out: &mut String, cells: impl Iterator<Item = String>) {
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&quote_field(&cell));
+    }
+    out.push_str("\r\n");
+}
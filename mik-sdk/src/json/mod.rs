@@ -49,6 +49,7 @@
 //! ```
 
 mod builder;
+mod export;
 mod lazy;
 #[cfg(test)]
 mod tests;
@@ -60,6 +61,7 @@ use miniserde::json::{Number, Value};
 
 // Re-export public types and functions
 pub use builder::{arr, bool, float, int, null, obj, str};
+pub use export::{to_csv, to_ndjson};
 pub use to_json::ToJson;
 pub use value::JsonValue;
 
@@ -338,6 +338,15 @@ fn test_get_on_non_object() {
     assert!(v.get("key").is_null());
 }
 
+#[test]
+fn test_is_object() {
+    assert!(obj().is_object());
+    assert!(obj().set("key", int(1)).is_object());
+    assert!(!arr().is_object());
+    assert!(!null().is_object());
+    assert!(!int(42).is_object());
+}
+
 // === at() edge cases ===
 
 #[test]
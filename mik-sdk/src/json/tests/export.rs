@@ -0,0 +1,80 @@
+//! Tests for CSV and NDJSON export.
+
+use super::super::*;
+
+#[test]
+fn test_ndjson_one_compact_value_per_line() {
+    let rows = vec![
+        obj().set("id", int(1)).set("tags", arr().push(str("a"))),
+        obj().set("id", int(2)),
+    ];
+    assert_eq!(
+        to_ndjson(&rows),
+        "{\"id\":1,\"tags\":[\"a\"]}\n{\"id\":2}\n"
+    );
+    assert_eq!(to_ndjson::<JsonValue>(&[]), "");
+}
+
+#[test]
+fn test_ndjson_reserializes_lazy_values() {
+    let parsed = try_parse(b"{\n  \"id\": 1\n}").unwrap();
+    assert_eq!(to_ndjson(&[parsed]), "{\"id\":1}\n");
+}
+
+#[test]
+fn test_csv_columns_sorted_then_later_keys() {
+    let rows = vec![
+        obj().set("name", str("Alice")).set("age", int(30)),
+        obj()
+            .set("email", str("bob@example.com"))
+            .set("name", str("Bob")),
+    ];
+    // Object keys are sorted, so row one contributes "age" before "name"
+    assert_eq!(
+        to_csv(&rows),
+        "age,name,email\r\n30,Alice,\r\n,Bob,bob@example.com\r\n"
+    );
+}
+
+#[test]
+fn test_csv_cell_values() {
+    let row = obj()
+        .set("active", bool(true))
+        .set("note", null())
+        .set("score", float(1.5))
+        .set("tags", arr().push(str("x")).push(str("y")));
+    assert_eq!(
+        to_csv(&[row]),
+        "active,note,score,tags\r\ntrue,,1.5,\"[\"\"x\"\",\"\"y\"\"]\"\r\n"
+    );
+}
+
+#[test]
+fn test_csv_quotes_special_characters() {
+    let row = obj()
+        .set("a", str("say \"hi\""))
+        .set("b", str("line\nbreak"))
+        .set("c", str("plain"));
+    assert_eq!(
+        to_csv(&[row]),
+        "a,b,c\r\n\"say \"\"hi\"\"\",\"line\nbreak\",plain\r\n"
+    );
+}
+
+#[test]
+fn test_csv_neutralizes_formulas() {
+    let row = obj()
+        .set("formula", str("=SUM(A1:A9)"))
+        .set("negative", int(-5))
+        .set("handle", str("@alice"));
+    assert_eq!(
+        to_csv(&[row]),
+        "formula,handle,negative\r\n'=SUM(A1:A9),'@alice,-5\r\n"
+    );
+}
+
+#[test]
+fn test_csv_scalar_rows_use_value_column() {
+    assert_eq!(to_csv(&["a", "b"]), "value\r\na\r\nb\r\n");
+    assert_eq!(to_csv::<JsonValue>(&[]), "");
+}
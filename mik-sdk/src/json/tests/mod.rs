@@ -1,6 +1,7 @@
 //! All tests for the json module, organized into submodules.
 
 mod building;
+mod export;
 mod parsing;
 mod path;
 mod proptest;
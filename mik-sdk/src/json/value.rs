@@ -90,7 +90,7 @@ impl JsonValue {
     // === Reading (chainable) ===
 
     /// Get the Value for tree operations, parsing if in lazy mode.
-    fn get_value_for_tree(&self) -> Value {
+    pub(crate) fn get_value_for_tree(&self) -> Value {
         match &self.inner {
             JsonInner::Parsed(v) => (**v).clone(),
             JsonInner::Lazy { bytes } => Self::parse_bytes(bytes).unwrap_or(Value::Null),
@@ -292,6 +292,12 @@ impl JsonValue {
         matches!(self.get_value_for_tree(), Value::Null)
     }
 
+    /// Is this value an object?
+    #[must_use]
+    pub fn is_object(&self) -> bool {
+        matches!(self.get_value_for_tree(), Value::Object(_))
+    }
+
     /// Get object keys (empty if not an object).
     ///
     /// Note: This triggers a full parse if in lazy mode.
@@ -193,7 +193,9 @@
 //! }
 //! ```
 
+pub mod conditional;
 pub mod constants;
+pub mod cors;
 mod request;
 pub mod typed;
 
@@ -202,8 +204,15 @@ pub mod http_client;
 pub mod json;
 pub mod log;
 pub mod random;
+pub mod response;
+pub mod sse;
+pub mod stream;
 pub mod time;
 
+// In-process testing of routes! handlers (native only)
+#[cfg(not(target_arch = "wasm32"))]
+pub mod testing;
+
 // WASI bindings (HTTP, random, clocks)
 // Always included for WASM target, uses http-client feature for HTTP client on native
 #[cfg(any(target_arch = "wasm32", feature = "http-client"))]
@@ -215,6 +224,8 @@ pub use mik_sql as query;
 
 pub use mik_sdk_macros::{
     // Derive macros for typed inputs
+    Cookies,
+    Headers,
     Path,
     Query,
     Type,
@@ -240,6 +251,7 @@ pub use mik_sdk_macros::{
     redirect,
     // Routing macros
     routes,
+    sse,
 };
 
 // SQL CRUD macros - re-exported from mik-sql-macros when sql feature is enabled
@@ -278,6 +290,7 @@ pub fn __ensure_helper<T, H: EnsureHelper<T>>(value: H) -> Option<T> {
 pub use request::{
     DecodeError, Method, MultipartError, Part, Request, SameSite, SetCookie, url_decode,
 };
+pub use response::{ApiError, IntoResponse};
 
 /// HTTP status code constants.
 ///
@@ -325,6 +338,8 @@ pub mod status {
     pub const CONFLICT: u16 = 409;
     /// 410 Gone - Resource permanently removed.
     pub const GONE: u16 = 410;
+    /// 412 Precondition Failed - `If-Match` / `If-Unmodified-Since` not met.
+    pub const PRECONDITION_FAILED: u16 = 412;
     /// 422 Unprocessable Entity - Validation failed.
     pub const UNPROCESSABLE_ENTITY: u16 = 422;
     /// 429 Too Many Requests - Rate limit exceeded.
@@ -357,6 +372,9 @@ pub mod status {
 /// - [`status`] - HTTP status code constants
 /// - [`mod@env`] - Environment variable access helpers
 /// - [`http_client`] - HTTP client for outbound requests
+/// - [`response`] - [`ApiError`] and [`IntoResponse`] for handler return values
+/// - [`Streaming`](stream::Streaming) - response bodies sent chunk by chunk
+/// - [`mod@sse`] - Server-Sent Events, also via [`sse!`]
 /// - Core macros: [`ok!`], [`error!`], [`json!`], [`routes!`], [`log!`]
 /// - DX macros: [`guard!`],
 ///   [`created!`], [`no_content!`], [`redirect!`], [`not_found!`],
@@ -371,20 +389,23 @@ pub mod prelude {
     pub use crate::request::{
         DecodeError, Method, MultipartError, Part, Request, SameSite, SetCookie,
     };
+    pub use crate::response::{ApiError, IntoResponse};
     pub use crate::status;
+    pub use crate::stream::Streaming;
     pub use crate::time;
     // Typed input types
     pub use crate::typed::{
-        FromJson, FromPath, FromQuery, Id, OpenApiSchema, ParseError, Validate, ValidationError,
+        FormData, FromCookies, FromForm, FromHeaders, FromJson, FromPath, FromQuery, Id,
+        OpenApiSchema, ParseError, Upload, Validate, ValidationError, ValidationErrors,
     };
     // Core macros (json module already exported above)
     pub use crate::{error, ok, routes};
     // Derive macros for typed inputs
-    pub use crate::{Path, Query, Type};
+    pub use crate::{Cookies, Headers, Path, Query, Type};
     // DX macros
     pub use crate::{
         accepted, bad_request, conflict, created, ensure, fetch, forbidden, guard, no_content,
-        not_found, redirect,
+        not_found, redirect, sse,
     };
 
     // SQL macros and types - only when sql feature is enabled
@@ -410,8 +431,8 @@ mod api_contracts {
     assert_impl_all!(crate::Request: std::fmt::Debug);
     assert_not_impl_any!(crate::Request: Clone);
 
-    // Method is Copy, Clone, Debug, PartialEq, Eq, Hash
-    assert_impl_all!(crate::Method: Copy, Clone, std::fmt::Debug, PartialEq, Eq, std::hash::Hash);
+    // Method is Clone, Debug, PartialEq, Eq, Hash (not Copy: Other holds a String)
+    assert_impl_all!(crate::Method: Clone, std::fmt::Debug, PartialEq, Eq, std::hash::Hash);
 
     // Id is Clone, Debug, PartialEq, Eq, Hash (can be map key)
     assert_impl_all!(crate::typed::Id: Clone, std::fmt::Debug, PartialEq, Eq, std::hash::Hash);
@@ -436,6 +457,9 @@ mod api_contracts {
     // ValidationError is Clone, Debug, PartialEq, Eq
     assert_impl_all!(crate::typed::ValidationError: Clone, std::fmt::Debug, PartialEq, Eq);
 
+    // ValidationErrors is Clone, Debug, PartialEq, Eq and a std error
+    assert_impl_all!(crate::typed::ValidationErrors: Clone, std::fmt::Debug, PartialEq, Eq, std::error::Error);
+
     // DecodeError is Copy, Clone, Debug, PartialEq, Eq
     assert_impl_all!(crate::DecodeError: Copy, Clone, std::fmt::Debug, PartialEq, Eq);
 
@@ -448,6 +472,10 @@ mod api_contracts {
     // SetCookie is Clone, Debug
     assert_impl_all!(crate::SetCookie: Clone, std::fmt::Debug);
 
+    // ApiError is Clone, Debug and a std error
+    assert_impl_all!(crate::ApiError: Clone, std::fmt::Debug, std::error::Error);
+    static_assertions::const_assert!(std::mem::size_of::<crate::ApiError>() <= 128);
+
     // ========================================================================
     // HTTP Client types (when http-client feature is enabled)
     // ========================================================================
@@ -0,0 +1,189 @@
+//! `Accept` header parsing and content negotiation (RFC 9110 §12.5.1).
+//!
+//! Media ranges carry a quality value (`q`, default 1) and may use the
+//! `type/*` and `*/*` wildcards. An offer takes the quality of the most
+//! specific range that matches it; `q=0` means "not acceptable".
+
+/// Maximum media ranges considered from one `Accept` header.
+const MAX_MEDIA_RANGES: usize = 64;
+
+/// One media range from an `Accept` header, without parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MediaRange<'a> {
+    main: &'a str,
+    sub: &'a str,
+    /// Quality in thousandths (0..=1000).
+    quality: u16,
+}
+
+impl MediaRange<'_> {
+    /// Match specificity against an offer: exact 3, `type/*` 2, `*/*` 1, no match 0.
+    fn specificity(&self, main: &str, sub: &str) -> u8 {
+        match (self.main, self.sub) {
+            ("*", "*") => 1,
+            (range_main, "*") if range_main.eq_ignore_ascii_case(main) => 2,
+            (range_main, range_sub)
+                if range_main.eq_ignore_ascii_case(main) && range_sub.eq_ignore_ascii_case(sub) =>
+            {
+                3
+            },
+            _ => 0,
+        }
+    }
+}
+
+/// Parse a quality value: `0`, `1` or a decimal with up to three digits.
+fn parse_quality(value: &str) -> Option<u16> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let thousandths = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(3)
+        .fold(0u16, |acc, digit| acc * 10 + u16::from(digit - b'0'));
+    match whole {
+        "0" => Some(thousandths),
+        "1" if thousandths == 0 => Some(1000),
+        _ => None,
+    }
+}
+
+/// Parse the media ranges of an `Accept` header, skipping malformed entries.
+fn parse_accept(header: &str) -> Vec<MediaRange<'_>> {
+    header
+        .split(',')
+        .take(MAX_MEDIA_RANGES)
+        .filter_map(|entry| {
+            let mut params = entry.split(';');
+            let (main, sub) = params.next()?.trim().split_once('/')?;
+            let (main, sub) = (main.trim(), sub.trim());
+            if main.is_empty() || sub.is_empty() || (main == "*" && sub != "*") {
+                return None;
+            }
+            let mut quality = 1000;
+            for param in params {
+                if let Some((name, value)) = param.split_once('=')
+                    && name.trim().eq_ignore_ascii_case("q")
+                {
+                    quality = parse_quality(value.trim())?;
+                }
+            }
+            Some(MediaRange { main, sub, quality })
+        })
+        .collect()
+}
+
+/// Quality of `offer` under the parsed ranges (0 when nothing matches).
+fn quality_of(ranges: &[MediaRange<'_>], offer: &str) -> u16 {
+    let essence = offer.split(';').next().unwrap_or(offer).trim();
+    let Some((main, sub)) = essence.split_once('/') else {
+        return 0;
+    };
+    ranges
+        .iter()
+        .map(|range| (range.specificity(main, sub), range.quality))
+        .filter(|(specificity, _)| *specificity > 0)
+        // Most specific range wins; among equals, the first one listed
+        .fold((0, 0), |best, candidate| {
+            if candidate.0 > best.0 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .1
+}
+
+/// Pick the offer the client prefers most.
+///
+/// Ties go to the earlier offer, so `offers` lists the server's preference.
+/// Without an `Accept` header (or with an empty one) the first offer wins.
+pub(super) fn negotiate<'a>(accept: Option<&str>, offers: &[&'a str]) -> Option<&'a str> {
+    let Some(accept) = accept.filter(|value| !value.trim().is_empty()) else {
+        return offers.first().copied();
+    };
+    let ranges = parse_accept(accept);
+    offers
+        .iter()
+        .map(|offer| (*offer, quality_of(&ranges, offer)))
+        .filter(|(_, quality)| *quality > 0)
+        .fold(None, |best: Option<(&str, u16)>, candidate| match best {
+            Some(best) if best.1 >= candidate.1 => Some(best),
+            _ => Some(candidate),
+        })
+        .map(|(offer, _)| offer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFERS: &[&str] = &["application/json", "text/csv", "application/x-ndjson"];
+
+    #[test]
+    fn test_parse_quality() {
+        assert_eq!(parse_quality("1"), Some(1000));
+        assert_eq!(parse_quality("1.000"), Some(1000));
+        assert_eq!(parse_quality("0.5"), Some(500));
+        assert_eq!(parse_quality("0.125"), Some(125));
+        assert_eq!(parse_quality("0"), Some(0));
+        assert_eq!(parse_quality("1.5"), None);
+        assert_eq!(parse_quality("0.1234"), None);
+        assert_eq!(parse_quality("high"), None);
+    }
+
+    #[test]
+    fn test_parse_accept_skips_malformed_ranges() {
+        let ranges = parse_accept("text/html;level=1;q=0.7, junk, */json, image/*;q=x");
+        assert_eq!(
+            ranges,
+            vec![MediaRange {
+                main: "text",
+                sub: "html",
+                quality: 700,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_negotiate_by_quality() {
+        let accept = "application/json;q=0.5, text/csv";
+        assert_eq!(negotiate(Some(accept), OFFERS), Some("text/csv"));
+    }
+
+    #[test]
+    fn test_negotiate_wildcards_and_specificity() {
+        // text/csv is excluded by its exact range despite text/*
+        let accept = "text/*, text/csv;q=0, */*;q=0.1";
+        assert_eq!(negotiate(Some(accept), OFFERS), Some("application/json"));
+        assert_eq!(
+            negotiate(Some("TEXT/*"), OFFERS),
+            Some("text/csv"),
+            "media types are case-insensitive"
+        );
+    }
+
+    #[test]
+    fn test_negotiate_ties_follow_offer_order() {
+        assert_eq!(negotiate(Some("*/*"), OFFERS), Some("application/json"));
+        let accept = "application/x-ndjson, text/csv";
+        assert_eq!(negotiate(Some(accept), OFFERS), Some("text/csv"));
+    }
+
+    #[test]
+    fn test_negotiate_missing_or_unacceptable() {
+        assert_eq!(negotiate(None, OFFERS), Some("application/json"));
+        assert_eq!(negotiate(Some("  "), OFFERS), Some("application/json"));
+        assert_eq!(negotiate(Some("image/png"), OFFERS), None);
+        assert_eq!(negotiate(Some("*/*;q=0"), OFFERS), None);
+        assert_eq!(negotiate(None, &[]), None);
+    }
+
+    #[test]
+    fn test_offer_parameters_are_ignored() {
+        let offers = ["text/csv; charset=utf-8"];
+        assert_eq!(negotiate(Some("text/csv"), &offers), Some(offers[0]));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides the `Request` struct that wraps raw `request-data` from WIT
 //! and provides convenient accessors for path parameters, query strings, headers, and body.
 
+mod accept;
 mod cookie;
 mod multipart;
 mod parsing;
@@ -12,16 +13,22 @@ pub use multipart::{MultipartError, Part};
 use parsing::contains_ignore_ascii_case;
 pub use parsing::{DecodeError, url_decode};
 
+use crate::conditional::{self, ETag};
 use crate::constants::{
     HEADER_AUTHORIZATION, HEADER_COOKIE, HEADER_TRACE_ID, MAX_FORM_FIELDS, MAX_HEADER_VALUE_LEN,
     MAX_TOTAL_HEADERS_SIZE, MAX_URL_DECODED_LEN, MIME_MULTIPART,
 };
 use crate::json::{self, JsonValue};
+use crate::response::ApiError;
+use crate::typed::FormData;
 use std::cell::OnceCell;
 use std::collections::HashMap;
 
 /// HTTP method enum matching the WIT definition.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Extension methods (WebDAV `PROPFIND`, cache `PURGE`, ...) arrive as
+/// [`Method::Other`] with the method token exactly as sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum Method {
     /// HTTP GET method - retrieve a resource.
@@ -38,12 +45,18 @@ pub enum Method {
     Head,
     /// HTTP OPTIONS method - retrieve allowed methods.
     Options,
+    /// HTTP TRACE method - loop the request back for diagnostics.
+    Trace,
+    /// Any other method token, case preserved (e.g., `"PROPFIND"`).
+    Other(String),
 }
 
 impl Method {
-    /// Returns the method as an uppercase string (e.g., "GET", "POST").
+    /// Returns the method name (e.g., "GET", "POST", "PROPFIND").
+    ///
+    /// Standard methods are uppercase; [`Method::Other`] returns its token as-is.
     #[must_use]
-    pub const fn as_str(&self) -> &'static str {
+    pub const fn as_str(&self) -> &str {
         match self {
             Self::Get => "GET",
             Self::Post => "POST",
@@ -52,6 +65,8 @@ impl Method {
             Self::Delete => "DELETE",
             Self::Head => "HEAD",
             Self::Options => "OPTIONS",
+            Self::Trace => "TRACE",
+            Self::Other(name) => name.as_str(),
         }
     }
 }
@@ -216,10 +231,16 @@ impl Request {
         }
     }
 
+    /// Split back into `(method, path, headers, body)`, dropping the caches.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn into_parts(self) -> crate::testing::RequestParts {
+        (self.method, self.path, self.headers, self.body)
+    }
+
     /// HTTP method (GET, POST, etc.).
     #[inline]
-    pub const fn method(&self) -> Method {
-        self.method
+    pub const fn method(&self) -> &Method {
+        &self.method
     }
 
     /// Full request path including query string (e.g., "/users/123?page=1").
@@ -551,6 +572,7 @@ impl Request {
     ///
     /// Performs a simple case-insensitive substring match against the Accept header.
     /// Does not parse q-values; returns `true` if the MIME type is present at all.
+    /// Use [`negotiate()`](Self::negotiate) to pick between several representations.
     ///
     /// ```ignore
     /// // Accept: text/html, application/json
@@ -563,6 +585,99 @@ impl Request {
             .is_some_and(|accept| contains_ignore_ascii_case(accept, mime))
     }
 
+    /// Pick the best of `offers` for the client's Accept header.
+    ///
+    /// Honors q-values and the `type/*` and `*/*` wildcards; the most specific
+    /// matching range decides an offer's quality, and `q=0` excludes it. Ties go
+    /// to the earlier offer, and a missing Accept header selects the first one.
+    /// Returns `None` when nothing is acceptable (respond with 406).
+    ///
+    /// ```ignore
+    /// // Accept: text/csv, application/json;q=0.8
+    /// match req.negotiate(&["application/json", "text/csv"]) {
+    ///     Some("text/csv") => response::csv(&rows),
+    ///     Some(_) => json_rows.into_response(),
+    ///     None => ApiError::not_acceptable(&["application/json", "text/csv"]).into_response(),
+    /// }
+    /// ```
+    #[must_use]
+    pub fn negotiate<'a>(&self, offers: &[&'a str]) -> Option<&'a str> {
+        accept::negotiate(self.header_opt("accept"), offers)
+    }
+
+    /// Whether the client's cached copy is current, so a `304` can be sent.
+    ///
+    /// Only `GET` and `HEAD` requests qualify. `If-None-Match` is compared
+    /// (weakly) against `etag`; when absent, `If-Modified-Since` is compared
+    /// against `last_modified` (Unix seconds). Missing validators never match.
+    ///
+    /// ```ignore
+    /// let etag = ETag::from_body(&body);
+    /// if req.is_fresh(Some(&etag), None) {
+    ///     return conditional::not_modified(&etag);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn is_fresh(&self, etag: Option<&ETag>, last_modified: Option<u64>) -> bool {
+        if !matches!(self.method, Method::Get | Method::Head) {
+            return false;
+        }
+        let if_none_match = self.header_all(conditional::HEADER_IF_NONE_MATCH);
+        if !if_none_match.is_empty() {
+            return conditional::list_matches(&if_none_match.join(","), etag, true);
+        }
+        match (
+            self.header_opt(conditional::HEADER_IF_MODIFIED_SINCE),
+            last_modified,
+        ) {
+            (Some(since), Some(last_modified)) => {
+                conditional::unmodified_since(since, last_modified).unwrap_or(false)
+            },
+            _ => false,
+        }
+    }
+
+    /// Check `If-Match` / `If-Unmodified-Since` before changing a resource.
+    ///
+    /// Pass the current representation's validators (`None` when the resource
+    /// does not exist). `If-Match` requires a strong match, or `*` for any
+    /// existing resource; when absent, `If-Unmodified-Since` is compared
+    /// against `last_modified`. Fails with `412 Precondition Failed`.
+    ///
+    /// ```ignore
+    /// // PUT /users/{id} with If-Match: "7"
+    /// req.check_preconditions(Some(&ETag::strong(user.version.to_string())), None)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a `412` [`ApiError`] when a precondition is not met.
+    pub fn check_preconditions(
+        &self,
+        etag: Option<&ETag>,
+        last_modified: Option<u64>,
+    ) -> Result<(), ApiError> {
+        let if_match = self.header_all(conditional::HEADER_IF_MATCH);
+        if !if_match.is_empty() {
+            if conditional::list_matches(&if_match.join(","), etag, false) {
+                return Ok(());
+            }
+            return Err(ApiError::precondition_failed(
+                "If-Match does not match the current representation",
+            ));
+        }
+        if let (Some(since), Some(last_modified)) = (
+            self.header_opt(conditional::HEADER_IF_UNMODIFIED_SINCE),
+            last_modified,
+        ) && conditional::unmodified_since(since, last_modified) == Some(false)
+        {
+            return Err(ApiError::precondition_failed(
+                "Resource was modified after If-Unmodified-Since",
+            ));
+        }
+        Ok(())
+    }
+
     /// Get the first form field value from a form-urlencoded body, or a default.
     ///
     /// Parses `application/x-www-form-urlencoded` body data.
@@ -600,6 +715,27 @@ impl Request {
         self.form_cache().get(name).map_or(&[], Vec::as_slice)
     }
 
+    /// All fields of a form-urlencoded body as [`FormData`].
+    ///
+    /// This is what `form: T` inputs in `routes!` decode from; for
+    /// multipart bodies use [`FormData::from_parts`] with [`multipart()`](Self::multipart).
+    ///
+    /// ```ignore
+    /// // Body: name=Alice&tags=a&tags=b
+    /// let form = req.form_data();
+    /// form.value("name");  // Some("Alice")
+    /// form.values("tags"); // ["a", "b"]
+    /// ```
+    #[must_use]
+    pub fn form_data(&self) -> FormData {
+        self.form_cache()
+            .iter()
+            .flat_map(|(name, values)| values.iter().map(move |value| (name, value)))
+            .fold(FormData::new(), |form, (name, value)| {
+                form.with_value(name.as_str(), value.as_str())
+            })
+    }
+
     /// Get a cookie value by name from the Cookie header, or a default.
     ///
     /// Parses the `Cookie` header lazily on first access.
@@ -0,0 +1,102 @@
+//! Conditional request tests (If-None-Match, If-Modified-Since, If-Match)
+
+use super::super::*;
+use std::collections::HashMap;
+
+const JAN_16: &str = "Thu, 16 Jan 2025 10:50:00 GMT";
+const JAN_16_SECS: u64 = 1_737_024_600;
+
+fn request(method: Method, headers: &[(&str, &str)]) -> Request {
+    Request::new(
+        method,
+        "/users/1".to_string(),
+        headers
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+        None,
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn test_is_fresh_if_none_match() {
+    let etag = ETag::strong("v2");
+    let req = request(Method::Get, &[("If-None-Match", r#""v1", W/"v2""#)]);
+    assert!(req.is_fresh(Some(&etag), None));
+    assert!(!req.is_fresh(Some(&ETag::strong("v3")), None));
+    assert!(!req.is_fresh(None, None));
+
+    let req = request(Method::Head, &[("If-None-Match", "*")]);
+    assert!(req.is_fresh(Some(&etag), None));
+}
+
+#[test]
+fn test_is_fresh_only_for_safe_methods() {
+    let req = request(Method::Post, &[("If-None-Match", r#""v1""#)]);
+    assert!(!req.is_fresh(Some(&ETag::strong("v1")), None));
+}
+
+#[test]
+fn test_is_fresh_if_modified_since() {
+    let req = request(Method::Get, &[("If-Modified-Since", JAN_16)]);
+    assert!(req.is_fresh(None, Some(JAN_16_SECS)));
+    assert!(req.is_fresh(None, Some(JAN_16_SECS - 60)));
+    assert!(!req.is_fresh(None, Some(JAN_16_SECS + 1)));
+    assert!(!req.is_fresh(None, None));
+
+    let req = request(Method::Get, &[("If-Modified-Since", "last week")]);
+    assert!(!req.is_fresh(None, Some(0)));
+}
+
+#[test]
+fn test_if_none_match_takes_precedence_over_date() {
+    let req = request(
+        Method::Get,
+        &[("If-None-Match", r#""old""#), ("If-Modified-Since", JAN_16)],
+    );
+    assert!(!req.is_fresh(Some(&ETag::strong("new")), Some(JAN_16_SECS)));
+}
+
+#[test]
+fn test_check_preconditions_if_match() {
+    let current = ETag::strong("7");
+    let req = request(Method::Put, &[("If-Match", r#""6", "7""#)]);
+    assert!(req.check_preconditions(Some(&current), None).is_ok());
+
+    let req = request(Method::Put, &[("If-Match", r#""6""#)]);
+    let err = req.check_preconditions(Some(&current), None).unwrap_err();
+    assert_eq!(err.status(), 412);
+    assert_eq!(err.title(), "Precondition Failed");
+
+    // Weak tags never satisfy If-Match
+    let req = request(Method::Patch, &[("If-Match", r#"W/"7""#)]);
+    assert!(req.check_preconditions(Some(&current), None).is_err());
+}
+
+#[test]
+fn test_check_preconditions_wildcard_requires_existing() {
+    let req = request(Method::Put, &[("If-Match", "*")]);
+    assert!(
+        req.check_preconditions(Some(&ETag::weak("x")), None)
+            .is_ok()
+    );
+    assert!(req.check_preconditions(None, None).is_err());
+}
+
+#[test]
+fn test_check_preconditions_if_unmodified_since() {
+    let req = request(Method::Delete, &[("If-Unmodified-Since", JAN_16)]);
+    assert!(req.check_preconditions(None, Some(JAN_16_SECS)).is_ok());
+    assert!(
+        req.check_preconditions(None, Some(JAN_16_SECS + 1))
+            .is_err()
+    );
+    assert!(req.check_preconditions(None, None).is_ok());
+}
+
+#[test]
+fn test_check_preconditions_without_headers() {
+    let req = request(Method::Put, &[]);
+    assert!(req.check_preconditions(None, None).is_ok());
+}
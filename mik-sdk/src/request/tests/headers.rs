@@ -163,6 +163,8 @@ fn test_all_http_methods() {
     assert_eq!(Method::Delete.as_str(), "DELETE");
     assert_eq!(Method::Head.as_str(), "HEAD");
     assert_eq!(Method::Options.as_str(), "OPTIONS");
+    assert_eq!(Method::Trace.as_str(), "TRACE");
+    assert_eq!(Method::Other("PROPFIND".to_string()).as_str(), "PROPFIND");
 }
 
 #[test]
@@ -227,6 +229,31 @@ fn test_accepts_multiple() {
     assert!(!req.accepts("xml"));
 }
 
+#[test]
+fn test_negotiate_uses_accept_header() {
+    let req = Request::new(
+        Method::Get,
+        "/".to_string(),
+        vec![(
+            "Accept".to_string(),
+            "application/json;q=0.9, text/csv".to_string(),
+        )],
+        None,
+        HashMap::new(),
+    );
+    assert_eq!(
+        req.negotiate(&["application/json", "text/csv"]),
+        Some("text/csv")
+    );
+    assert_eq!(req.negotiate(&["application/xml"]), None);
+
+    let req = Request::new(Method::Get, "/".to_string(), vec![], None, HashMap::new());
+    assert_eq!(
+        req.negotiate(&["application/json", "text/csv"]),
+        Some("application/json")
+    );
+}
+
 #[test]
 fn test_accepts_missing_header() {
     let req = Request::new(Method::Get, "/".to_string(), vec![], None, HashMap::new());
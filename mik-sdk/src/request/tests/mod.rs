@@ -1,6 +1,7 @@
 #![allow(clippy::iter_on_single_items)]
 
 mod body;
+mod conditional;
 mod cookies;
 mod form;
 mod headers;
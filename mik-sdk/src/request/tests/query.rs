@@ -15,7 +15,7 @@ fn test_request_basics() {
             .collect(),
     );
 
-    assert_eq!(req.method(), Method::Get);
+    assert_eq!(req.method(), &Method::Get);
     assert_eq!(req.path(), "/users/123?page=2");
     assert_eq!(req.path_without_query(), "/users/123");
     assert_eq!(req.param_or("id", ""), "123");
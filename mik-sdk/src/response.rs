@@ -0,0 +1,605 @@
+//! Handler return values and RFC 7807 errors.
+//!
+//! `routes!` handlers may return anything implementing [`IntoResponse`] -
+//! including `Result<T, E>` when both sides do, so fallible steps can use `?`:
+//!
+//! ```ignore
+//! fn get_user(path: Id, _req: &Request) -> Result<Response, ApiError> {
+//!     let id: i64 = path.parse()?;                 // ParseError -> 400
+//!     let user = find_user(id).ok_or_else(|| ApiError::not_found("User not found"))?;
+//!     Ok(ok!({ "id": user.id, "name": user.name }))
+//! }
+//! ```
+//!
+//! [`ApiError`] renders the same problem JSON as `error!`.
+
+use crate::constants::{
+    HEADER_CONTENT_TYPE, MIME_CSV, MIME_JSON, MIME_NDJSON, MIME_PROBLEM_JSON, status_title,
+};
+use crate::http_client;
+use crate::json::{self, JsonValue, ToJson};
+use crate::typed::{ParseError, ValidationError, ValidationErrors};
+
+/// Status, headers and body of an HTTP response.
+///
+/// The component-neutral form of the `handler::Response` generated by
+/// `cargo-component`; `routes!` converts between the two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResponseParts {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: Vec<(String, String)>,
+    /// Response body, if any.
+    pub body: Option<Vec<u8>>,
+    /// Set by `Streaming`: the body follows as chunks.
+    streamed: bool,
+}
+
+impl ResponseParts {
+    /// Create a response from its parts.
+    #[must_use]
+    pub const fn new(status: u16, headers: Vec<(String, String)>, body: Option<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+            streamed: false,
+        }
+    }
+
+    /// Mark the response as the head of a streamed body.
+    pub(crate) const fn streamed(mut self) -> Self {
+        self.streamed = true;
+        self
+    }
+
+    /// Whether this response was produced by a `Streaming` response.
+    ///
+    /// This is an implementation detail of `routes!` and should not be used directly.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn __is_streamed(&self) -> bool {
+        self.streamed
+    }
+
+    /// Add a response header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Types a `routes!` handler can return.
+///
+/// Implemented for `handler::Response` (by `routes!`), [`ApiError`],
+/// [`JsonValue`] (a `200` JSON response) and `Result<T, E>` where both
+/// `T` and `E` implement it.
+///
+/// ```
+/// # use mik_sdk::response::{ApiError, IntoResponse};
+/// let result: Result<mik_sdk::json::JsonValue, ApiError> = Err(ApiError::not_found("No such user"));
+/// assert_eq!(result.into_response().status, 404);
+/// ```
+pub trait IntoResponse {
+    /// Convert into response parts.
+    fn into_response(self) -> ResponseParts;
+}
+
+impl IntoResponse for ResponseParts {
+    fn into_response(self) -> ResponseParts {
+        self
+    }
+}
+
+impl IntoResponse for JsonValue {
+    fn into_response(self) -> ResponseParts {
+        json_response(200, &self)
+    }
+}
+
+impl<T: IntoResponse, E: IntoResponse> IntoResponse for Result<T, E> {
+    fn into_response(self) -> ResponseParts {
+        match self {
+            Ok(value) => value.into_response(),
+            Err(error) => error.into_response(),
+        }
+    }
+}
+
+/// Return values of a handler whose route declares `-> T`.
+///
+/// `routes!` serializes the value with [`ToJson`] and sends it with the
+/// route's `#[status(...)]` code, so the declared schema is the wire format.
+/// Implemented for `T` itself and for `Result<T, E>` where `E: IntoResponse`.
+///
+/// ```
+/// # use mik_sdk::response::{ApiError, IntoTypedResponse};
+/// let names = vec!["ada".to_string()];
+/// let response = IntoTypedResponse::<Vec<String>>::into_typed_response(names, 200);
+/// assert_eq!(response.body.unwrap(), br#"["ada"]"#);
+///
+/// let missing: Result<String, ApiError> = Err(ApiError::not_found("No such user"));
+/// assert_eq!(missing.into_typed_response(200).status, 404);
+/// ```
+///
+/// Returning anything else is a compile error:
+///
+/// ```compile_fail
+/// # use mik_sdk::response::IntoTypedResponse;
+/// IntoTypedResponse::<String>::into_typed_response(42_i64, 200);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "handler returns `{Self}`, but its route declares `-> {T}`",
+    label = "expected `{T}` or `Result<{T}, E>`",
+    note = "return the declared type, or remove `-> {T}` from the route to return any response"
+)]
+pub trait IntoTypedResponse<T> {
+    /// Serialize into a JSON response with the given success status.
+    fn into_typed_response(self, status: u16) -> ResponseParts;
+}
+
+impl<T: ToJson> IntoTypedResponse<T> for T {
+    fn into_typed_response(self, status: u16) -> ResponseParts {
+        json_response(status, &self.to_json())
+    }
+}
+
+impl<T: ToJson, E: IntoResponse> IntoTypedResponse<T> for Result<T, E> {
+    fn into_typed_response(self, status: u16) -> ResponseParts {
+        match self {
+            Ok(value) => value.into_typed_response(status),
+            Err(error) => error.into_response(),
+        }
+    }
+}
+
+fn json_response(status: u16, body: &JsonValue) -> ResponseParts {
+    ResponseParts::new(
+        status,
+        vec![(HEADER_CONTENT_TYPE.to_string(), MIME_JSON.to_string())],
+        Some(body.to_bytes()),
+    )
+}
+
+/// A `200` CSV response of `items` (see [`json::to_csv`]).
+///
+/// ```
+/// # use mik_sdk::{json, response};
+/// let rows = vec![json::obj().set("id", json::int(1))];
+/// let response = response::csv(&rows);
+/// assert_eq!(response.body.unwrap(), b"id\r\n1\r\n");
+/// ```
+#[must_use]
+pub fn csv<T: ToJson>(items: &[T]) -> ResponseParts {
+    ResponseParts::new(
+        200,
+        vec![(
+            HEADER_CONTENT_TYPE.to_string(),
+            format!("{MIME_CSV}; charset=utf-8"),
+        )],
+        Some(json::to_csv(items).into_bytes()),
+    )
+}
+
+/// A `200` newline-delimited JSON response of `items` (see [`json::to_ndjson`]).
+#[must_use]
+pub fn ndjson<T: ToJson>(items: &[T]) -> ResponseParts {
+    ResponseParts::new(
+        200,
+        vec![(HEADER_CONTENT_TYPE.to_string(), MIME_NDJSON.to_string())],
+        Some(json::to_ndjson(items).into_bytes()),
+    )
+}
+
+/// An RFC 7807 problem response, the `?`-friendly counterpart of `error!`.
+///
+/// The title defaults to the standard reason phrase of the status.
+///
+/// ```
+/// # use mik_sdk::response::{ApiError, IntoResponse};
+/// let error = ApiError::new(409)
+///     .with_detail("Email already registered")
+///     .with_type("urn:problem:duplicate-email")
+///     .with_meta("field", "email")
+///     .with_header("Retry-After", "0");
+///
+/// let response = error.into_response();
+/// assert_eq!(response.status, 409);
+/// assert_eq!(
+///     String::from_utf8(response.body.unwrap()).unwrap(),
+///     r#"{"detail":"Email already registered","field":"email","status":409,"title":"Conflict","type":"urn:problem:duplicate-email"}"#
+/// );
+/// ```
+// Optional strings are boxed to keep `Result<T, ApiError>` under clippy's
+// `result_large_err` threshold.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    status: u16,
+    title: Option<Box<str>>,
+    detail: Option<Box<str>>,
+    problem_type: Option<Box<str>>,
+    instance: Option<Box<str>>,
+    meta: Vec<(String, JsonValue)>,
+    headers: Vec<(String, String)>,
+}
+
+impl ApiError {
+    /// An error with the given status code.
+    #[must_use]
+    pub const fn new(status: u16) -> Self {
+        Self {
+            status,
+            title: None,
+            detail: None,
+            problem_type: None,
+            instance: None,
+            meta: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// `400 Bad Request` with a detail message.
+    #[must_use]
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::new(400).with_detail(detail)
+    }
+
+    /// `401 Unauthorized` with a detail message.
+    #[must_use]
+    pub fn unauthorized(detail: impl Into<String>) -> Self {
+        Self::new(401).with_detail(detail)
+    }
+
+    /// `403 Forbidden` with a detail message.
+    #[must_use]
+    pub fn forbidden(detail: impl Into<String>) -> Self {
+        Self::new(403).with_detail(detail)
+    }
+
+    /// `404 Not Found` with a detail message.
+    #[must_use]
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(404).with_detail(detail)
+    }
+
+    /// `406 Not Acceptable`, listing the media types the route can produce.
+    ///
+    /// Pair with [`Request::negotiate`](crate::Request::negotiate); the
+    /// `available` member carries the same list for clients.
+    #[must_use]
+    pub fn not_acceptable(available: &[&str]) -> Self {
+        Self::new(406)
+            .with_detail(format!("Supported media types: {}", available.join(", ")))
+            .with_meta("available", available)
+    }
+
+    /// `409 Conflict` with a detail message.
+    #[must_use]
+    pub fn conflict(detail: impl Into<String>) -> Self {
+        Self::new(409).with_detail(detail)
+    }
+
+    /// `412 Precondition Failed` with a detail message.
+    #[must_use]
+    pub fn precondition_failed(detail: impl Into<String>) -> Self {
+        Self::new(412).with_detail(detail)
+    }
+
+    /// `422 Unprocessable Entity` with a detail message.
+    #[must_use]
+    pub fn unprocessable(detail: impl Into<String>) -> Self {
+        Self::new(422).with_detail(detail)
+    }
+
+    /// `500 Internal Server Error` with a detail message.
+    #[must_use]
+    pub fn internal(detail: impl Into<String>) -> Self {
+        Self::new(500).with_detail(detail)
+    }
+
+    /// Override the title (defaults to the status reason phrase).
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into().into_boxed_str());
+        self
+    }
+
+    /// Set the human-readable detail message.
+    #[must_use]
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into().into_boxed_str());
+        self
+    }
+
+    /// Set the problem `type` URI (defaults to `about:blank`).
+    #[must_use]
+    pub fn with_type(mut self, problem_type: impl Into<String>) -> Self {
+        self.problem_type = Some(problem_type.into().into_boxed_str());
+        self
+    }
+
+    /// Set the `instance` URI identifying this occurrence.
+    #[must_use]
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into().into_boxed_str());
+        self
+    }
+
+    /// Add an extension member to the problem body.
+    #[must_use]
+    pub fn with_meta(mut self, key: impl Into<String>, value: impl ToJson) -> Self {
+        self.meta.push((key.into(), value.to_json()));
+        self
+    }
+
+    /// Add a response header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// The HTTP status code.
+    #[must_use]
+    pub const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The problem title.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        self.title
+            .as_deref()
+            .unwrap_or_else(|| status_title(self.status))
+    }
+
+    /// The detail message, if set.
+    #[must_use]
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// The problem details JSON body.
+    #[must_use]
+    pub fn to_json(&self) -> JsonValue {
+        let mut body = json::obj()
+            .set(
+                "type",
+                json::str(self.problem_type.as_deref().unwrap_or("about:blank")),
+            )
+            .set("title", json::str(self.title()))
+            .set("status", json::int(i64::from(self.status)));
+        if let Some(detail) = &self.detail {
+            body = body.set("detail", json::str(detail));
+        }
+        if let Some(instance) = &self.instance {
+            body = body.set("instance", json::str(instance));
+        }
+        self.meta
+            .iter()
+            .fold(body, |body, (key, value)| body.set(key, value.clone()))
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.status, self.title())?;
+        if let Some(detail) = &self.detail {
+            write!(f, ": {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> ResponseParts {
+        let body = self.to_json().to_bytes();
+        let mut headers = vec![(
+            HEADER_CONTENT_TYPE.to_string(),
+            MIME_PROBLEM_JSON.to_string(),
+        )];
+        headers.extend(self.headers);
+        ResponseParts::new(self.status, headers, Some(body))
+    }
+}
+
+/// `400 Bad Request`, as for a typed input that fails to parse.
+impl From<ParseError> for ApiError {
+    fn from(error: ParseError) -> Self {
+        Self::bad_request(error.to_string())
+    }
+}
+
+/// `422 Unprocessable Entity` with `field` and `constraint` extension members.
+impl From<ValidationError> for ApiError {
+    fn from(error: ValidationError) -> Self {
+        Self::unprocessable(error.to_string())
+            .with_meta("field", error.field())
+            .with_meta("constraint", error.constraint())
+    }
+}
+
+/// `422 Unprocessable Entity` with an `errors` extension member listing every
+/// violation as `{"field", "constraint", "message"}`.
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        let detail = match errors.len() {
+            1 => errors.errors()[0].to_string(),
+            count => format!("{count} validation errors"),
+        };
+        Self::unprocessable(detail).with_meta("errors", errors)
+    }
+}
+
+/// `504 Gateway Timeout` for timeouts, `502 Bad Gateway` otherwise.
+///
+/// The detail is generic: upstream error messages can expose internal hosts.
+impl From<http_client::Error> for ApiError {
+    fn from(error: http_client::Error) -> Self {
+        match error {
+            http_client::Error::Timeout { .. } => {
+                Self::new(504).with_detail("Upstream request timed out")
+            },
+            _ => Self::new(502).with_detail("Upstream request failed"),
+        }
+    }
+}
+
+/// `400 Bad Request` for a malformed pagination cursor.
+#[cfg(feature = "sql")]
+impl From<mik_sql::CursorError> for ApiError {
+    fn from(error: mik_sql::CursorError) -> Self {
+        Self::bad_request(format!("Invalid cursor: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body_of(response: &ResponseParts) -> String {
+        String::from_utf8(response.body.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_api_error_matches_error_macro_shape() {
+        let response = ApiError::not_found("User not found")
+            .with_instance("/users/7")
+            .into_response();
+        assert_eq!(response.status, 404);
+        assert_eq!(
+            response.headers,
+            vec![("content-type".to_string(), MIME_PROBLEM_JSON.to_string())]
+        );
+        assert_eq!(
+            body_of(&response),
+            r#"{"detail":"User not found","instance":"/users/7","status":404,"title":"Not Found","type":"about:blank"}"#
+        );
+    }
+
+    #[test]
+    fn test_custom_title_and_headers() {
+        let error = ApiError::new(429)
+            .with_title("Slow Down")
+            .with_header("Retry-After", "30");
+        assert_eq!(error.to_string(), "429 Slow Down");
+        let response = error.into_response();
+        assert_eq!(response.headers.len(), 2);
+        assert_eq!(response.headers[1].0, "Retry-After");
+        assert!(body_of(&response).contains(r#""title":"Slow Down""#));
+    }
+
+    #[test]
+    fn test_from_parse_and_validation_errors() {
+        let error = ApiError::from(ParseError::missing("email"));
+        assert_eq!(error.status(), 400);
+        assert_eq!(error.detail(), Some("missing required field `email`"));
+
+        let response = ApiError::from(ValidationError::min("name", 3)).into_response();
+        assert_eq!(response.status, 422);
+        let body = body_of(&response);
+        assert!(body.contains(r#""field":"name""#));
+        assert!(body.contains(r#""constraint":"min""#));
+    }
+
+    #[test]
+    fn test_from_validation_errors_lists_every_violation() {
+        let mut errors = ValidationErrors::new();
+        errors.push(ValidationError::min("name", 3));
+        errors.push(ValidationError::format("items[1].email", "email"));
+        let response = ApiError::from(errors).into_response();
+        assert_eq!(response.status, 422);
+        assert_eq!(
+            body_of(&response),
+            r#"{"detail":"2 validation errors","errors":[{"constraint":"min","field":"name","message":"`name` must be at least 3"},{"constraint":"format","field":"items[1].email","message":"`items[1].email` must be a valid email"}],"status":422,"title":"Unprocessable Entity","type":"about:blank"}"#
+        );
+
+        let single = ApiError::from(ValidationErrors::from(ValidationError::max("age", 150)));
+        assert_eq!(single.detail(), Some("`age` must be at most 150"));
+    }
+
+    #[test]
+    fn test_from_http_client_error() {
+        let timeout = http_client::Error::Timeout {
+            timeout_ms: Some(100),
+        };
+        assert_eq!(ApiError::from(timeout).status(), 504);
+        let refused = http_client::Error::ConnectionError("refused by 10.0.0.7".to_string());
+        let error = ApiError::from(refused);
+        assert_eq!(error.status(), 502);
+        assert_eq!(error.detail(), Some("Upstream request failed"));
+    }
+
+    #[cfg(feature = "sql")]
+    #[test]
+    fn test_from_cursor_error() {
+        let error = ApiError::from(mik_sql::CursorError::InvalidBase64);
+        assert_eq!(error.status(), 400);
+        assert_eq!(
+            error.detail(),
+            Some("Invalid cursor: invalid base64 encoding in cursor")
+        );
+    }
+
+    #[test]
+    fn test_typed_response_uses_declared_status() {
+        let response = IntoTypedResponse::<i64>::into_typed_response(7, 201);
+        assert_eq!(response.status, 201);
+        assert_eq!(
+            response.headers,
+            vec![("content-type".to_string(), MIME_JSON.to_string())]
+        );
+        assert_eq!(body_of(&response), "7");
+
+        let ok: Result<Option<String>, ApiError> = Ok(None);
+        assert_eq!(body_of(&ok.into_typed_response(200)), "null");
+    }
+
+    #[test]
+    fn test_result_and_json_into_response() {
+        let ok: Result<JsonValue, ApiError> = Ok(json::obj().set("id", json::int(1)));
+        let response = ok.into_response();
+        assert_eq!(response.status, 200);
+        assert_eq!(body_of(&response), r#"{"id":1}"#);
+
+        let err: Result<JsonValue, ApiError> = Err(ApiError::forbidden("No"));
+        assert_eq!(err.into_response().status, 403);
+    }
+
+    #[test]
+    fn test_not_acceptable() {
+        let response = ApiError::not_acceptable(&[MIME_JSON, MIME_CSV]).into_response();
+        assert_eq!(response.status, 406);
+        assert_eq!(
+            body_of(&response),
+            r#"{"available":["application/json","text/csv"],"detail":"Supported media types: application/json, text/csv","status":406,"title":"Not Acceptable","type":"about:blank"}"#
+        );
+    }
+
+    #[test]
+    fn test_csv_and_ndjson_responses() {
+        let rows = vec![json::obj().set("id", json::int(1))];
+
+        let response = csv(&rows).with_header("Vary", "Accept");
+        assert_eq!(
+            response.headers,
+            vec![
+                (
+                    HEADER_CONTENT_TYPE.to_string(),
+                    "text/csv; charset=utf-8".to_string()
+                ),
+                ("Vary".to_string(), "Accept".to_string()),
+            ]
+        );
+        assert_eq!(body_of(&response), "id\r\n1\r\n");
+
+        let response = ndjson(&rows);
+        assert_eq!(response.headers[0].1, MIME_NDJSON);
+        assert_eq!(body_of(&response), "{\"id\":1}\n");
+    }
+}
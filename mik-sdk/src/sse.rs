@@ -0,0 +1,258 @@
+//! Server-Sent Events (`text/event-stream`).
+//!
+//! Build [`Event`]s, then send them all at once with [`response`] (or the
+//! `sse!` macro), or one at a time as they are produced with [`stream`]:
+//!
+//! ```
+//! # use mik_sdk::{json, sse::{self, Event}};
+//! let events = vec![
+//!     Event::new().event("greeting").id("1").data("hello\nworld"),
+//!     Event::new().json(&json::obj().set("count", json::int(2))),
+//! ];
+//! assert_eq!(
+//!     sse::encode(events),
+//!     "event: greeting\nid: 1\ndata: hello\ndata: world\n\ndata: {\"count\":2}\n\n"
+//! );
+//! ```
+//!
+//! Multi-line data becomes one `data:` line per line, which clients join back
+//! with `\n`. Line breaks are removed from `event` and `id`, where the format
+//! has no way to carry them.
+
+use crate::constants::{HEADER_CACHE_CONTROL, HEADER_CONTENT_TYPE, MIME_EVENT_STREAM};
+use crate::json::ToJson;
+use crate::response::ResponseParts;
+use crate::stream::Streaming;
+use std::fmt::{self, Write};
+
+/// One event of an event stream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Event {
+    comment: Option<String>,
+    kind: Option<String>,
+    id: Option<String>,
+    retry: Option<u32>,
+    data: Option<String>,
+}
+
+impl Event {
+    /// An empty event; add fields with the builder methods.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            comment: None,
+            kind: None,
+            id: None,
+            retry: None,
+            data: None,
+        }
+    }
+
+    /// Set the event type (clients default to `message`).
+    #[must_use]
+    pub fn event(mut self, name: impl Into<String>) -> Self {
+        self.kind = Some(strip_line_breaks(name.into()));
+        self
+    }
+
+    /// Set the event ID, which clients send back as `Last-Event-ID` on reconnect.
+    #[must_use]
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        // Clients ignore IDs containing NUL
+        self.id = Some(strip_line_breaks(id.into()).replace('\0', ""));
+        self
+    }
+
+    /// Set the reconnection delay, in milliseconds.
+    #[must_use]
+    pub const fn retry(mut self, millis: u32) -> Self {
+        self.retry = Some(millis);
+        self
+    }
+
+    /// Append data; each call (and each line of `data`) is one `data:` line.
+    #[must_use]
+    pub fn data(mut self, data: impl AsRef<str>) -> Self {
+        let data = data.as_ref();
+        match &mut self.data {
+            Some(existing) => {
+                existing.push('\n');
+                existing.push_str(data);
+            },
+            None => self.data = Some(data.to_string()),
+        }
+        self
+    }
+
+    /// Append a value serialized as JSON.
+    #[must_use]
+    pub fn json(self, value: &impl ToJson) -> Self {
+        self.data(value.to_json().to_string())
+    }
+
+    /// Add a comment line, ignored by clients; useful as a keep-alive.
+    #[must_use]
+    pub fn comment(mut self, text: impl Into<String>) -> Self {
+        self.comment = Some(text.into());
+        self
+    }
+}
+
+/// The wire format: one `field: value` line per field, then a blank line.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(comment) = &self.comment {
+            for line in lines(comment) {
+                writeln!(f, ": {line}")?;
+            }
+        }
+        if let Some(event) = &self.kind {
+            writeln!(f, "event: {event}")?;
+        }
+        if let Some(id) = &self.id {
+            writeln!(f, "id: {id}")?;
+        }
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {retry}")?;
+        }
+        if let Some(data) = &self.data {
+            for line in lines(data) {
+                writeln!(f, "data: {line}")?;
+            }
+        }
+        f.write_char('\n')
+    }
+}
+
+/// Split on `\r\n`, `\r` or `\n`, the line endings the format accepts.
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.split('\n')
+        .flat_map(|line| line.strip_suffix('\r').unwrap_or(line).split('\r'))
+}
+
+fn strip_line_breaks(mut value: String) -> String {
+    value.retain(|c| c != '\r' && c != '\n');
+    value
+}
+
+/// Headers of an event stream: the content type, and no caching.
+fn headers() -> Vec<(String, String)> {
+    vec![
+        (
+            HEADER_CONTENT_TYPE.to_string(),
+            MIME_EVENT_STREAM.to_string(),
+        ),
+        (HEADER_CACHE_CONTROL.to_string(), "no-cache".to_string()),
+    ]
+}
+
+/// Serialize events into an event-stream body.
+pub fn encode(events: impl IntoIterator<Item = Event>) -> String {
+    events.into_iter().map(|event| event.to_string()).collect()
+}
+
+/// A `200` event-stream response carrying all `events`.
+///
+/// ```
+/// # use mik_sdk::sse::{self, Event};
+/// let response = sse::response([Event::new().data("ready")]);
+/// assert_eq!(response.headers[0].1, "text/event-stream");
+/// assert_eq!(response.body.unwrap(), b"data: ready\n\n");
+/// ```
+#[must_use]
+pub fn response(events: impl IntoIterator<Item = Event>) -> ResponseParts {
+    ResponseParts::new(200, headers(), Some(encode(events).into_bytes()))
+}
+
+/// A `200` event-stream response sending each event as it is produced.
+///
+/// ```
+/// # use mik_sdk::sse::{self, Event};
+/// let ticks = (1..=3).map(|n| Event::new().event("tick").id(n.to_string()).data(n.to_string()));
+/// let response = sse::stream(ticks);
+/// # let _ = response;
+/// ```
+#[must_use]
+pub fn stream<I>(events: I) -> Streaming
+where
+    I: IntoIterator<Item = Event>,
+    I::IntoIter: 'static,
+{
+    headers().into_iter().fold(
+        Streaming::new(events.into_iter().map(|event| event.to_string())),
+        |response, (name, value)| response.with_header(name, value),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    #[test]
+    fn test_all_fields() {
+        let event = Event::new()
+            .comment("hi")
+            .event("update")
+            .id("42")
+            .retry(3000)
+            .data("payload");
+        assert_eq!(
+            event.to_string(),
+            ": hi\nevent: update\nid: 42\nretry: 3000\ndata: payload\n\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_line_data() {
+        let event = Event::new().data("a\r\nb\rc\nd").data("e");
+        assert_eq!(
+            event.to_string(),
+            "data: a\ndata: b\ndata: c\ndata: d\ndata: e\n\n"
+        );
+        assert_eq!(Event::new().data("").to_string(), "data: \n\n");
+        assert_eq!(Event::new().data("x\n").to_string(), "data: x\ndata: \n\n");
+    }
+
+    #[test]
+    fn test_event_and_id_cannot_break_lines() {
+        let event = Event::new().event("a\nevent: b").id("1\r\n2\0");
+        assert_eq!(event.to_string(), "event: aevent: b\nid: 12\n\n");
+    }
+
+    #[test]
+    fn test_json_data() {
+        let value = json::obj().set("msg", json::str("line\nbreak"));
+        assert_eq!(
+            Event::new().json(&value).to_string(),
+            "data: {\"msg\":\"line\\nbreak\"}\n\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_event_and_comment_only() {
+        assert_eq!(Event::new().to_string(), "\n");
+        assert_eq!(
+            Event::new().comment("keep\nalive").to_string(),
+            ": keep\n: alive\n\n"
+        );
+    }
+
+    #[test]
+    fn test_response_and_stream() {
+        let events = || [Event::new().data("1"), Event::new().data("2")];
+        let response = response(events());
+        assert_eq!(
+            response.headers,
+            vec![
+                ("content-type".to_string(), "text/event-stream".to_string()),
+                ("cache-control".to_string(), "no-cache".to_string()),
+            ]
+        );
+        assert_eq!(response.body.unwrap(), b"data: 1\n\ndata: 2\n\n");
+
+        let streamed = stream(events());
+        assert_eq!(streamed.headers(), response.headers.as_slice());
+        assert_eq!(streamed.collect_body(), b"data: 1\n\ndata: 2\n\n");
+    }
+}
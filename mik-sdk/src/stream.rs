@@ -0,0 +1,242 @@
+//! Streaming response bodies.
+//!
+//! A [`Streaming`] response sends its status and headers first, then the body
+//! one chunk at a time: the bridge pulls the next chunk only after writing the
+//! previous one, so large exports never sit in memory all at once.
+//!
+//! ```
+//! # use mik_sdk::stream::Streaming;
+//! let pages = (1..=3).map(|page| format!("page {page}\n"));
+//! let response = Streaming::new(pages).content_type("text/plain; charset=utf-8");
+//! # let _ = response;
+//! ```
+//!
+//! Chunks can also be produced on demand, e.g. one database page per call:
+//!
+//! ```
+//! # use mik_sdk::stream::Streaming;
+//! let mut offset = 0;
+//! let response = Streaming::from_fn(move || {
+//!     offset += 100;
+//!     (offset <= 300).then(|| format!("rows up to {offset}\n").into_bytes())
+//! });
+//! # let _ = response;
+//! ```
+//!
+//! Return it from a `routes!` handler like any other response. Streamed
+//! bodies are not compressed by the bridge, and `HEAD` requests get the
+//! headers only. An `after` hook may edit the response; if it returns a
+//! different response instead, the stream is dropped.
+
+use crate::constants::{HEADER_CONTENT_TYPE, MIME_NDJSON};
+use crate::json::{self, ToJson};
+use crate::response::{IntoResponse, ResponseParts};
+use std::cell::RefCell;
+
+type ChunkIter = Box<dyn Iterator<Item = Vec<u8>>>;
+
+thread_local! {
+    /// Body of the `Streaming` response the current handler returned.
+    static PENDING: RefCell<Option<BodyChunks>> = const { RefCell::new(None) };
+}
+
+/// A response whose body is produced incrementally.
+pub struct Streaming {
+    status: u16,
+    headers: Vec<(String, String)>,
+    chunks: ChunkIter,
+}
+
+impl Streaming {
+    /// A `200` response streaming the given chunks in order.
+    #[must_use]
+    pub fn new<I>(chunks: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Vec<u8>> + 'static,
+        I::IntoIter: 'static,
+    {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            chunks: Box::new(chunks.into_iter().map(Into::into)),
+        }
+    }
+
+    /// A `200` response whose chunks come from `next`, until it returns `None`.
+    #[must_use]
+    pub fn from_fn(next: impl FnMut() -> Option<Vec<u8>> + 'static) -> Self {
+        Self::new(std::iter::from_fn(next))
+    }
+
+    /// A `200` newline-delimited JSON response, one item per chunk.
+    ///
+    /// The streaming counterpart of [`response::ndjson`](crate::response::ndjson).
+    #[must_use]
+    pub fn ndjson<I>(items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToJson + 'static,
+        I::IntoIter: 'static,
+    {
+        let lines = items
+            .into_iter()
+            .map(|item| json::to_ndjson(std::slice::from_ref(&item)));
+        Self::new(lines).content_type(MIME_NDJSON)
+    }
+
+    /// Set the status code (defaults to `200`).
+    #[must_use]
+    pub const fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Add a response header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the `Content-Type` header.
+    #[must_use]
+    pub fn content_type(self, mime: impl Into<String>) -> Self {
+        self.with_header(HEADER_CONTENT_TYPE, mime)
+    }
+
+    /// The status code.
+    #[must_use]
+    pub const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The response headers.
+    #[must_use]
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Consume the response, returning all chunks joined into one body.
+    #[must_use]
+    pub fn collect_body(self) -> Vec<u8> {
+        self.chunks.flatten().collect()
+    }
+}
+
+impl std::fmt::Debug for Streaming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Streaming")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the status and headers; the chunks are handed to `routes!`,
+/// which passes them to the bridge after the response head.
+impl IntoResponse for Streaming {
+    fn into_response(self) -> ResponseParts {
+        PENDING.with(|pending| *pending.borrow_mut() = Some(BodyChunks::new(self.chunks)));
+        ResponseParts::new(self.status, self.headers, None).streamed()
+    }
+}
+
+/// The chunks of a streamed body, as exported to the bridge.
+///
+/// `routes!` wraps this in the `body-stream` resource of `mik:core/handler`.
+pub struct BodyChunks(RefCell<ChunkIter>);
+
+impl BodyChunks {
+    fn new(chunks: ChunkIter) -> Self {
+        Self(RefCell::new(chunks))
+    }
+
+    /// The next non-empty chunk, or `None` once the body is complete.
+    pub fn next_chunk(&self) -> Option<Vec<u8>> {
+        self.0.borrow_mut().find(|chunk| !chunk.is_empty())
+    }
+}
+
+impl std::fmt::Debug for BodyChunks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BodyChunks").finish_non_exhaustive()
+    }
+}
+
+/// Header `routes!` puts on the response of a `Streaming` handler, so the
+/// stream is only sent after that response and not after one an `after` hook
+/// built in its place. Removed before the response leaves the component.
+#[doc(hidden)]
+pub const __STREAM_MARKER: &str = "x-mik-streamed";
+
+/// Remove the [`__STREAM_MARKER`] header, returning whether it was present.
+///
+/// This is an implementation detail of `routes!` and should not be used directly.
+#[doc(hidden)]
+pub fn __take_marker(headers: &mut Vec<(String, String)>) -> bool {
+    let before = headers.len();
+    headers.retain(|(name, _)| !name.eq_ignore_ascii_case(__STREAM_MARKER));
+    headers.len() != before
+}
+
+/// Take the body of the last `Streaming` response, if any.
+///
+/// This is an implementation detail of `routes!` and should not be used directly.
+#[doc(hidden)]
+pub fn __take_pending() -> Option<BodyChunks> {
+    PENDING.with(|pending| pending.borrow_mut().take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(chunks: &BodyChunks) -> Vec<String> {
+        std::iter::from_fn(|| chunks.next_chunk())
+            .map(|chunk| String::from_utf8(chunk).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_into_response_hands_over_chunks() {
+        let response = Streaming::new(["a", "", "b"])
+            .with_status(201)
+            .content_type("text/plain")
+            .into_response();
+        assert_eq!(response.status, 201);
+        assert_eq!(
+            response.headers,
+            vec![(HEADER_CONTENT_TYPE.to_string(), "text/plain".to_string())]
+        );
+        assert_eq!(response.body, None);
+
+        let chunks = __take_pending().expect("pending body");
+        assert_eq!(drain(&chunks), ["a", "b"], "empty chunks are skipped");
+        assert!(__take_pending().is_none(), "taken only once");
+    }
+
+    #[test]
+    fn test_from_fn_is_lazy() {
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let _ = Streaming::from_fn(move || {
+            counter.set(counter.get() + 1);
+            (counter.get() <= 2).then(|| b"x".to_vec())
+        })
+        .into_response();
+        assert_eq!(calls.get(), 0);
+
+        let chunks = __take_pending().unwrap();
+        assert_eq!(drain(&chunks), ["x", "x"]);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_ndjson_and_collect_body() {
+        let rows = vec![json::obj().set("id", json::int(1)), json::arr()];
+        let response = Streaming::ndjson(rows);
+        assert_eq!(response.headers()[0].1, MIME_NDJSON);
+        assert_eq!(response.collect_body(), b"{\"id\":1}\n[]\n");
+    }
+}
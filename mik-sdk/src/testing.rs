@@ -0,0 +1,419 @@
+//! In-process testing for `routes!` handlers.
+//!
+//! On native targets `routes!` also emits a `__mik_test` function that runs a
+//! request through the full dispatcher - routing, typed input parsing,
+//! validation, hooks and CORS - and returns a [`TestResponse`]. No WASM
+//! toolchain, bridge or runtime is involved, so plain `cargo test` is enough.
+//!
+//! ```ignore
+//! use mik_sdk::testing::TestRequest;
+//!
+//! #[test]
+//! fn creates_users() {
+//!     __mik_test(TestRequest::post("/users").json(json!({ "name": "Alice" })))
+//!         .assert_status(201)
+//!         .assert_header("content-type", "application/json")
+//!         .assert_json_path("name", "Alice");
+//! }
+//! ```
+//!
+//! Only available on native targets (excluded from WASM builds).
+
+use crate::constants::{HEADER_CONTENT_TYPE, HEADER_CONTENT_TYPE_TITLE, MIME_JSON};
+use crate::json::{self, JsonValue, ToJson};
+use crate::{Method, Request};
+
+/// `(method, path, headers, body)` of a request.
+#[doc(hidden)]
+pub type RequestParts = (Method, String, Vec<(String, String)>, Option<Vec<u8>>);
+
+/// A request to send through `__mik_test`.
+///
+/// ```
+/// # use mik_sdk::testing::TestRequest;
+/// # use mik_sdk::Method;
+/// let request = TestRequest::put("/users/1?notify=true")
+///     .header("authorization", "Bearer token")
+///     .text("hello");
+///
+/// assert_eq!(request.method(), &Method::Put);
+/// assert_eq!(request.path(), "/users/1?notify=true");
+/// assert_eq!(request.body_bytes(), Some(&b"hello"[..]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestRequest {
+    method: Method,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl TestRequest {
+    /// A request with the given method and path (query string included).
+    #[must_use]
+    pub fn new(method: Method, path: &str) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// A `GET` request.
+    #[must_use]
+    pub fn get(path: &str) -> Self {
+        Self::new(Method::Get, path)
+    }
+
+    /// A `POST` request.
+    #[must_use]
+    pub fn post(path: &str) -> Self {
+        Self::new(Method::Post, path)
+    }
+
+    /// A `PUT` request.
+    #[must_use]
+    pub fn put(path: &str) -> Self {
+        Self::new(Method::Put, path)
+    }
+
+    /// A `PATCH` request.
+    #[must_use]
+    pub fn patch(path: &str) -> Self {
+        Self::new(Method::Patch, path)
+    }
+
+    /// A `DELETE` request.
+    #[must_use]
+    pub fn delete(path: &str) -> Self {
+        Self::new(Method::Delete, path)
+    }
+
+    /// A `HEAD` request.
+    #[must_use]
+    pub fn head(path: &str) -> Self {
+        Self::new(Method::Head, path)
+    }
+
+    /// An `OPTIONS` request.
+    #[must_use]
+    pub fn options(path: &str) -> Self {
+        Self::new(Method::Options, path)
+    }
+
+    /// Add a request header.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set a raw request body.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set a text body.
+    #[must_use]
+    pub fn text(self, text: &str) -> Self {
+        self.body(text)
+    }
+
+    /// Set a JSON body and `Content-Type: application/json` (unless already set).
+    #[must_use]
+    pub fn json(mut self, value: impl ToJson) -> Self {
+        if !self
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(HEADER_CONTENT_TYPE))
+        {
+            self = self.header(HEADER_CONTENT_TYPE_TITLE, MIME_JSON);
+        }
+        self.body(value.to_json().to_bytes())
+    }
+
+    /// The request method.
+    #[must_use]
+    pub const fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// The request path, including the query string.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The request headers, in insertion order.
+    #[must_use]
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// The request body.
+    #[must_use]
+    pub fn body_bytes(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+
+    /// Split into `(method, path, headers, body)`.
+    ///
+    /// Used by the code `routes!` generates; prefer the accessors.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn into_parts(self) -> RequestParts {
+        (self.method, self.path, self.headers, self.body)
+    }
+}
+
+impl From<Request> for TestRequest {
+    fn from(request: Request) -> Self {
+        let (method, path, headers, body) = request.into_parts();
+        Self {
+            method,
+            path,
+            headers,
+            body,
+        }
+    }
+}
+
+/// The response returned by `__mik_test`, with fluent assertions.
+///
+/// Assertions panic with the response body in the message and return
+/// `&Self`, so they chain:
+///
+/// ```
+/// # use mik_sdk::testing::TestResponse;
+/// let response = TestResponse::new(
+///     200,
+///     vec![("Content-Type".to_string(), "application/json".to_string())],
+///     Some(br#"{"user":{"name":"Alice","tags":["admin"]}}"#.to_vec()),
+/// );
+///
+/// response
+///     .assert_status(200)
+///     .assert_header("content-type", "application/json")
+///     .assert_no_header("set-cookie")
+///     .assert_json_path("user.name", "Alice")
+///     .assert_json_path("user.tags[0]", "admin");
+///
+/// assert_eq!(response.json_path("user.tags").len(), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TestResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers, in the order the handler set them.
+    pub headers: Vec<(String, String)>,
+    /// Response body, if any.
+    pub body: Option<Vec<u8>>,
+}
+
+impl TestResponse {
+    /// Create a response from its parts.
+    #[must_use]
+    pub const fn new(status: u16, headers: Vec<(String, String)>, body: Option<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// First value of a header (case-insensitive).
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All values of a header (case-insensitive).
+    #[must_use]
+    pub fn header_all(&self, name: &str) -> Vec<&str> {
+        self.headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// The body as UTF-8 text (empty if there is no body or it is not UTF-8).
+    #[must_use]
+    pub fn text(&self) -> &str {
+        self.body
+            .as_deref()
+            .and_then(|body| std::str::from_utf8(body).ok())
+            .unwrap_or_default()
+    }
+
+    /// The body parsed as JSON (null if there is no body or it is not JSON).
+    #[must_use]
+    pub fn json(&self) -> JsonValue {
+        self.body
+            .as_deref()
+            .and_then(json::try_parse)
+            .unwrap_or_else(JsonValue::null)
+    }
+
+    /// The JSON value at a dotted path such as `"user.name"` or
+    /// `"items[0].id"` (null if missing).
+    #[must_use]
+    pub fn json_path(&self, path: &str) -> JsonValue {
+        path.split(['.', '['])
+            .filter(|segment| !segment.is_empty())
+            .fold(self.json(), |value, segment| {
+                let key = segment.strip_suffix(']').unwrap_or(segment);
+                match key.parse::<usize>() {
+                    Ok(index) if !value.at(index).is_null() => value.at(index),
+                    _ => value.get(key),
+                }
+            })
+    }
+
+    /// Assert the status code.
+    #[track_caller]
+    pub fn assert_status(&self, expected: u16) -> &Self {
+        assert!(
+            self.status == expected,
+            "expected status {expected}, got {}\nbody: {}",
+            self.status,
+            self.text()
+        );
+        self
+    }
+
+    /// Assert a header is present with the given value (name is case-insensitive).
+    #[track_caller]
+    pub fn assert_header(&self, name: &str, expected: &str) -> &Self {
+        let values = self.header_all(name);
+        assert!(
+            values.contains(&expected),
+            "expected header {name}: {expected}, got {values:?}\nheaders: {:?}",
+            self.headers
+        );
+        self
+    }
+
+    /// Assert a header is absent.
+    #[track_caller]
+    pub fn assert_no_header(&self, name: &str) -> &Self {
+        assert!(
+            self.header(name).is_none(),
+            "expected no {name} header, got {:?}",
+            self.header_all(name)
+        );
+        self
+    }
+
+    /// Assert the body text.
+    #[track_caller]
+    pub fn assert_text(&self, expected: &str) -> &Self {
+        assert!(
+            self.text() == expected,
+            "expected body {expected:?}, got {:?}",
+            self.text()
+        );
+        self
+    }
+
+    /// Assert the JSON value at `path` (see [`json_path`](Self::json_path)).
+    #[track_caller]
+    pub fn assert_json_path(&self, path: &str, expected: impl ToJson) -> &Self {
+        let actual = self.json_path(path).to_string();
+        let expected = expected.to_json().to_string();
+        assert!(
+            actual == expected,
+            "expected JSON at {path:?} to be {expected}, got {actual}\nbody: {}",
+            self.text()
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn json_response(body: &str) -> TestResponse {
+        TestResponse::new(
+            200,
+            vec![("Content-Type".to_string(), MIME_JSON.to_string())],
+            Some(body.as_bytes().to_vec()),
+        )
+    }
+
+    #[test]
+    fn test_json_body_sets_content_type_once() {
+        let request = TestRequest::post("/users").json(json::obj().set("a", json::int(1)));
+        assert_eq!(request.body_bytes(), Some(&br#"{"a":1}"#[..]));
+        assert_eq!(request.headers().len(), 1);
+
+        let request = TestRequest::post("/users")
+            .header("content-type", "application/vnd.api+json")
+            .json(true);
+        assert_eq!(request.headers().len(), 1);
+        assert_eq!(request.headers()[0].1, "application/vnd.api+json");
+    }
+
+    #[test]
+    fn test_from_request() {
+        let request = Request::new(
+            Method::Delete,
+            "/users/1".to_string(),
+            vec![("x-id".to_string(), "7".to_string())],
+            Some(b"body".to_vec()),
+            HashMap::new(),
+        );
+        let (method, path, headers, body) = TestRequest::from(request).into_parts();
+        assert_eq!(method, Method::Delete);
+        assert_eq!(path, "/users/1");
+        assert_eq!(headers.len(), 1);
+        assert_eq!(body, Some(b"body".to_vec()));
+    }
+
+    #[test]
+    fn test_json_path_walks_objects_and_arrays() {
+        let response = json_response(r#"{"items":[{"id":1},{"id":2}],"meta":{"n":2}}"#);
+        assert_eq!(response.json_path("items[1].id").int(), Some(2));
+        assert_eq!(response.json_path("items.0.id").int(), Some(1));
+        assert_eq!(response.json_path("meta.n").int(), Some(2));
+        assert!(response.json_path("meta.missing").is_null());
+        assert!(response.json_path("items[9]").is_null());
+    }
+
+    #[test]
+    fn test_assert_json_path_compares_values() {
+        json_response(r#"{"tags":["a","b"],"ok":true,"n":null}"#)
+            .assert_json_path("tags", vec!["a", "b"])
+            .assert_json_path("ok", true)
+            .assert_json_path("n", None::<i64>);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected status 404, got 200")]
+    fn test_assert_status_panics_on_mismatch() {
+        json_response("{}").assert_status(404);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected header x-missing: 1")]
+    fn test_assert_header_panics_when_absent() {
+        json_response("{}").assert_header("x-missing", "1");
+    }
+
+    #[test]
+    fn test_non_json_body_is_null() {
+        let response = TestResponse::new(204, vec![], None);
+        assert!(response.json().is_null());
+        assert_eq!(response.text(), "");
+        response.assert_no_header("content-type").assert_text("");
+    }
+}
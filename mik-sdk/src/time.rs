@@ -170,7 +170,6 @@ pub fn to_millis(seconds: u64, nanoseconds: u32) -> u64 {
 /// assert_eq!(iso, "2025-01-16T10:50:00.500Z");
 /// ```
 #[must_use]
-#[allow(clippy::similar_names)] // doe/doy are standard date algorithm abbreviations
 pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
     use crate::constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
 
@@ -182,7 +181,105 @@ pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
     let minutes = remaining / SECONDS_PER_MINUTE;
     let secs = remaining % SECONDS_PER_MINUTE;
 
-    // Howard Hinnant's algorithm: https://howardhinnant.github.io/date_algorithms.html
+    let (year, m, d) = civil_from_days(days);
+
+    if nanoseconds == 0 {
+        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}Z")
+    } else {
+        let millis = nanoseconds / 1_000_000;
+        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}.{millis:03}Z")
+    }
+}
+
+/// Weekday and month names used by HTTP dates.
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Format a Unix timestamp as an HTTP date (RFC 9110 IMF-fixdate).
+///
+/// Used by `Last-Modified`, `Expires` and `Date` headers.
+///
+/// # Examples
+///
+/// ```
+/// let date = mik_sdk::time::to_http_date(1737024600);
+/// assert_eq!(date, "Thu, 16 Jan 2025 10:50:00 GMT");
+/// ```
+#[must_use]
+pub fn to_http_date(seconds: u64) -> String {
+    use crate::constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
+
+    let days = seconds / SECONDS_PER_DAY;
+    let remaining = seconds % SECONDS_PER_DAY;
+    let hours = remaining / SECONDS_PER_HOUR;
+    let minutes = remaining % SECONDS_PER_HOUR / SECONDS_PER_MINUTE;
+    let secs = remaining % SECONDS_PER_MINUTE;
+
+    let (year, month, day) = civil_from_days(days);
+    // 1970-01-01 was a Thursday
+    let weekday = WEEKDAYS[((days + 4) % 7) as usize];
+    let month = MONTHS[(month - 1) as usize];
+    format!("{weekday}, {day:02} {month} {year:04} {hours:02}:{minutes:02}:{secs:02} GMT")
+}
+
+/// Parse an HTTP date (RFC 9110 IMF-fixdate) into a Unix timestamp.
+///
+/// Returns `None` for malformed dates, dates before 1970 and the obsolete
+/// RFC 850 and asctime formats. The weekday name is not checked.
+///
+/// # Examples
+///
+/// ```
+/// let secs = mik_sdk::time::parse_http_date("Thu, 16 Jan 2025 10:50:00 GMT");
+/// assert_eq!(secs, Some(1737024600));
+/// assert_eq!(mik_sdk::time::parse_http_date("yesterday"), None);
+/// ```
+#[must_use]
+pub fn parse_http_date(date: &str) -> Option<u64> {
+    use crate::constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE};
+
+    fn number(digits: &str, len: usize) -> Option<u64> {
+        if digits.len() != len || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    let (weekday, rest) = date.trim().split_once(", ")?;
+    if !WEEKDAYS.contains(&weekday) {
+        return None;
+    }
+    let mut parts = rest.split(' ');
+    let day = number(parts.next()?, 2)?;
+    let month_name = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month_name)? as u64 + 1;
+    let year = number(parts.next()?, 4)?;
+    let mut clock = parts.next()?.split(':');
+    let hours = number(clock.next()?, 2)?;
+    let minutes = number(clock.next()?, 2)?;
+    let secs = number(clock.next()?, 2)?;
+    if parts.next()? != "GMT" || parts.next().is_some() || clock.next().is_some() {
+        return None;
+    }
+    if year < 1970 || day == 0 || day > 31 || hours > 23 || minutes > 59 || secs > 60 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    // Reject dates like 31 Feb that roll over into the next month
+    if civil_from_days(days) != (year, month, day) {
+        return None;
+    }
+    Some(days * SECONDS_PER_DAY + hours * SECONDS_PER_HOUR + minutes * SECONDS_PER_MINUTE + secs)
+}
+
+/// Convert days since the Unix epoch to `(year, month, day)`.
+///
+/// Howard Hinnant's algorithm: <https://howardhinnant.github.io/date_algorithms.html>
+#[allow(clippy::similar_names)] // doe/doy are standard date algorithm abbreviations
+const fn civil_from_days(days: u64) -> (u64, u64, u64) {
     let z = days + 719468;
     let era = z / 146097;
     let doe = z - era * 146097;
@@ -193,13 +290,19 @@ pub fn to_iso(seconds: u64, nanoseconds: u32) -> String {
     let d = doy - (153 * mp + 2) / 5 + 1;
     let m = if mp < 10 { mp + 3 } else { mp - 9 };
     let year = if m <= 2 { y + 1 } else { y };
+    (year, m, d)
+}
 
-    if nanoseconds == 0 {
-        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}Z")
-    } else {
-        let millis = nanoseconds / 1_000_000;
-        format!("{year:04}-{m:02}-{d:02}T{hours:02}:{minutes:02}:{secs:02}.{millis:03}Z")
-    }
+/// Convert a date on or after 1970-01-01 to days since the Unix epoch.
+#[allow(clippy::similar_names)] // doe/yoe are standard date algorithm abbreviations
+const fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
 }
 
 #[cfg(test)]
@@ -421,4 +524,35 @@ mod tests {
         let t2 = now_millis();
         assert!(t2 > t1, "Time should advance: {t2} should be > {t1}");
     }
+
+    #[test]
+    fn test_to_http_date() {
+        assert_eq!(to_http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(to_http_date(784111777), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(to_http_date(1709208000), "Thu, 29 Feb 2024 12:00:00 GMT");
+    }
+
+    #[test]
+    fn test_parse_http_date_roundtrip() {
+        for secs in [0, 784111777, 951782400, 1709208000, 1735689599, 4133980799] {
+            assert_eq!(parse_http_date(&to_http_date(secs)), Some(secs));
+        }
+    }
+
+    #[test]
+    fn test_parse_http_date_rejects_invalid() {
+        for date in [
+            "",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Sun, 31 Feb 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Sun, 06 Nov 1969 08:49:37 GMT",
+            "Xyz, 06 Nov 1994 08:49:37 GMT",
+        ] {
+            assert_eq!(parse_http_date(date), None, "{date}");
+        }
+    }
 }
@@ -0,0 +1,353 @@
+//! Form and multipart body decoding.
+//!
+//! [`FormData`] is the decoded body of an `application/x-www-form-urlencoded`
+//! or `multipart/form-data` request: text fields plus, for multipart, file
+//! [`Upload`]s. `#[derive(Type)]` implements [`FromForm`] on top of it, so the
+//! same struct works as `body:`, `form:` and `multipart:` input in `routes!`.
+
+use super::{FromJson, ParseError};
+use crate::json::{self, JsonValue, ToJson};
+use crate::request::Part;
+
+/// A file uploaded as a `multipart/form-data` part.
+///
+/// ```
+/// # use mik_sdk::typed::Upload;
+/// let upload = Upload::new("hello")
+///     .with_filename("hello.txt")
+///     .with_content_type("text/plain");
+///
+/// assert_eq!(upload.filename(), Some("hello.txt"));
+/// assert_eq!(upload.content_type(), Some("text/plain"));
+/// assert_eq!(upload.text(), Some("hello"));
+/// assert_eq!(upload.len(), 5);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Upload {
+    filename: Option<String>,
+    content_type: Option<String>,
+    data: Vec<u8>,
+}
+
+impl Upload {
+    /// Create an upload with the given contents.
+    #[must_use]
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            filename: None,
+            content_type: None,
+            data: data.into(),
+        }
+    }
+
+    /// Set the client-provided filename.
+    #[must_use]
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// Set the part's Content-Type.
+    #[must_use]
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// The filename from Content-Disposition, as sent by the client.
+    ///
+    /// Untrusted: never use it as a filesystem path without sanitizing.
+    #[must_use]
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// The Content-Type of the part, if specified.
+    #[must_use]
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// The raw file contents.
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The contents as UTF-8 text, if valid.
+    #[must_use]
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.data).ok()
+    }
+
+    /// Size of the contents in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the file is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Consume the upload and return its contents.
+    #[must_use]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl From<&Part<'_>> for Upload {
+    fn from(part: &Part<'_>) -> Self {
+        Self {
+            filename: part.filename().map(str::to_string),
+            content_type: part.content_type().map(str::to_string),
+            data: part.data().to_vec(),
+        }
+    }
+}
+
+/// Uploads only arrive as multipart parts, never inside JSON.
+impl FromJson for Upload {
+    fn from_json(_value: &JsonValue) -> Result<Self, ParseError> {
+        Err(ParseError::type_mismatch("value", "file"))
+    }
+}
+
+/// Serializes the upload's metadata (`filename`, `content_type`, `size`), not its contents.
+impl ToJson for Upload {
+    fn to_json(&self) -> JsonValue {
+        json::obj()
+            .set("filename", self.filename.to_json())
+            .set("content_type", self.content_type.to_json())
+            .set("size", self.data.len().to_json())
+    }
+}
+
+/// A decoded form body: text fields and file uploads, in request order.
+///
+/// ```
+/// # use mik_sdk::typed::{FormData, Upload};
+/// let form = FormData::new()
+///     .with_value("tags", "rust")
+///     .with_value("tags", "wasm")
+///     .with_value("subscribe", "on")
+///     .with_file("avatar", Upload::new(vec![0x89, 0x50]));
+///
+/// assert_eq!(form.value("tags"), Some("rust"));
+/// assert_eq!(form.values("tags"), vec!["rust", "wasm"]);
+/// assert_eq!(form.flag("subscribe"), Ok(Some(true)));
+/// assert_eq!(form.file("avatar").map(Upload::len), Some(2));
+/// assert!(form.value("avatar").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormData {
+    values: Vec<(String, String)>,
+    files: Vec<(String, Upload)>,
+}
+
+impl FormData {
+    /// An empty form.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Decode multipart parts: parts with a filename become uploads, the
+    /// rest text fields (non-UTF-8 text parts are skipped).
+    #[must_use]
+    pub fn from_parts(parts: &[Part<'_>]) -> Self {
+        let mut form = Self::new();
+        for part in parts {
+            if part.filename().is_some() {
+                form.files
+                    .push((part.name().to_string(), Upload::from(part)));
+            } else if let Some(text) = part.text() {
+                form.values
+                    .push((part.name().to_string(), text.to_string()));
+            }
+        }
+        form
+    }
+
+    /// Add a text field.
+    #[must_use]
+    pub fn with_value(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.push((name.into(), value.into()));
+        self
+    }
+
+    /// Add a file upload.
+    #[must_use]
+    pub fn with_file(mut self, name: impl Into<String>, upload: Upload) -> Self {
+        self.files.push((name.into(), upload));
+        self
+    }
+
+    /// First value of a text field.
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All values of a text field (e.g. repeated checkboxes).
+    #[must_use]
+    pub fn values(&self, name: &str) -> Vec<&str> {
+        self.values
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// First file uploaded under `name`.
+    #[must_use]
+    pub fn file(&self, name: &str) -> Option<&Upload> {
+        self.files
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, upload)| upload)
+    }
+
+    /// All files uploaded under `name`.
+    #[must_use]
+    pub fn files(&self, name: &str) -> Vec<&Upload> {
+        self.files
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, upload)| upload)
+            .collect()
+    }
+
+    /// A checkbox-style boolean field.
+    ///
+    /// Accepts `true`/`false`, `on`/`off` and `1`/`0` (case-insensitive);
+    /// `None` when the field is absent, as browsers omit unchecked boxes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError::TypeMismatch`] for any other value.
+    pub fn flag(&self, name: &str) -> Result<Option<bool>, ParseError> {
+        let Some(value) = self.value(name) else {
+            return Ok(None);
+        };
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "on" | "1" => Ok(Some(true)),
+            "false" | "off" | "0" => Ok(Some(false)),
+            _ => Err(ParseError::type_mismatch(name, "boolean")),
+        }
+    }
+}
+
+/// Trait for types that can be decoded from a form or multipart body.
+///
+/// Derived by `#[derive(Type)]` for structs and internally tagged enums
+/// (`#[mik(tag = "...")]`, the tag read from its own field), using the same
+/// field names and renames as the JSON representation:
+///
+/// | Field type            | Decoded from                                   |
+/// |-----------------------|------------------------------------------------|
+/// | `String`, numbers     | first value of the field                       |
+/// | `bool`                | checkbox value; absent means `false`           |
+/// | `Option<T>`           | absent or empty value is `None`                |
+/// | `Vec<T>`              | every value of the field                       |
+/// | `Upload`              | file part (multipart only)                     |
+/// | `Option<Upload>`      | file part, if present                          |
+/// | `Vec<Upload>`         | every file part of the field                   |
+/// | other `Type` structs  | field value parsed as JSON                     |
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::{FormData, FromForm, ParseError};
+/// struct Login { user: String }
+///
+/// impl FromForm for Login {
+///     fn from_form(form: &FormData) -> Result<Self, ParseError> {
+///         let user = form.value("user").ok_or_else(|| ParseError::missing("user"))?;
+///         Ok(Self { user: user.to_string() })
+///     }
+/// }
+///
+/// let form = FormData::new().with_value("user", "alice");
+/// assert_eq!(Login::from_form(&form).unwrap().user, "alice");
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be read from a form",
+    note = "`#[derive(Type)]` implements `FromForm` for structs and for enums with `#[mik(tag = \"...\")]` and no `content`; other enums have no flat form representation"
+)]
+pub trait FromForm: Sized {
+    /// Decode this type from form fields and uploads.
+    fn from_form(form: &FormData) -> Result<Self, ParseError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Method;
+    use crate::request::Request;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_parts_splits_values_and_files() {
+        let body = b"--B\r\n\
+            Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+            Holiday\r\n\
+            --B\r\n\
+            Content-Disposition: form-data; name=\"photo\"; filename=\"beach.png\"\r\n\
+            Content-Type: image/png\r\n\r\n\
+            PNG\r\n\
+            --B--\r\n";
+        let request = Request::new(
+            Method::Post,
+            "/upload".to_string(),
+            vec![(
+                "content-type".to_string(),
+                "multipart/form-data; boundary=B".to_string(),
+            )],
+            Some(body.to_vec()),
+            HashMap::new(),
+        );
+        let form = FormData::from_parts(&request.multipart().unwrap());
+
+        assert_eq!(form.value("title"), Some("Holiday"));
+        assert!(form.value("photo").is_none());
+        let photo = form.file("photo").unwrap();
+        assert_eq!(photo.filename(), Some("beach.png"));
+        assert_eq!(photo.content_type(), Some("image/png"));
+        assert_eq!(photo.data(), b"PNG");
+    }
+
+    #[test]
+    fn test_flag_values() {
+        let form = FormData::new()
+            .with_value("a", "ON")
+            .with_value("b", "0")
+            .with_value("c", "maybe");
+        assert_eq!(form.flag("a"), Ok(Some(true)));
+        assert_eq!(form.flag("b"), Ok(Some(false)));
+        assert_eq!(form.flag("missing"), Ok(None));
+        assert_eq!(
+            form.flag("c"),
+            Err(ParseError::type_mismatch("c", "boolean"))
+        );
+    }
+
+    #[test]
+    fn test_upload_json_is_metadata_only() {
+        let upload = Upload::new("abc").with_filename("a.txt");
+        assert_eq!(
+            upload.to_json().to_string(),
+            r#"{"content_type":null,"filename":"a.txt","size":3}"#
+        );
+        assert!(Upload::from_json(&json::obj()).is_err());
+    }
+}
@@ -0,0 +1,426 @@
+//! Checks behind `#[field(format = "...")]`.
+//!
+//! `#[derive(Type)]` calls these from `validate()` for string fields whose
+//! format is one of the names below; other formats only appear in the
+//! OpenAPI schema.
+//!
+//! | Format      | Accepts                                              |
+//! |-------------|------------------------------------------------------|
+//! | `email`     | `local@domain`, with a hostname as the domain        |
+//! | `uuid`      | Hyphenated `8-4-4-4-12` hex digits, any case         |
+//! | `date`      | RFC 3339 `full-date`, e.g. `2024-02-29`              |
+//! | `date-time` | RFC 3339 `date-time`, e.g. `2024-02-29T12:00:00Z`    |
+//! | `uri`       | Absolute URI with a scheme, e.g. `https://a.b/c?d`   |
+//! | `ipv4`      | Dotted-quad IPv4 address                             |
+//! | `ipv6`      | IPv6 address                                         |
+//! | `hostname`  | DNS name of 1-63 character labels, at most 253 total |
+//!
+//! ```
+//! use mik_sdk::typed::formats;
+//!
+//! assert!(formats::is_email("alice@example.com"));
+//! assert!(!formats::is_email("alice@"));
+//! assert!(formats::is_date_time("2024-02-29T12:00:00.5+01:00"));
+//! assert!(!formats::is_date("2023-02-29"));
+//! ```
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Check `value` against a named format.
+///
+/// Returns `None` for formats without a runtime check.
+#[must_use]
+pub fn check(format: &str, value: &str) -> Option<bool> {
+    let valid = match format {
+        "email" => is_email(value),
+        "uuid" => is_uuid(value),
+        "date" => is_date(value),
+        "date-time" => is_date_time(value),
+        "uri" => is_uri(value),
+        "ipv4" => is_ipv4(value),
+        "ipv6" => is_ipv6(value),
+        "hostname" => is_hostname(value),
+        _ => return None,
+    };
+    Some(valid)
+}
+
+/// `local@domain`: a dot-separated local part of at most 64 characters and a
+/// hostname domain.
+///
+/// Quoted local parts and IP-literal domains are not accepted.
+#[must_use]
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+    value.len() <= 254
+        && !local.is_empty()
+        && local.len() <= 64
+        && local.split('.').all(|atom| {
+            !atom.is_empty()
+                && atom
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || is_atext(b))
+        })
+        && domain.contains('.')
+        && is_hostname(domain)
+}
+
+/// Non-alphanumeric characters allowed in an unquoted local part (RFC 5322).
+const fn is_atext(b: u8) -> bool {
+    matches!(
+        b,
+        b'!' | b'#'
+            | b'$'
+            | b'%'
+            | b'&'
+            | b'\''
+            | b'*'
+            | b'+'
+            | b'-'
+            | b'/'
+            | b'='
+            | b'?'
+            | b'^'
+            | b'_'
+            | b'`'
+            | b'{'
+            | b'|'
+            | b'}'
+            | b'~'
+    )
+}
+
+/// Hyphenated UUID (`8-4-4-4-12` hex digits), upper or lower case.
+#[must_use]
+pub fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.bytes().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// RFC 3339 `full-date` (`YYYY-MM-DD`), with real month lengths.
+#[must_use]
+pub fn is_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let (Some(year), Some(month), Some(day)) = (
+        digits(&bytes[0..4]),
+        digits(&bytes[5..7]),
+        digits(&bytes[8..10]),
+    ) else {
+        return false;
+    };
+    (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)
+}
+
+/// RFC 3339 `date-time`: `full-date "T" HH:MM:SS[.frac]` then `Z` or `±HH:MM`.
+///
+/// `T` and `Z` may be lowercase; a leap second (`:60`) is accepted.
+#[must_use]
+pub fn is_date_time(value: &str) -> bool {
+    if value.len() < 20 || !value.is_char_boundary(10) {
+        return false;
+    }
+    let (date, rest) = value.split_at(10);
+    let Some(time) = rest.strip_prefix(['T', 't']) else {
+        return false;
+    };
+    is_date(date) && is_time(time)
+}
+
+/// `HH:MM:SS[.frac]` followed by a time zone offset.
+fn is_time(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.len() < 9 || bytes[2] != b':' || bytes[5] != b':' {
+        return false;
+    }
+    let (Some(hour), Some(minute), Some(second)) = (
+        digits(&bytes[0..2]),
+        digits(&bytes[3..5]),
+        digits(&bytes[6..8]),
+    ) else {
+        return false;
+    };
+    if hour > 23 || minute > 59 || second > 60 {
+        return false;
+    }
+
+    let mut offset = &bytes[8..];
+    if let Some(fraction) = offset.strip_prefix(b".") {
+        let len = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+        if len == 0 {
+            return false;
+        }
+        offset = &fraction[len..];
+    }
+    match offset {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', h1, h2, b':', m1, m2] => {
+            matches!((digits(&[*h1, *h2]), digits(&[*m1, *m2])), (Some(h), Some(m)) if h <= 23 && m <= 59)
+        },
+        _ => false,
+    }
+}
+
+/// Parse ASCII digits; `None` if any byte is not a digit.
+fn digits(bytes: &[u8]) -> Option<u32> {
+    bytes.iter().try_fold(0u32, |acc, &b| {
+        b.is_ascii_digit().then(|| acc * 10 + u32::from(b - b'0'))
+    })
+}
+
+const fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        },
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Absolute URI (RFC 3986): a scheme, `:`, then URI characters.
+///
+/// Percent signs must start a `%XX` escape; spaces and other characters
+/// outside the URI grammar are rejected.
+#[must_use]
+pub fn is_uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return false;
+    };
+    let mut scheme_bytes = scheme.bytes();
+    if !scheme_bytes.next().is_some_and(|b| b.is_ascii_alphabetic())
+        || !scheme_bytes.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+    {
+        return false;
+    }
+
+    let rest = rest.as_bytes();
+    let mut i = 0;
+    while i < rest.len() {
+        match rest[i] {
+            b'%' => {
+                if !rest
+                    .get(i + 1..i + 3)
+                    .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                {
+                    return false;
+                }
+                i += 3;
+            },
+            b if b.is_ascii_alphanumeric() || is_uri_char(b) => i += 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Unreserved, reserved and delimiter characters other than `%` (RFC 3986).
+const fn is_uri_char(b: u8) -> bool {
+    matches!(
+        b,
+        b'-' | b'.'
+            | b'_'
+            | b'~'
+            | b':'
+            | b'/'
+            | b'?'
+            | b'#'
+            | b'['
+            | b']'
+            | b'@'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+    )
+}
+
+/// Dotted-quad IPv4 address; leading zeros are rejected.
+#[must_use]
+pub fn is_ipv4(value: &str) -> bool {
+    value.parse::<Ipv4Addr>().is_ok()
+}
+
+/// IPv6 address, including `::` compression and embedded IPv4.
+#[must_use]
+pub fn is_ipv6(value: &str) -> bool {
+    value.parse::<Ipv6Addr>().is_ok()
+}
+
+/// DNS hostname (RFC 1123): dot-separated labels of 1-63 letters, digits and
+/// hyphens, not starting or ending with a hyphen; at most 253 characters.
+#[must_use]
+pub fn is_hostname(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email() {
+        for valid in [
+            "a@b.co",
+            "first.last+tag@sub.example.com",
+            "o'brien@example.org",
+            "x_y-z@a-b.io",
+        ] {
+            assert!(is_email(valid), "{valid}");
+        }
+        for invalid in [
+            "",
+            "plain",
+            "@example.com",
+            "a@",
+            "a@localhost",
+            "a..b@example.com",
+            ".a@example.com",
+            "a.@example.com",
+            "a b@example.com",
+            "a@-example.com",
+            "a@example..com",
+            "a@b@example.com",
+        ] {
+            assert!(!is_email(invalid), "{invalid}");
+        }
+        assert!(!is_email(&format!("{}@example.com", "a".repeat(65))));
+    }
+
+    #[test]
+    fn test_uuid() {
+        assert!(is_uuid("550e8400-e29b-41d4-a716-446655440000"));
+        assert!(is_uuid("550E8400-E29B-41D4-A716-446655440000"));
+        assert!(!is_uuid("550e8400e29b41d4a716446655440000"));
+        assert!(!is_uuid("550e8400-e29b-41d4-a716-44665544000g"));
+        assert!(!is_uuid("{550e8400-e29b-41d4-a716-446655440000}"));
+    }
+
+    #[test]
+    fn test_date() {
+        assert!(is_date("2024-01-31"));
+        assert!(is_date("2024-02-29"));
+        assert!(is_date("2000-02-29"));
+        assert!(!is_date("1900-02-29"));
+        assert!(!is_date("2023-02-29"));
+        assert!(!is_date("2024-04-31"));
+        assert!(!is_date("2024-13-01"));
+        assert!(!is_date("2024-00-10"));
+        assert!(!is_date("2024-1-01"));
+        assert!(!is_date("2024/01/01"));
+    }
+
+    #[test]
+    fn test_date_time() {
+        for valid in [
+            "2024-01-15T10:30:00Z",
+            "2024-01-15t10:30:00z",
+            "2024-01-15T10:30:00.123456+05:30",
+            "2024-01-15T23:59:60-00:00",
+        ] {
+            assert!(is_date_time(valid), "{valid}");
+        }
+        for invalid in [
+            "2024-01-15",
+            "2024-01-15T10:30:00",
+            "2024-01-15 10:30:00Z",
+            "2024-01-15T24:00:00Z",
+            "2024-01-15T10:60:00Z",
+            "2024-01-15T10:30:00.Z",
+            "2024-01-15T10:30:00+0530",
+            "2024-01-15T10:30:00+24:00",
+            "2024-02-30T10:30:00Z",
+            "2024-01-15T10:30Z",
+            "2024-01-1é10:30:00Z",
+        ] {
+            assert!(!is_date_time(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_uri() {
+        for valid in [
+            "https://example.com",
+            "http://user@host:8080/p/a/t/h?query=1&x=%20#frag",
+            "mailto:alice@example.com",
+            "urn:isbn:0451450523",
+            "git+ssh://[::1]/repo",
+        ] {
+            assert!(is_uri(valid), "{valid}");
+        }
+        for invalid in [
+            "",
+            "example.com",
+            "/relative/path",
+            "1http://example.com",
+            "https://exa mple.com",
+            "https://example.com/%zz",
+            "https://example.com/%2",
+            "https://example.com/<tag>",
+        ] {
+            assert!(!is_uri(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_ip_addresses() {
+        assert!(is_ipv4("192.168.0.1"));
+        assert!(!is_ipv4("192.168.0"));
+        assert!(!is_ipv4("256.1.1.1"));
+        assert!(!is_ipv4("01.1.1.1"));
+        assert!(!is_ipv4("::1"));
+
+        assert!(is_ipv6("::1"));
+        assert!(is_ipv6("2001:db8::8a2e:370:7334"));
+        assert!(is_ipv6("::ffff:192.0.2.1"));
+        assert!(!is_ipv6("2001:db8:::1"));
+        assert!(!is_ipv6("192.168.0.1"));
+        assert!(!is_ipv6("[::1]"));
+    }
+
+    #[test]
+    fn test_hostname() {
+        assert!(is_hostname("localhost"));
+        assert!(is_hostname("api.example-site.com"));
+        assert!(is_hostname("xn--bcher-kva.example"));
+        assert!(!is_hostname(""));
+        assert!(!is_hostname("-bad.com"));
+        assert!(!is_hostname("bad-.com"));
+        assert!(!is_hostname("a..b"));
+        assert!(!is_hostname("under_score.com"));
+        assert!(!is_hostname(&format!("{}.com", "a".repeat(64))));
+        assert!(!is_hostname(&["a"; 128].join(".")));
+    }
+
+    #[test]
+    fn test_check_dispatch() {
+        assert_eq!(check("email", "a@b.co"), Some(true));
+        assert_eq!(check("date-time", "nope"), Some(false));
+        assert_eq!(check("password", "anything"), None);
+    }
+}
@@ -4,16 +4,20 @@
 //! - [`Id`] - Built-in path parameter for single ID routes
 //! - [`ParseError`] - Error type for parsing failures
 //! - [`ValidationError`] - Error type for constraint validation
-//! - Traits for parsing JSON, query strings, and path parameters
+//! - [`ValidationErrors`] - Every violation in a value, with field paths
+//! - [`FormData`] and [`Upload`] - Decoded form and multipart bodies
+//! - [`formats`] - Checks behind `#[field(format = "...")]`
+//! - Traits for parsing JSON, forms, query strings, path parameters, headers and cookies
 //!
 //! # Newtypes and Validation
 //!
-//! This SDK provides structural types ([`Id`], derive macros) but intentionally
-//! delegates field validation to external crates like `garde`.
+//! This SDK provides structural types ([`Id`], derive macros) and enforces the
+//! field constraints an OpenAPI schema can describe (`min`, `max`, `format`,
+//! `pattern`), but intentionally delegates other validation to external crates
+//! like `garde`.
 //!
-//! **Why?** Validation requirements vary widely between projects. Some need strict
-//! email validation, others accept any string. By separating concerns:
-//! - SDK handles parsing and type conversion
+//! **Why?** Validation requirements vary widely between projects. By separating concerns:
+//! - SDK handles parsing, type conversion and the documented schema
 //! - Validation crates handle domain-specific rules
 //!
 //! # Example: ParseError and ValidationError
@@ -31,11 +35,18 @@
 //! assert_eq!(too_short.constraint(), "min");
 //! ```
 
+mod form;
+pub mod formats;
 mod parse_error;
+#[doc(hidden)]
+pub mod pattern;
 mod validation_error;
+mod validation_errors;
 
+pub use form::{FormData, FromForm, Upload};
 pub use parse_error::ParseError;
 pub use validation_error::ValidationError;
+pub use validation_errors::ValidationErrors;
 
 use crate::json::JsonValue;
 use std::collections::HashMap;
@@ -270,6 +281,43 @@ pub trait FromPath: Sized {
     fn from_params(params: &HashMap<String, String>) -> Result<Self, ParseError>;
 }
 
+/// Trait for types that can be parsed from request headers.
+///
+/// Usually derived with `#[derive(Headers)]`, which matches header names
+/// case-insensitively and uses the first value of a repeated header.
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::{FromHeaders, ParseError};
+/// struct ApiKey { key: String }
+///
+/// impl FromHeaders for ApiKey {
+///     fn from_headers(headers: &[(String, String)]) -> Result<Self, ParseError> {
+///         headers.iter()
+///             .find(|(k, _)| k.eq_ignore_ascii_case("x-api-key"))
+///             .map(|(_, v)| Self { key: v.clone() })
+///             .ok_or_else(|| ParseError::missing("x-api-key"))
+///     }
+/// }
+///
+/// let headers = vec![("X-Api-Key".to_string(), "secret".to_string())];
+/// assert_eq!(ApiKey::from_headers(&headers).unwrap().key, "secret");
+/// ```
+pub trait FromHeaders: Sized {
+    /// Parse this type from request headers.
+    fn from_headers(headers: &[(String, String)]) -> Result<Self, ParseError>;
+}
+
+/// Trait for types that can be parsed from request cookies.
+///
+/// Usually derived with `#[derive(Cookies)]`. Receives the cookies parsed
+/// from the `Cookie` header, as returned by [`Request::cookies`](crate::Request::cookies).
+pub trait FromCookies: Sized {
+    /// Parse this type from request cookies.
+    fn from_cookies(cookies: &[(String, String)]) -> Result<Self, ParseError>;
+}
+
 /// Trait for types that can be validated against constraints.
 ///
 /// Implement this for types with field constraints. Usually derived with `#[derive(Type)]`.
@@ -303,6 +351,20 @@ pub trait FromPath: Sized {
 pub trait Validate {
     /// Validate this value against its constraints.
     fn validate(&self) -> Result<(), ValidationError>;
+
+    /// Validate this value and everything nested in it, collecting every
+    /// violation instead of stopping at the first.
+    ///
+    /// `#[derive(Type)]` walks nested types and `Vec` elements, reporting
+    /// paths like `items[3].name`. The default reports the error from
+    /// [`validate`](Self::validate), if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns all violations found.
+    fn validate_all(&self) -> Result<(), ValidationErrors> {
+        self.validate().map_err(ValidationErrors::from)
+    }
 }
 
 /// Trait for types that can generate their OpenAPI schema.
@@ -344,6 +406,20 @@ pub trait OpenApiSchema {
         "[]"
     }
 
+    /// Get OpenAPI header parameters array for Headers types.
+    ///
+    /// Only meaningful for types derived with `#[derive(Headers)]`.
+    fn openapi_header_params() -> &'static str {
+        "[]"
+    }
+
+    /// Get OpenAPI cookie parameters array for Cookies types.
+    ///
+    /// Only meaningful for types derived with `#[derive(Cookies)]`.
+    fn openapi_cookie_params() -> &'static str {
+        "[]"
+    }
+
     /// Get schemas for nested types referenced by this type via `$ref`.
     ///
     /// Returns a comma-separated list of `"TypeName":schema` entries (no outer braces).
@@ -589,6 +665,18 @@ mod tests {
         assert!(matches!(err, ParseError::Custom { .. }));
     }
 
+    #[test]
+    fn test_parse_error_unknown_fields() {
+        let err = ParseError::unknown_fields(vec!["admin".to_string(), "role".to_string()]);
+        assert_eq!(err.field(), "");
+        assert_eq!(err.message(), "unknown fields `admin`, `role`");
+        assert!(matches!(err, ParseError::UnknownFields { ref keys, .. } if keys.len() == 2));
+
+        let err = ParseError::unknown_fields(vec!["zip".to_string()]).with_path("address");
+        assert_eq!(err.field(), "address");
+        assert_eq!(err.message(), "unknown field `zip` in `address`");
+    }
+
     #[test]
     fn test_parse_error_custom_with_string() {
         let msg = String::from("Custom error message");
@@ -50,6 +50,15 @@ pub enum ParseError {
         expected: String,
     },
 
+    /// The input object has keys the type doesn't define
+    /// (`#[mik(deny_unknown_fields)]`).
+    UnknownFields {
+        /// The name of the object field (or empty at the top level)
+        field: String,
+        /// The unexpected keys
+        keys: Vec<String>,
+    },
+
     /// A custom parse error with a user-defined message.
     Custom {
         /// The name of the field (or empty for general errors)
@@ -89,6 +98,16 @@ impl ParseError {
         }
     }
 
+    /// Create an error for unexpected object keys.
+    #[inline]
+    #[must_use]
+    pub const fn unknown_fields(keys: Vec<String>) -> Self {
+        Self::UnknownFields {
+            field: String::new(),
+            keys,
+        }
+    }
+
     /// Create a custom parse error.
     #[inline]
     #[must_use]
@@ -107,6 +126,7 @@ impl ParseError {
             Self::MissingField { field }
             | Self::InvalidFormat { field, .. }
             | Self::TypeMismatch { field, .. }
+            | Self::UnknownFields { field, .. }
             | Self::Custom { field, .. } => field,
         }
     }
@@ -144,6 +164,14 @@ impl ParseError {
                 field: format!("{parent}.{field}"),
                 expected,
             },
+            Self::UnknownFields { field, keys } => Self::UnknownFields {
+                field: if field.is_empty() {
+                    parent.to_string()
+                } else {
+                    format!("{parent}.{field}")
+                },
+                keys,
+            },
             Self::Custom { field, message } => Self::Custom {
                 field: format!("{parent}.{field}"),
                 message,
@@ -164,6 +192,20 @@ impl std::fmt::Display for ParseError {
             Self::TypeMismatch { field, expected } => {
                 write!(f, "expected {expected} for field `{field}`")
             },
+            Self::UnknownFields { field, keys } => {
+                let plural = if keys.len() == 1 { "" } else { "s" };
+                write!(f, "unknown field{plural} ")?;
+                for (i, key) in keys.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "`{key}`")?;
+                }
+                if !field.is_empty() {
+                    write!(f, " in `{field}`")?;
+                }
+                Ok(())
+            },
             Self::Custom { message, .. } => {
                 write!(f, "{message}")
             },
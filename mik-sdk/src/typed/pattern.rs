@@ -0,0 +1,508 @@
+//! Matcher behind `#[field(pattern = "...")]`.
+//!
+//! `#[derive(Type)]` parses the pattern at compile time and emits it as a
+//! static [`Node`] tree, so nothing is compiled at runtime. This is an
+//! implementation detail of the derive and should not be used directly.
+//!
+//! Matching follows ECMA-262 (the dialect OpenAPI uses): the pattern may
+//! match anywhere in the value unless anchored with `^` / `$`, and
+//! quantifiers are greedy with backtracking. Backtracking uses an explicit
+//! stack, so long values never recurse. Pathological patterns give up after
+//! an amount of work proportional to the value's length and report
+//! [`LimitExceeded`] rather than a mismatch.
+
+/// Backtracking steps allowed per character of the value.
+const STEPS_PER_CHAR: usize = 1_000;
+
+/// One element of a compiled pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Node {
+    /// A literal character.
+    Char(char),
+    /// `.` - any character except a line break.
+    Any,
+    /// `[...]`, `\d`, `\w` or `\s`: a set of inclusive character ranges.
+    Class {
+        /// Match characters outside the ranges instead.
+        negated: bool,
+        /// Inclusive ranges.
+        ranges: &'static [(char, char)],
+    },
+    /// `^` - start of the value.
+    Start,
+    /// `$` - end of the value.
+    End,
+    /// `(a|b)` - alternative sequences.
+    Group(&'static [&'static [Self]]),
+    /// `*`, `+`, `?`, `{n}`, `{n,}` or `{n,m}` applied to a node.
+    Repeat {
+        /// The repeated node.
+        node: &'static Self,
+        /// Minimum repetitions.
+        min: u32,
+        /// Maximum repetitions (`None` for unbounded).
+        max: Option<u32>,
+    },
+}
+
+/// Matching gave up before deciding whether the value matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LimitExceeded;
+
+/// Whether `pattern` matches somewhere in `text`.
+///
+/// # Errors
+///
+/// Returns [`LimitExceeded`] if matching needs more than
+/// `STEPS_PER_CHAR` steps per character of `text`.
+pub fn is_match(pattern: &[Node], text: &str) -> Result<bool, LimitExceeded> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut matcher = Matcher {
+        text: &chars,
+        steps: STEPS_PER_CHAR.saturating_mul(chars.len() + 1),
+        frames: Vec::new(),
+        choices: Vec::new(),
+    };
+    let starts = if pattern.first() == Some(&Node::Start) {
+        0
+    } else {
+        chars.len()
+    };
+    for start in 0..=starts {
+        if matcher.run(pattern, start)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// What is left to match: an index into `Matcher::frames`, or `None` once
+/// the whole pattern has matched.
+type Cont = Option<usize>;
+
+#[derive(Clone, Copy)]
+enum Goal<'p> {
+    /// Match these nodes in order.
+    Seq(&'p [Node]),
+    /// Iteration `count` of a repeat, started at `from`, just ended.
+    Repeat {
+        node: &'p Node,
+        min: u32,
+        max: Option<u32>,
+        count: u32,
+        from: usize,
+    },
+}
+
+/// A goal followed by the rest of the match; frames form linked lists that
+/// share their tails.
+#[derive(Clone, Copy)]
+struct Frame<'p> {
+    goal: Goal<'p>,
+    next: Cont,
+}
+
+/// A saved alternative. Frames pushed after it are unreachable once it is
+/// resumed, so the arena is cut back to `frames`.
+struct Choice {
+    pos: usize,
+    cont: Cont,
+    frames: usize,
+}
+
+struct Matcher<'t, 'p> {
+    text: &'t [char],
+    /// Steps left for the whole value.
+    steps: usize,
+    frames: Vec<Frame<'p>>,
+    choices: Vec<Choice>,
+}
+
+impl<'p> Matcher<'_, 'p> {
+    /// Match `pattern` starting at `start`, trying alternatives in order.
+    fn run(&mut self, pattern: &'p [Node], start: usize) -> Result<bool, LimitExceeded> {
+        self.frames.clear();
+        self.choices.clear();
+        let root = self.push(Goal::Seq(pattern), None);
+        self.save(start, Some(root));
+
+        while let Some(choice) = self.choices.pop() {
+            self.frames.truncate(choice.frames);
+            if self.resume(choice.pos, choice.cont)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Follow one path, saving the alternatives it skips, until it matches
+    /// or fails.
+    fn resume(&mut self, mut pos: usize, mut cont: Cont) -> Result<bool, LimitExceeded> {
+        loop {
+            self.steps = self.steps.checked_sub(1).ok_or(LimitExceeded)?;
+            let Some(frame) = cont.and_then(|index| self.frames.get(index).copied()) else {
+                return Ok(true);
+            };
+            cont = match frame.goal {
+                Goal::Seq([]) => frame.next,
+                Goal::Seq([node, rest @ ..]) => {
+                    let next = if rest.is_empty() {
+                        frame.next
+                    } else {
+                        Some(self.push(Goal::Seq(rest), frame.next))
+                    };
+                    let Some((end, next)) = self.step(node, pos, next) else {
+                        return Ok(false);
+                    };
+                    pos = end;
+                    next
+                },
+                Goal::Repeat {
+                    node,
+                    min,
+                    max,
+                    count,
+                    from,
+                } => {
+                    // An empty iteration past the minimum would repeat forever
+                    if pos == from && count > min {
+                        return Ok(false);
+                    }
+                    self.repeat(node, min, max, count, pos, frame.next)
+                },
+            };
+        }
+    }
+
+    /// Match one node at `pos`, returning where the rest continues.
+    fn step(&mut self, node: &'p Node, pos: usize, next: Cont) -> Option<(usize, Cont)> {
+        match node {
+            Node::Start => (pos == 0).then_some((pos, next)),
+            Node::End => (pos == self.text.len()).then_some((pos, next)),
+            Node::Group(alternatives) => {
+                let (first, others) = alternatives.split_first()?;
+                for alternative in others.iter().rev() {
+                    let cont = self.push(Goal::Seq(alternative), next);
+                    self.save(pos, Some(cont));
+                }
+                Some((pos, Some(self.push(Goal::Seq(first), next))))
+            },
+            Node::Repeat { node, min, max } if single_char(node) => {
+                // Greedy: take the longest run, then give back one character at a time
+                let limit = max.map_or(usize::MAX, |max| max as usize);
+                let count = self
+                    .text
+                    .iter()
+                    .skip(pos)
+                    .take(limit)
+                    .take_while(|&&c| matches_char(node, c))
+                    .count();
+                let min = *min as usize;
+                if count < min {
+                    return None;
+                }
+                for end in pos + min..pos + count {
+                    self.save(end, next);
+                }
+                Some((pos + count, next))
+            },
+            Node::Repeat { node, min, max } => {
+                Some((pos, self.repeat(node, *min, *max, 0, pos, next)))
+            },
+            _ => self
+                .text
+                .get(pos)
+                .is_some_and(|&c| matches_char(node, c))
+                .then_some((pos + 1, next)),
+        }
+    }
+
+    /// After `count` iterations ending at `pos`: try another one first,
+    /// saving the option to stop here.
+    fn repeat(
+        &mut self,
+        node: &'p Node,
+        min: u32,
+        max: Option<u32>,
+        count: u32,
+        pos: usize,
+        next: Cont,
+    ) -> Cont {
+        if max.is_some_and(|max| count >= max) {
+            return next;
+        }
+        if count >= min {
+            self.save(pos, next);
+        }
+        let after = self.push(
+            Goal::Repeat {
+                node,
+                min,
+                max,
+                count: count + 1,
+                from: pos,
+            },
+            next,
+        );
+        Some(self.push(Goal::Seq(std::slice::from_ref(node)), Some(after)))
+    }
+
+    fn push(&mut self, goal: Goal<'p>, next: Cont) -> usize {
+        self.frames.push(Frame { goal, next });
+        self.frames.len() - 1
+    }
+
+    fn save(&mut self, pos: usize, cont: Cont) {
+        self.choices.push(Choice {
+            pos,
+            cont,
+            frames: self.frames.len(),
+        });
+    }
+}
+
+const fn single_char(node: &Node) -> bool {
+    matches!(node, Node::Char(_) | Node::Any | Node::Class { .. })
+}
+
+fn matches_char(node: &Node, c: char) -> bool {
+    match node {
+        Node::Char(expected) => c == *expected,
+        Node::Any => !matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}'),
+        Node::Class { negated, ranges } => {
+            ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&c))
+                != *negated
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGIT: Node = Node::Class {
+        negated: false,
+        ranges: &[('0', '9')],
+    };
+    const LOWER: Node = Node::Class {
+        negated: false,
+        ranges: &[('a', 'z')],
+    };
+
+    #[test]
+    fn test_unanchored_search() {
+        let pattern = [Node::Char('b'), Node::Char('c')];
+        assert_eq!(is_match(&pattern, "abcd"), Ok(true));
+        assert_eq!(is_match(&pattern, "acbd"), Ok(false));
+        assert_eq!(is_match(&[], ""), Ok(true));
+    }
+
+    #[test]
+    fn test_anchors() {
+        // ^[a-z]+$
+        let pattern = [
+            Node::Start,
+            Node::Repeat {
+                node: &LOWER,
+                min: 1,
+                max: None,
+            },
+            Node::End,
+        ];
+        assert_eq!(is_match(&pattern, "hello"), Ok(true));
+        assert_eq!(is_match(&pattern, "hello1"), Ok(false));
+        assert_eq!(is_match(&pattern, ""), Ok(false));
+    }
+
+    #[test]
+    fn test_bounded_repeat_backtracks() {
+        // ^\d{2,3}3$
+        let pattern = [
+            Node::Start,
+            Node::Repeat {
+                node: &DIGIT,
+                min: 2,
+                max: Some(3),
+            },
+            Node::Char('3'),
+            Node::End,
+        ];
+        assert_eq!(is_match(&pattern, "123"), Ok(true));
+        assert_eq!(is_match(&pattern, "1233"), Ok(true));
+        assert_eq!(is_match(&pattern, "13"), Ok(false));
+        assert_eq!(is_match(&pattern, "12343"), Ok(false));
+    }
+
+    #[test]
+    fn test_groups_and_alternation() {
+        // ^(ab|c)*d$
+        let pattern = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Group(&[&[Node::Char('a'), Node::Char('b')], &[Node::Char('c')]]),
+                min: 0,
+                max: None,
+            },
+            Node::Char('d'),
+            Node::End,
+        ];
+        assert_eq!(is_match(&pattern, "d"), Ok(true));
+        assert_eq!(is_match(&pattern, "abcabd"), Ok(true));
+        assert_eq!(is_match(&pattern, "abad"), Ok(false));
+    }
+
+    #[test]
+    fn test_negated_class_and_any() {
+        let not_digit = Node::Class {
+            negated: true,
+            ranges: &[('0', '9')],
+        };
+        assert_eq!(
+            is_match(&[Node::Start, not_digit, Node::End], "é"),
+            Ok(true)
+        );
+        assert_eq!(
+            is_match(&[Node::Start, not_digit, Node::End], "5"),
+            Ok(false)
+        );
+        assert_eq!(is_match(&[Node::Any], "x"), Ok(true));
+        assert_eq!(is_match(&[Node::Any], "\n"), Ok(false));
+    }
+
+    #[test]
+    fn test_empty_group_repeat_terminates() {
+        // ^(a?)*$
+        let pattern = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Group(&[&[Node::Repeat {
+                    node: &Node::Char('a'),
+                    min: 0,
+                    max: Some(1),
+                }]]),
+                min: 0,
+                max: None,
+            },
+            Node::End,
+        ];
+        assert_eq!(is_match(&pattern, "aaa"), Ok(true));
+        assert_eq!(is_match(&pattern, "aab"), Ok(false));
+    }
+
+    #[test]
+    fn test_long_values_do_not_overflow() {
+        // ^(a|b)*$ and ^.*$
+        let group = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Group(&[&[Node::Char('a')], &[Node::Char('b')]]),
+                min: 0,
+                max: None,
+            },
+            Node::End,
+        ];
+        let any = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Any,
+                min: 0,
+                max: None,
+            },
+            Node::End,
+        ];
+        let long = "ab".repeat(50_000);
+        assert_eq!(is_match(&group, &long), Ok(true));
+        assert_eq!(is_match(&group, &format!("{long}c")), Ok(false));
+        assert_eq!(is_match(&any, &long), Ok(true));
+    }
+
+    #[test]
+    fn test_long_valid_values_with_repeated_groups() {
+        const WORD: Node = Node::Class {
+            negated: false,
+            ranges: &[('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')],
+        };
+        const SPACE: Node = Node::Class {
+            negated: false,
+            ranges: &[(' ', ' '), ('\t', '\t')],
+        };
+
+        // ^([a-z]+,)*[a-z]+$
+        let tags = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Group(&[&[
+                    Node::Repeat {
+                        node: &LOWER,
+                        min: 1,
+                        max: None,
+                    },
+                    Node::Char(','),
+                ]]),
+                min: 0,
+                max: None,
+            },
+            Node::Repeat {
+                node: &LOWER,
+                min: 1,
+                max: None,
+            },
+            Node::End,
+        ];
+        let value = vec!["tag"; 600].join(",");
+        assert_eq!(is_match(&tags, &value), Ok(true));
+        assert_eq!(is_match(&tags, &format!("{value},")), Ok(false));
+
+        // ^(\w+\s?)+$
+        let words = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Group(&[&[
+                    Node::Repeat {
+                        node: &WORD,
+                        min: 1,
+                        max: None,
+                    },
+                    Node::Repeat {
+                        node: &SPACE,
+                        min: 0,
+                        max: Some(1),
+                    },
+                ]]),
+                min: 1,
+                max: None,
+            },
+            Node::End,
+        ];
+        let value = vec!["word"; 600].join(" ");
+        assert_eq!(is_match(&words, &value), Ok(true));
+    }
+
+    #[test]
+    fn test_catastrophic_pattern_gives_up() {
+        // ^(a*)*b$ against "aaaa...c"
+        let pattern = [
+            Node::Start,
+            Node::Repeat {
+                node: &Node::Group(&[&[Node::Repeat {
+                    node: &Node::Char('a'),
+                    min: 0,
+                    max: None,
+                }]]),
+                min: 0,
+                max: None,
+            },
+            Node::Char('b'),
+            Node::End,
+        ];
+        assert_eq!(
+            is_match(&pattern, &format!("{}c", "a".repeat(40))),
+            Err(LimitExceeded)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! ValidationErrors collection and implementations.
+
+use std::collections::BTreeMap;
+
+use super::ValidationError;
+use crate::json::{self, JsonValue, ToJson};
+
+/// Every constraint violation found in a value, in the order they were found.
+///
+/// Returned by [`Validate::validate_all`](super::Validate::validate_all).
+/// Errors in nested types and `Vec` elements carry their full path, such as
+/// `address.city` or `items[3].name`.
+///
+/// # Example
+///
+/// ```
+/// # use mik_sdk::typed::{ValidationError, ValidationErrors};
+/// let mut item = ValidationErrors::new();
+/// item.push(ValidationError::min("name", 1));
+///
+/// let mut errors = ValidationErrors::new();
+/// errors.push(ValidationError::format("email", "email"));
+/// errors.nest("items[3]", item);
+///
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors.field("items[3].name")[0].constraint(), "min");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    /// An empty collection.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Add a violation.
+    pub fn push(&mut self, error: ValidationError) {
+        self.errors.push(error);
+    }
+
+    /// Add the violations of a nested value, prefixing their fields with `path`
+    /// (kept as they are when `path` is empty).
+    pub fn nest(&mut self, path: &str, errors: Self) {
+        if path.is_empty() {
+            self.errors.extend(errors.errors);
+        } else {
+            self.errors
+                .extend(errors.errors.into_iter().map(|error| error.with_path(path)));
+        }
+    }
+
+    /// Whether no violations were found.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Number of violations.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// The violations, in the order they were found.
+    #[must_use]
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    /// Iterate over the violations.
+    pub fn iter(&self) -> std::slice::Iter<'_, ValidationError> {
+        self.errors.iter()
+    }
+
+    /// The violations of one field path, e.g. `"items[3].name"`.
+    #[must_use]
+    pub fn field(&self, path: &str) -> Vec<&ValidationError> {
+        self.errors.iter().filter(|e| e.field() == path).collect()
+    }
+
+    /// The violations grouped by field path.
+    #[must_use]
+    pub fn by_field(&self) -> BTreeMap<&str, Vec<&ValidationError>> {
+        let mut fields: BTreeMap<&str, Vec<&ValidationError>> = BTreeMap::new();
+        for error in &self.errors {
+            fields.entry(error.field()).or_default().push(error);
+        }
+        fields
+    }
+
+    /// `Ok(())` if empty, otherwise `Err(self)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` when it holds at least one violation.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl From<ValidationError> for ValidationErrors {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            errors: vec![error],
+        }
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = ValidationError;
+    type IntoIter = std::vec::IntoIter<ValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationErrors {
+    type Item = &'a ValidationError;
+    type IntoIter = std::slice::Iter<'a, ValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+/// An array of `{"field", "constraint", "message"}` objects.
+impl ToJson for ValidationErrors {
+    fn to_json(&self) -> JsonValue {
+        self.errors.iter().fold(json::arr(), |errors, error| {
+            errors.push(
+                json::obj()
+                    .set("field", json::str(error.field()))
+                    .set("constraint", json::str(error.constraint()))
+                    .set("message", json::str(error.to_string())),
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nest_builds_indexed_paths() {
+        let mut inner = ValidationErrors::new();
+        inner.push(ValidationError::min("name", 1));
+        let mut item = ValidationErrors::new();
+        item.nest("items[3]", inner);
+        let mut errors = ValidationErrors::new();
+        errors.nest("order", item);
+
+        assert_eq!(errors.errors()[0].field(), "order.items[3].name");
+
+        let mut flat = ValidationErrors::new();
+        flat.nest("", errors);
+        assert_eq!(flat.errors()[0].field(), "order.items[3].name");
+    }
+
+    #[test]
+    fn test_by_field_groups_violations() {
+        let mut errors = ValidationErrors::new();
+        errors.push(ValidationError::min("name", 2));
+        errors.push(ValidationError::format("email", "email"));
+        errors.push(ValidationError::pattern("name", "^[a-z]+$"));
+
+        let fields = errors.by_field();
+        assert_eq!(
+            fields.keys().copied().collect::<Vec<_>>(),
+            ["email", "name"]
+        );
+        assert_eq!(fields["name"].len(), 2);
+        assert_eq!(errors.field("name")[1].constraint(), "pattern");
+        assert!(errors.field("missing").is_empty());
+    }
+
+    #[test]
+    fn test_into_result() {
+        assert_eq!(ValidationErrors::new().into_result(), Ok(()));
+        let errors = ValidationErrors::from(ValidationError::max("n", 1));
+        assert_eq!(errors.clone().into_result(), Err(errors));
+    }
+
+    #[test]
+    fn test_json_and_display() {
+        let mut errors = ValidationErrors::new();
+        errors.push(ValidationError::min("name", 2));
+        errors.push(ValidationError::format("email", "email"));
+
+        assert_eq!(
+            errors.to_json().to_string(),
+            r#"[{"constraint":"min","field":"name","message":"`name` must be at least 2"},{"constraint":"format","field":"email","message":"`email` must be a valid email"}]"#
+        );
+        assert_eq!(
+            errors.to_string(),
+            "`name` must be at least 2; `email` must be a valid email"
+        );
+    }
+}
@@ -93,6 +93,8 @@ impl ClientRequest {
             Method::Patch => http_types::Method::Patch,
             Method::Head => http_types::Method::Head,
             Method::Options => http_types::Method::Options,
+            Method::Trace => http_types::Method::Trace,
+            Method::Other(name) => http_types::Method::Other(name.clone()),
         };
         outgoing_req
             .set_method(&wasi_method)
@@ -297,10 +297,12 @@ proptest! {
             Just(Method::Patch),
             Just(Method::Head),
             Just(Method::Options),
+            Just(Method::Trace),
+            "[A-Z]{3,12}".prop_map(Method::Other),
         ]
     ) {
         let req = Request::new(
-            method,
+            method.clone(),
             "/".to_string(),
             vec![],
             None,
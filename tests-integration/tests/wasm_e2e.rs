@@ -482,9 +482,9 @@ fn test_501_unsupported_method_connect() {
 
 #[test]
 #[ignore = "requires pre-built WASM components"]
-fn test_501_unsupported_method_trace() {
+fn test_405_trace_without_route() {
     run_on_all_runtimes("hello-world-service.wasm", |server| {
-        // TRACE method is not supported by mik-bridge
+        // TRACE reaches the handler, which has no TRACE route for "/"
         let client = std::net::TcpStream::connect(format!("127.0.0.1:{}", server.port));
 
         if let Ok(mut stream) = client {
@@ -502,10 +502,10 @@ fn test_501_unsupported_method_trace() {
             let mut response = [0u8; 1024];
             if let Ok(n) = stream.read(&mut response) {
                 let response_str = String::from_utf8_lossy(&response[..n]);
-                // Should contain 501 status
+                // Should contain 405 status
                 assert!(
-                    response_str.contains("501") || response_str.contains("Not Implemented"),
-                    "Expected 501 for TRACE, got: {response_str}"
+                    response_str.contains("405") || response_str.contains("Method Not Allowed"),
+                    "Expected 405 for TRACE, got: {response_str}"
                 );
             }
         }